@@ -0,0 +1,150 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+// Benchmarks over the boards of benches/corpus.toml. Run them all with
+// `cargo bench`, or only those whose name contains a filter with
+// `cargo bench -- solve/a-star`.
+
+use eight_puzzle_solver::{
+    a_star::SearchConfig,
+    algorithms::Algorithm,
+    board::{Board, BoardSet},
+    goal::GoalContext,
+    puzzles::{Collection, Format},
+};
+use std::{
+    collections::HashSet,
+    env,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const CORPUS: &str = include_str!("corpus.toml");
+
+// How far from the goal the boards of the visited set benchmarks go.
+const VISITED_DEPTH: usize = 14;
+
+// Each benchmark runs this long before it is measured, to warm up caches and
+// to pick how many iterations make up a sample.
+const WARM_UP: Duration = Duration::from_millis(300);
+const SAMPLES: usize = 20;
+const SAMPLE_TIME: Duration = Duration::from_millis(50);
+
+fn main() {
+    // cargo passes `--bench`, anything else is a filter
+    let filter = env::args().skip(1).find(|arg| !arg.starts_with("--"));
+    let goal = GoalContext::standard();
+    let corpus: Vec<(String, Vec<String>, Board)> = Collection::parse(CORPUS, Format::Toml)
+        .expect("invalid benchmark corpus")
+        .puzzles
+        .into_iter()
+        .map(|puzzle| {
+            let board = puzzle.board(&goal).expect("invalid board in the corpus");
+            return (puzzle.name, puzzle.tags, board);
+        })
+        .collect();
+
+    for (name, _, board) in &corpus {
+        bench(&filter, &format!("distance/{}", name), || {
+            black_box(black_box(board).distance(goal.goal()));
+        });
+    }
+    for (name, _, board) in &corpus {
+        bench(&filter, &format!("successors/{}", name), || {
+            black_box(black_box(board).get_possible_next_states());
+        });
+    }
+    // the visited set of a search: boards keyed by their Zobrist hash, against
+    // the same boards packed into a number and hashed as usual
+    let nearby = within(goal.goal(), VISITED_DEPTH);
+    bench(&filter, "visited/zobrist", || {
+        let mut visited = BoardSet::default();
+        for board in &nearby {
+            visited.insert(*board);
+            black_box(visited.contains(black_box(board)));
+        }
+    });
+    bench(&filter, "visited/packed", || {
+        let mut visited = HashSet::new();
+        for board in &nearby {
+            visited.insert(board.packed());
+            black_box(visited.contains(&black_box(board).packed()));
+        }
+    });
+    for algorithm in Algorithm::ALL {
+        for (name, tags, board) in &corpus {
+            // iterative deepening gives up on the hard boards after a long time
+            if algorithm == Algorithm::Iddfs && tags.iter().any(|tag| tag == "hard") {
+                continue;
+            }
+            bench(&filter, &format!("solve/{}/{}", algorithm, name), || {
+                black_box(algorithm.solve(*board, &goal, SearchConfig::default()));
+            });
+        }
+    }
+}
+
+// Times `routine` and prints the fastest, median and slowest time per
+// iteration over the samples.
+fn bench(filter: &Option<String>, name: &str, mut routine: impl FnMut()) {
+    if filter
+        .as_ref()
+        .is_some_and(|filter| !name.contains(filter.as_str()))
+    {
+        return;
+    }
+    let started = Instant::now();
+    let mut iterations: u32 = 0;
+    while started.elapsed() < WARM_UP {
+        routine();
+        iterations += 1;
+    }
+    let per_iteration = started.elapsed() / iterations;
+    let batch = (SAMPLE_TIME.as_nanos() / per_iteration.as_nanos().max(1)).max(1) as u32;
+
+    let mut samples: Vec<Duration> = (0..SAMPLES)
+        .map(|_| {
+            let started = Instant::now();
+            for _ in 0..batch {
+                routine();
+            }
+            return started.elapsed() / batch;
+        })
+        .collect();
+    samples.sort();
+    println!(
+        "{:<32} time: [{:>10} {:>10} {:>10}]",
+        name,
+        format_duration(samples[0]),
+        format_duration(samples[SAMPLES / 2]),
+        format_duration(samples[SAMPLES - 1])
+    );
+}
+
+fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    return match nanos {
+        n if n < 1e3 => format!("{:.1} ns", n),
+        n if n < 1e6 => format!("{:.2} µs", n / 1e3),
+        n if n < 1e9 => format!("{:.2} ms", n / 1e6),
+        n => format!("{:.2} s", n / 1e9),
+    };
+}
+
+// Every board at most `depth` moves away from `board`.
+fn within(board: &Board, depth: usize) -> Vec<Board> {
+    let mut seen = BoardSet::default();
+    seen.insert(*board);
+    let mut boards = vec![*board];
+    let mut layer = vec![*board];
+    for _ in 0..depth {
+        layer = layer
+            .iter()
+            .flat_map(|b| b.get_possible_next_states())
+            .map(|n| n.board)
+            .filter(|b| seen.insert(*b))
+            .collect();
+        boards.extend(&layer);
+    }
+    return boards;
+}
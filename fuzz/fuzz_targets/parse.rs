@@ -0,0 +1,39 @@
+#![no_main]
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+// Every way a board or a puzzle collection comes in as text: typed or pasted
+// boards, the command line, collection files. None of them may panic.
+
+use eight_puzzle_solver::{
+    board::Board,
+    goal::GoalContext,
+    puzzles::{Collection, Format},
+    validate::{self, CellOrder},
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let _ = text.parse::<Board>();
+    let _ = validate::parse(text);
+    for (rows, cols) in [(2, 2), (3, 3), (2, 4), (4, 4)] {
+        let goal = GoalContext::for_size(rows, cols);
+        for order in CellOrder::ALL {
+            if let Ok(board) = validate::check_import(text, &goal, order) {
+                // whatever is accepted reads back the same
+                let code = board.code();
+                assert_eq!(validate::check_import(&code, &goal, CellOrder::Auto), Ok(board));
+            }
+        }
+    }
+    for format in [Format::Toml, Format::Json] {
+        if let Ok(collection) = Collection::parse(text, format) {
+            for puzzle in &collection.puzzles {
+                let _ = puzzle.board(&GoalContext::standard());
+            }
+        }
+    }
+});
@@ -0,0 +1,46 @@
+#![no_main]
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+// Arbitrary boards, valid or not, given straight to every algorithm. A search
+// may give up or reject the board, but must not panic, and what it returns
+// must be a solution.
+
+use eight_puzzle_solver::{
+    a_star::{Progress, SearchConfig},
+    algorithms::{Algorithm, SearchOutcome},
+    board::Board,
+    error::SolverError,
+    goal::GoalContext,
+    verify,
+};
+use libfuzzer_sys::fuzz_target;
+
+// Keeps each run short; hard boards only need to get this far without panicking.
+const MAX_EXPANDED: usize = 10_000;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&shape, cells)) = data.split_first() else {
+        return;
+    };
+    let rows = 2 + (shape & 0b11) as usize % 3; // 2-4
+    let cols = 2 + (shape >> 2 & 0b11) as usize % 3;
+    // 0 is the empty cell; duplicates, gaps and missing blanks are all fine
+    let cells: Vec<Option<i64>> = cells
+        .iter()
+        .take(rows * cols)
+        .map(|&tile| (tile != 0).then_some(tile as i64))
+        .collect();
+    let board = Board::from_cells(rows, cols, &cells);
+    let goal = GoalContext::for_size(rows, cols);
+    for algorithm in Algorithm::ALL {
+        let mut observer = |progress: &Progress| progress.expanded < MAX_EXPANDED;
+        match algorithm.solve_observed(board, &goal, SearchConfig::default(), &mut observer) {
+            SearchOutcome::Solved(solution) => {
+                assert_eq!(verify::verify_with_goal(&board, &solution.path, &goal), Ok(()));
+            }
+            SearchOutcome::Error(SolverError::InvalidSolution(e)) => panic!("{}", e),
+            _ => {}
+        }
+    }
+});
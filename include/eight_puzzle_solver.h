@@ -0,0 +1,63 @@
+/* Generated with cbindgen from src/ffi.rs, do not edit by hand. */
+
+#ifndef EIGHT_PUZZLE_SOLVER_H
+#define EIGHT_PUZZLE_SOLVER_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Cells of a board passed to [`eps_solve`].
+ */
+#define EPS_CELLS 9
+
+/**
+ * The board is not a valid 8-puzzle, or the goal cannot be reached from it.
+ */
+#define EPS_INVALID_BOARD -1
+
+/**
+ * A pointer argument is null.
+ */
+#define EPS_NULL_POINTER -2
+
+/**
+ * The search ended without a solution.
+ */
+#define EPS_NO_SOLUTION -3
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solves the 8-puzzle `board`, 9 bytes row by row with 0 for the empty cell,
+ * with A* and the default heuristic.
+ *
+ * Returns the number of moves and stores the moves of the blank in LURD
+ * notation as a NUL-terminated string in `*moves`, which must be released
+ * with [`eps_free_moves`]. On failure, returns one of the negative `EPS_`
+ * codes and leaves `*moves` null.
+ *
+ * # Safety
+ *
+ * `board` must point to 9 readable bytes and `moves` to a writable pointer.
+ */
+int32_t eps_solve(const uint8_t *board, char **moves);
+
+/**
+ * Releases a move string returned by [`eps_solve`]. Null is ignored.
+ *
+ * # Safety
+ *
+ * `moves` must be null or a string from [`eps_solve`] not released yet.
+ */
+void eps_free_moves(char *moves);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* EIGHT_PUZZLE_SOLVER_H */
@@ -1,37 +1,63 @@
 #![deny(unused_variables)]
 #![deny(unused_imports)]
 
-use crate::board::{self};
+use crate::{
+    actions::{Action, ActionSet},
+    algorithms,
+    board::{self},
+    cost::CostModel,
+    explain::Phase,
+    goal::GoalContext,
+    heuristic::Heuristic,
+    notation::{self, Move},
+    rng,
+    symmetry::{self, GoalSymmetry},
+    verify,
+};
 
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
-    collections::{BinaryHeap, HashSet},
+    collections::{BTreeMap, BinaryHeap},
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
 };
 
-const FINISHED: board::Board = board::Board {
-    b: [
-        [Some(1), Some(2), Some(3)],
-        [Some(4), Some(5), Some(6)],
-        [Some(7), Some(8), None],
-    ],
-};
+pub const FINISHED: board::Board = board::STANDARD_GOAL;
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct State {
     pub board: board::Board,
+    #[serde(skip)] // checkpoints replay `moves` instead, which is far smaller
     pub path: Vec<board::Board>,
-    pub g: i64, // g(n) = the cost so far
-    pub f: i64, // f(n) = g(n) + h(n)
+    pub moves: Vec<Move>, // how the blank moved along `path`
+    pub g: i64,           // g(n) = the cost so far
+    pub h: i64,           // h(n) = the estimated cost to the goal
+    pub f: i64,           // f(n) = g(n) + h(n)
+    pub seq: usize,       // how many states were queued before this one
+    pub tie_break: TieBreak,
+    #[serde(default)]
+    pub jitter: u64, // random rank among equal f(n) for the random tie-break
 }
 
+// Smallest f(n) first, then the tie-breaking policy, then the oldest state,
+// so the same input always gives the same path.
 impl Ord for State {
     fn cmp(&self, other: &Self) -> Ordering {
-        return other.f.cmp(&self.f).then_with(|| {
-            other
-                .board
-                .distance(&FINISHED)
-                .cmp(&self.board.distance(&FINISHED))
-        });
+        let by_policy = match self.tie_break {
+            TieBreak::SmallerH => other.h.cmp(&self.h),
+            TieBreak::LargerG => self.g.cmp(&other.g),
+            TieBreak::Fifo => Ordering::Equal,
+            TieBreak::Random => other.jitter.cmp(&self.jitter),
+        };
+        // the last resort is the order they were queued in
+        let by_seq = match self.tie_break {
+            TieBreak::LargerG => self.seq.cmp(&other.seq),
+            _ => other.seq.cmp(&self.seq),
+        };
+        return other.f.cmp(&self.f).then(by_policy).then(by_seq);
     }
 }
 
@@ -42,49 +68,577 @@ impl PartialOrd for State {
     }
 }
 
-pub fn search(starting_board: board::Board) -> Option<Vec<board::Board>> {
-    // A* algorithm
-    let mut queue: BinaryHeap<State> = BinaryHeap::new();
+/// Which of two boards with the same f(n) A* expands first. Since
+/// g(n) = f(n) - weight * h(n), a larger g and a smaller h go together; the
+/// two policies part on boards with the same g, where `SmallerH` takes the
+/// one queued first and `LargerG` the one queued last, diving deeper along
+/// the path it just extended.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum TieBreak {
+    #[default]
+    SmallerH, // the one the heuristic puts closest to the goal
+    LargerG, // the one furthest from the start, then the one queued last
+    Fifo,    // the one queued first
+    Random,  // any of them, drawn from the seed of the search
+}
+
+impl TieBreak {
+    pub const ALL: [TieBreak; 4] = [
+        TieBreak::SmallerH,
+        TieBreak::LargerG,
+        TieBreak::Fifo,
+        TieBreak::Random,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            TieBreak::SmallerH => "smaller-h",
+            TieBreak::LargerG => "larger-g",
+            TieBreak::Fifo => "fifo",
+            TieBreak::Random => "random",
+        };
+    }
+}
+
+impl FromStr for TieBreak {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return TieBreak::ALL
+            .into_iter()
+            .find(|t| t.name() == s)
+            .ok_or_else(|| format!("unknown tie-breaking policy: {}", s));
+    }
+}
+
+impl fmt::Display for TieBreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SearchConfig {
+    pub heuristic: Heuristic,
+    // f(n) = g(n) + weight * h(n); anything above 1 trades optimality for speed
+    pub weight: i64,
+    pub beam_width: usize, // boards kept per depth by beam search
+    pub tie_break: TieBreak,
+    #[serde(default)] // checkpoints from before there was a choice
+    pub cost: CostModel,
+    #[serde(default)]
+    pub actions: ActionSet,
+    #[serde(default)]
+    pub seed: u64, // of the random tie-break
+    // skip boards a turned image of which was expanded, where the goal allows
+    #[serde(default)]
+    pub symmetry: bool,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        return SearchConfig {
+            heuristic: Heuristic::default(),
+            weight: 1,
+            beam_width: algorithms::DEFAULT_BEAM_WIDTH,
+            tie_break: TieBreak::default(),
+            cost: CostModel::default(),
+            actions: ActionSet::default(),
+            seed: rng::DETERMINISTIC_SEED,
+            symmetry: false,
+        };
+    }
+}
+
+impl SearchConfig {
+    /// Whether the solution found with this configuration is guaranteed to be
+    /// the shortest one. Only true for an unweighted admissible heuristic.
+    pub fn is_optimal(&self) -> bool {
+        return self.weight <= 1;
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct SearchStats {
+    pub expanded: usize,      // boards taken off the queue and expanded
+    pub generated: usize,     // successor boards pushed onto the queue
+    pub peak_frontier: usize, // most boards waiting to be expanded at once
+    #[serde(default)]
+    pub duplicates: usize, // boards taken off the queue but already expanded, and skipped
+    #[serde(default)]
+    pub repushed: usize, // successors pushed although they were already expanded
+    #[serde(default)]
+    pub symmetric: usize, // boards skipped because a turned image of them was expanded
+    pub elapsed: Duration,
+}
+
+/// What the search knew about one board of the solution.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Step {
+    pub g: i64,                    // moves made to reach the board
+    pub h: i64,                    // heuristic estimate of the moves left
+    pub options: Vec<(Move, i64)>, // every legal move from the board, with h of the board it leads to
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Solution {
+    pub path: Vec<board::Board>,
+    pub directions: Vec<Move>, // how the blank moved between the boards of `path`
+    pub steps: Vec<Step>,      // one per board of `path`
+    pub phases: Vec<Phase>,    // the stages the solver went through, if it works in stages
+    pub guaranteed_optimal: bool,
+    pub stats: SearchStats,
+    pub shortened: usize, // moves taken out after the search, see `shorten`
+}
+
+impl Solution {
+    pub fn moves(&self) -> usize {
+        return self.path.len().saturating_sub(1);
+    }
+}
+
+/// Annotates every board of `path` with its cost so far, its heuristic
+/// estimate, and the moves that were available from it.
+pub fn annotate(path: &[board::Board], goal: &GoalContext, heuristic: Heuristic) -> Vec<Step> {
+    return path
+        .iter()
+        .enumerate()
+        .map(|(g, board)| {
+            let options = Move::ALL
+                .iter()
+                .filter_map(|m| Some((*m, heuristic.estimate(&m.apply(board)?, goal))))
+                .collect();
+            Step {
+                g: g as i64,
+                h: heuristic.estimate(board, goal),
+                options,
+            }
+        })
+        .collect();
+}
+
+pub fn search(starting_board: board::Board) -> Option<Solution> {
+    return search_with(
+        starting_board,
+        &GoalContext::standard(),
+        SearchConfig::default(),
+    );
+}
+
+/// Snapshot of the search, reported every time a board is expanded.
+#[derive(Clone, Debug)]
+pub struct Progress {
+    pub board: board::Board, // the board being expanded
+    pub parent: Option<board::Board>,
+    pub g: i64,
+    pub h: i64,
+    pub f: i64,
+    pub frontier: usize, // boards waiting in the queue
+    pub expanded: usize,
+    pub initial_h: i64, // h of the starting board
+    pub best_h: i64,    // smallest h expanded so far
+    pub memory: usize,  // rough bytes held by the queue and the visited boards
+    pub frontier_f: FrontierHistogram,
+}
+
+/// Bars of a [`FrontierHistogram`].
+pub const HISTOGRAM_BARS: usize = 12;
+
+/// How many of the boards waiting in the queue have each f value, from the
+/// lowest one up. Empty for searches that do not order their queue by f.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FrontierHistogram {
+    pub lowest: i64,
+    pub counts: [usize; HISTOGRAM_BARS], // the last one also counts every higher f
+}
+
+impl FrontierHistogram {
+    // Buckets the counts of each f value on the queue, which is usually a
+    // few dozen values however long the queue is.
+    fn of(open_f: &BTreeMap<i64, usize>) -> FrontierHistogram {
+        let Some(&lowest) = open_f.keys().next() else {
+            return FrontierHistogram::default();
+        };
+        let mut counts = [0; HISTOGRAM_BARS];
+        for (f, count) in open_f {
+            let bar = ((f - lowest) as usize).min(HISTOGRAM_BARS - 1);
+            counts[bar] += count;
+        }
+        return FrontierHistogram { lowest, counts };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.counts.iter().all(|count| *count == 0);
+    }
+}
+
+pub fn search_with(
+    starting_board: board::Board,
+    goal: &GoalContext,
+    config: SearchConfig,
+) -> Option<Solution> {
+    return search_observed(starting_board, goal, config, &mut |_| true);
+}
+
+/// Same as [`search_with`], but calls `observer` with the progress every time a
+/// board is expanded. The search is abandoned (returning `None`) as soon as
+/// `observer` returns `false`.
+pub fn search_observed(
+    starting_board: board::Board,
+    goal: &GoalContext,
+    config: SearchConfig,
+    observer: &mut dyn FnMut(&Progress) -> bool,
+) -> Option<Solution> {
+    // half of all boards can never reach the goal, don't bother exploring those
+    if !goal.is_reachable(&starting_board) {
+        return None;
+    }
+    let checkpoint = Checkpoint::new(starting_board, goal, config);
+    return run(checkpoint, goal, observer, None, &mut Profiler(None));
+}
+
+/// Where a profiled search spent its time, see [`search_profiled`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SearchProfile {
+    pub successors: Duration, // finding the actions from a board and the states they lead to
+    pub heuristic: Duration,  // costing and estimating those states
+    pub queue: Duration,      // pushing onto and popping off the priority queue
+    pub visited: Duration,    // looking up and recording the boards seen so far
+    pub total: Duration,      // the whole search, the rest is bookkeeping
+}
+
+/// Same as [`search_with`], adding the time spent in each part of the search
+/// to `profile`. Timing every step makes the search itself slower.
+pub fn search_profiled(
+    starting_board: board::Board,
+    goal: &GoalContext,
+    config: SearchConfig,
+    profile: &mut SearchProfile,
+) -> Option<Solution> {
+    if !goal.is_reachable(&starting_board) {
+        return None;
+    }
+    let started = Instant::now();
+    let checkpoint = Checkpoint::new(starting_board, goal, config);
+    let solution = run(
+        checkpoint,
+        goal,
+        &mut |_| true,
+        None,
+        &mut Profiler(Some(profile)),
+    );
+    profile.total += started.elapsed();
+    return solution;
+}
+
+// Adds the time of each part of a search to a profile, if there is one.
+struct Profiler<'a>(Option<&'a mut SearchProfile>);
+
+impl Profiler<'_> {
+    fn time<T>(
+        &mut self,
+        part: fn(&mut SearchProfile) -> &mut Duration,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        let Some(profile) = self.0.as_deref_mut() else {
+            return f();
+        };
+        let started = Instant::now();
+        let result = f();
+        *part(profile) += started.elapsed();
+        return result;
+    }
+}
+
+/// Where an A* search stands: enough to carry on later from the same point.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub start: board::Board,
+    pub goal: board::Board,
+    pub config: SearchConfig,
+    pub frontier: Vec<State>, // the queue, in no particular order
+    pub visited: Vec<board::Board>,
+    pub stats: SearchStats, // `elapsed` is the time searched so far
+    pub initial_h: i64,
+    pub best_h: i64,
+}
+
+impl Checkpoint {
+    /// A search that has not expanded anything yet.
+    pub fn new(start: board::Board, goal: &GoalContext, config: SearchConfig) -> Checkpoint {
+        let h = config.heuristic.estimate(&start, goal);
+        return Checkpoint {
+            start,
+            goal: *goal.goal(),
+            config,
+            frontier: vec![State {
+                board: start,
+                path: vec![start],
+                moves: Vec::new(),
+                g: 0,
+                h,
+                f: config.weight.saturating_mul(h),
+                seq: 0,
+                tie_break: config.tie_break,
+                jitter: 0,
+            }],
+            visited: Vec::new(),
+            stats: SearchStats::default(),
+            initial_h: h,
+            best_h: h,
+        };
+    }
+}
+
+/// Carries on with the search saved in `checkpoint`, handing a fresh
+/// checkpoint to `save` every `every` expanded boards. The search is
+/// abandoned (returning `None`) as soon as `save` returns `false`.
+pub fn resume(
+    checkpoint: Checkpoint,
+    goal: &GoalContext,
+    every: usize,
+    save: &mut dyn FnMut(&Checkpoint) -> bool,
+) -> Option<Solution> {
+    if !goal.is_reachable(&checkpoint.start) {
+        return None;
+    }
+    return run(
+        checkpoint,
+        goal,
+        &mut |_| true,
+        Some((every.max(1), save)),
+        &mut Profiler(None),
+    );
+}
+
+// g and h of the state `action` leads to from `current`; a macro costs as
+// much as its moves, one after the other. Kept out of the search loop, like
+// `child`, so profilers tell them apart.
+#[inline(never)]
+fn evaluate(
+    current: &State,
+    action: &Action,
+    goal: &GoalContext,
+    config: &SearchConfig,
+) -> (i64, i64) {
+    let (mut g, mut h, mut board) = (current.g, current.h, current.board);
+    for next in &action.boards {
+        g += config.cost.of_move(&board, next);
+        h = config.heuristic.estimate_after_move(h, &board, next, goal);
+        board = *next;
+    }
+    return (g, h);
+}
+
+// The turns of the board that make no difference to the goal, when the search
+// asked to skip twins. Renumbering the tiles keeps every distance only when
+// all moves cost the same, and the heuristics are the same for twins.
+fn symmetries(goal: &GoalContext, config: &SearchConfig) -> Vec<GoalSymmetry> {
+    if !config.symmetry || config.cost != CostModel::Unit || config.actions != ActionSet::Single {
+        return Vec::new();
+    }
+    return symmetry::goal_symmetries(goal);
+}
 
-    let f = starting_board.distance(&FINISHED);
-    let path: Vec<board::Board> = vec![starting_board];
-    queue.push(State {
-        board: starting_board,
+// The queue ops keep `open_f` in step with what is on the queue.
+#[inline(never)]
+fn pop(queue: &mut BinaryHeap<State>, open_f: &mut BTreeMap<i64, usize>) -> Option<State> {
+    let state = queue.pop()?;
+    if let Some(count) = open_f.get_mut(&state.f) {
+        *count -= 1;
+        if *count == 0 {
+            open_f.remove(&state.f);
+        }
+    }
+    return Some(state);
+}
+
+#[inline(never)]
+fn push(queue: &mut BinaryHeap<State>, open_f: &mut BTreeMap<i64, usize>, state: State) {
+    *open_f.entry(state.f).or_default() += 1;
+    queue.push(state);
+}
+
+// The state `action` leads to from `current`, with its path so far.
+#[inline(never)]
+fn child(
+    current: &State,
+    action: Action,
+    g: i64,
+    h: i64,
+    seq: usize,
+    config: &SearchConfig,
+    jitter: u64,
+) -> State {
+    let board = action.board();
+    let mut path = current.path.clone();
+    path.extend(&action.boards);
+    let mut moves = current.moves.clone();
+    moves.extend(action.moves);
+    return State {
+        board,
         path,
-        f,
-        g: 0,
-    });
+        moves,
+        g,
+        h,
+        // huge weights saturate rather than wrap around
+        f: g.saturating_add(config.weight.saturating_mul(h)),
+        seq,
+        tie_break: config.tie_break,
+        jitter,
+    };
+}
 
-    let mut visited: HashSet<board::Board> = HashSet::new();
-    while !queue.is_empty() {
-        let current_opt: Option<State> = queue.pop();
-        current_opt.as_ref()?;
+type Saver<'a> = (usize, &'a mut dyn FnMut(&Checkpoint) -> bool);
 
-        let current = current_opt.unwrap();
-        if visited.contains(&current.board) {
+fn run(
+    checkpoint: Checkpoint,
+    goal: &GoalContext,
+    observer: &mut dyn FnMut(&Progress) -> bool,
+    mut saver: Option<Saver>,
+    profiler: &mut Profiler,
+) -> Option<Solution> {
+    let started = Instant::now();
+    let searched_before = checkpoint.stats.elapsed;
+    let (starting_board, config, initial_h) =
+        (checkpoint.start, checkpoint.config, checkpoint.initial_h);
+    let mut stats = checkpoint.stats;
+    let mut best_h = checkpoint.best_h;
+
+    // A* algorithm
+    let frontier = checkpoint.frontier.into_iter().filter_map(|mut state| {
+        if state.path.is_empty() {
+            // a state whose moves don't replay can only come from a damaged file
+            state.path = notation::apply_moves(&starting_board, &state.moves)?;
+        }
+        Some(state)
+    });
+    let mut queue: BinaryHeap<State> = frontier.collect();
+    // how many states on the queue have each f, for the progress reports
+    let mut open_f: BTreeMap<i64, usize> = BTreeMap::new();
+    for state in &queue {
+        *open_f.entry(state.f).or_default() += 1;
+    }
+    let mut visited: board::BoardSet = checkpoint.visited.into_iter().collect();
+    let symmetries = symmetries(goal, &config);
+    // the expanded boards by the image that stands for them and their twins
+    let mut twins: board::BoardSet = match symmetries.is_empty() {
+        true => board::BoardSet::default(),
+        false => visited
+            .iter()
+            .map(|board| symmetry::canonical(board, &symmetries))
+            .collect(),
+    };
+    // a resumed search goes on where the draws stopped
+    let mut rng = rng::seeded(config.seed.wrapping_add(stats.generated as u64));
+
+    while let Some(current) = profiler.time(|p| &mut p.queue, || pop(&mut queue, &mut open_f)) {
+        if profiler.time(|p| &mut p.visited, || visited.contains(&current.board)) {
+            stats.duplicates += 1;
+            continue;
+        }
+        let twin = (!symmetries.is_empty()).then(|| {
+            profiler.time(
+                |p| &mut p.visited,
+                || symmetry::canonical(&current.board, &symmetries),
+            )
+        });
+        if twin.is_some_and(|twin| twins.contains(&twin)) {
+            stats.symmetric += 1;
             continue;
         }
-        if current.board.distance(&FINISHED) == 0 {
-            return Some(current.path);
+        if goal.is_goal(&current.board) {
+            // callers of `search` and `search_with` get the path as it is
+            debug_assert!(
+                verify::verify_with_goal(&starting_board, &current.path, goal).is_ok(),
+                "A* returned an invalid path"
+            );
+            stats.elapsed = searched_before + started.elapsed();
+            return Some(Solution {
+                steps: annotate(&current.path, goal, config.heuristic),
+                path: current.path,
+                directions: current.moves,
+                phases: Vec::new(),
+                shortened: 0,
+                guaranteed_optimal: config.is_optimal(),
+                stats,
+            });
+        }
+
+        profiler.time(|p| &mut p.visited, || visited.insert(current.board));
+        if let Some(twin) = twin {
+            twins.insert(twin);
+        }
+        stats.expanded += 1;
+
+        best_h = best_h.min(current.h);
+        let progress = Progress {
+            board: current.board,
+            parent: current.path.len().checked_sub(2).map(|i| current.path[i]),
+            g: current.g,
+            h: current.h,
+            f: current.f,
+            frontier: queue.len(),
+            frontier_f: FrontierHistogram::of(&open_f),
+            expanded: stats.expanded,
+            initial_h,
+            best_h,
+            // every queued state carries a path about as long as this one's
+            memory: queue.len()
+                * (size_of::<State>()
+                    + current.path.len() * (size_of::<board::Board>() + size_of::<Move>()))
+                + (visited.len() + twins.len()) * size_of::<board::Board>(),
+        };
+        if !observer(&progress) {
+            return None;
+        }
+
+        let actions = profiler.time(
+            |p| &mut p.successors,
+            || config.actions.successors(&current.board),
+        );
+        for action in actions {
+            let (g, h) = profiler.time(
+                |p| &mut p.heuristic,
+                || evaluate(&current, &action, goal, &config),
+            );
+            let next_state = profiler.time(
+                |p| &mut p.successors,
+                || {
+                    let seq = stats.generated + 1; // the starting board was first
+                    child(&current, action, g, h, seq, &config, rng.next_u64())
+                },
+            );
+            if profiler.time(|p| &mut p.visited, || visited.contains(&next_state.board)) {
+                stats.repushed += 1;
+            }
+            profiler.time(
+                |p| &mut p.queue,
+                || push(&mut queue, &mut open_f, next_state),
+            );
+            stats.generated += 1;
         }
+        stats.peak_frontier = stats.peak_frontier.max(queue.len());
 
-        visited.insert(current.board);
-
-        let next_boards: Vec<board::Board> = current.board.get_possible_next_states();
-        for next_board in next_boards {
-            let g = current.g + 1;
-            let h = next_board.distance(&FINISHED);
-            let f = g + h;
-
-            let mut next_path = current.path.clone();
-            next_path.push(next_board);
-            let next_state = State {
-                board: next_board,
-                path: next_path,
-                g,
-                f,
+        if let Some((every, save)) = &mut saver
+            && stats.expanded.is_multiple_of(*every)
+        {
+            let checkpoint = Checkpoint {
+                start: starting_board,
+                goal: *goal.goal(),
+                config,
+                frontier: queue.iter().cloned().collect(),
+                visited: visited.iter().copied().collect(),
+                stats: SearchStats {
+                    elapsed: searched_before + started.elapsed(),
+                    ..stats
+                },
+                initial_h,
+                best_h,
             };
-            queue.push(next_state);
+            if !save(&checkpoint) {
+                return None;
+            }
         }
     }
 
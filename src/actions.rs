@@ -0,0 +1,103 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{board::Board, notation::Move};
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Which moves A* may take in one step. Macro moves are shortcuts made of
+/// single moves and cost as much as those, so they change how much the search
+/// expands but not which solutions are optimal.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum ActionSet {
+    #[default]
+    Single, // one move of the blank at a time
+    Macro, // also the blank turning a corner, and going around three sides of a 2x2 block
+}
+
+/// One step of the search: a single move, or a macro of two or three.
+#[derive(Clone, Debug)]
+pub struct Action {
+    pub moves: Vec<Move>,
+    pub boards: Vec<Board>, // the board after each of `moves`
+}
+
+impl Action {
+    /// Where the action ends up.
+    pub fn board(&self) -> Board {
+        return *self.boards.last().expect("an action has at least one move");
+    }
+}
+
+impl ActionSet {
+    pub const ALL: [ActionSet; 2] = [ActionSet::Single, ActionSet::Macro];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            ActionSet::Single => "single",
+            ActionSet::Macro => "macro",
+        };
+    }
+
+    /// Every action that can be taken from `board`.
+    pub fn successors(&self, board: &Board) -> Vec<Action> {
+        let singles = board
+            .get_possible_next_states()
+            .into_iter()
+            .map(|n| Action {
+                moves: vec![n.mv],
+                boards: vec![n.board],
+            });
+        if *self == ActionSet::Single {
+            return singles.collect();
+        }
+
+        let mut actions = Vec::new();
+        for single in singles {
+            actions.push(single.clone());
+            for clockwise in [true, false] {
+                // turning the same way twice takes the blank around a 2x2
+                // block, rotating three of its tiles
+                let mut action = single.clone();
+                for _ in 0..2 {
+                    let mv = turn(*action.moves.last().unwrap(), clockwise);
+                    let Some(next) = mv.apply(&action.board()) else {
+                        break;
+                    };
+                    action.moves.push(mv);
+                    action.boards.push(next);
+                    actions.push(action.clone());
+                }
+            }
+        }
+        return actions;
+    }
+}
+
+// The direction a quarter turn away from `mv`.
+fn turn(mv: Move, clockwise: bool) -> Move {
+    return match (mv, clockwise) {
+        (Move::Up, true) | (Move::Down, false) => Move::Right,
+        (Move::Right, true) | (Move::Left, false) => Move::Down,
+        (Move::Down, true) | (Move::Up, false) => Move::Left,
+        (Move::Left, true) | (Move::Right, false) => Move::Up,
+    };
+}
+
+impl FromStr for ActionSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return ActionSet::ALL
+            .into_iter()
+            .find(|a| a.name() == s)
+            .ok_or_else(|| format!("unknown action set: {}", s));
+    }
+}
+
+impl fmt::Display for ActionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
@@ -0,0 +1,267 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{self, Progress, SearchConfig, Solution},
+    board::{Board, BoardMap},
+    cost::CostModel,
+    error::SolverError,
+    goal::GoalContext,
+    notation::Move,
+    shorten, validate, verify,
+};
+
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+mod beam;
+mod bfs;
+mod human;
+mod iddfs;
+
+/// How often a running search logs its progress, in expanded boards.
+const LOG_EVERY_EXPANDED: usize = 100_000;
+
+/// Beam width used unless configured otherwise.
+pub const DEFAULT_BEAM_WIDTH: usize = 100;
+
+/// Uninformed searches give up after expanding this many boards; they need
+/// far too long for anything but small or easy puzzles.
+pub const MAX_UNINFORMED_EXPANDED: usize = 5_000_000;
+
+/// Every search algorithm the app can run, all solving through [`Algorithm::solve`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Algorithm {
+    #[default]
+    AStar,
+    Bfs,
+    Iddfs,
+    Beam,
+    Human,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 5] = [
+        Algorithm::AStar,
+        Algorithm::Bfs,
+        Algorithm::Iddfs,
+        Algorithm::Beam,
+        Algorithm::Human,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Algorithm::AStar => "a-star",
+            Algorithm::Bfs => "bfs",
+            Algorithm::Iddfs => "iddfs",
+            Algorithm::Beam => "beam",
+            Algorithm::Human => "human",
+        };
+    }
+
+    /// Whether the heuristic guides the search; the uninformed ones only use
+    /// it to annotate their results.
+    pub fn is_informed(&self) -> bool {
+        return match self {
+            Algorithm::AStar | Algorithm::Beam | Algorithm::Human => true,
+            Algorithm::Bfs | Algorithm::Iddfs => false,
+        };
+    }
+
+    /// Whether solutions found with `config` are guaranteed to be the
+    /// shortest ones.
+    pub fn is_optimal(&self, config: &SearchConfig) -> bool {
+        return match self {
+            Algorithm::AStar => config.is_optimal(),
+            // they find the fewest moves, not always the cheapest ones
+            Algorithm::Bfs | Algorithm::Iddfs => config.cost == CostModel::Unit,
+            Algorithm::Beam | Algorithm::Human => false,
+        };
+    }
+
+    /// What to tell the user when the search gave up on a solvable board.
+    pub fn failure_message(&self, config: &SearchConfig) -> String {
+        return match self {
+            Algorithm::AStar => "No solution found for this configuration.".to_string(),
+            Algorithm::Bfs | Algorithm::Iddfs => format!(
+                "No solution within {} expanded boards, try a-star.",
+                MAX_UNINFORMED_EXPANDED
+            ),
+            Algorithm::Beam => format!(
+                "Beam search (width {}) lost track of the goal, try a wider beam or a-star.",
+                config.beam_width
+            ),
+            Algorithm::Human => "No solution found for one of the stages.".to_string(),
+        };
+    }
+
+    pub fn solve(&self, board: Board, goal: &GoalContext, config: SearchConfig) -> SearchOutcome {
+        return self.solve_observed(board, goal, config, &mut |_| true);
+    }
+
+    /// Same as [`Algorithm::solve`], reporting progress to `observer`; the
+    /// search stops early, as [`SearchOutcome::Cancelled`], when it returns
+    /// `false`.
+    pub fn solve_observed(
+        &self,
+        board: Board,
+        goal: &GoalContext,
+        config: SearchConfig,
+        observer: &mut dyn FnMut(&Progress) -> bool,
+    ) -> SearchOutcome {
+        let shape = |b: &Board| (b.rows(), b.cols());
+        if shape(&board) != shape(goal.goal()) {
+            return SearchOutcome::Error(SolverError::ShapeMismatch {
+                board: shape(&board),
+                goal: shape(goal.goal()),
+            });
+        }
+        if let Err(rejection) = validate::check_tiles(&board) {
+            return SearchOutcome::Error(SolverError::InvalidBoard(rejection));
+        }
+        if !goal.is_reachable(&board) {
+            return SearchOutcome::Unsolvable;
+        }
+
+        let _span = tracing::info_span!(
+            "search",
+            algorithm = self.name(),
+            heuristic = config.heuristic.name(),
+            weight = config.weight
+        )
+        .entered();
+        tracing::info!(board = %board.code(), "search started");
+
+        // the observer sees every expansion, which is also where the rate is logged
+        let started = Instant::now();
+        let mut expanded = 0;
+        let mut cancelled = false;
+        let mut logging_observer = |progress: &Progress| {
+            if progress.expanded.is_multiple_of(LOG_EVERY_EXPANDED) {
+                tracing::debug!(
+                    expanded = progress.expanded,
+                    frontier = progress.frontier,
+                    nodes_per_second = per_second(progress.expanded, started.elapsed()),
+                    "searching"
+                );
+            }
+            expanded = progress.expanded;
+            cancelled = !observer(progress);
+            return !cancelled;
+        };
+        let result = match self {
+            Algorithm::AStar => a_star::search_observed(board, goal, config, &mut logging_observer),
+            Algorithm::Bfs => bfs::search_observed(board, goal, config, &mut logging_observer),
+            Algorithm::Iddfs => iddfs::search_observed(board, goal, config, &mut logging_observer),
+            Algorithm::Beam => beam::search_observed(board, goal, config, &mut logging_observer),
+            Algorithm::Human => human::search_observed(board, goal, config, &mut logging_observer),
+        };
+
+        let Some(mut solution) = result else {
+            tracing::info!(
+                elapsed_ms = started.elapsed().as_secs_f64() * 1000.0,
+                cancelled,
+                "no solution"
+            );
+            return match cancelled {
+                true => SearchOutcome::Cancelled,
+                false => SearchOutcome::LimitExceeded { expanded },
+            };
+        };
+        tracing::info!(
+            moves = solution.moves(),
+            expanded = solution.stats.expanded,
+            elapsed_ms = solution.stats.elapsed.as_secs_f64() * 1000.0,
+            nodes_per_second = per_second(solution.stats.expanded, solution.stats.elapsed),
+            "solution found"
+        );
+        if !self.is_optimal(&config) {
+            let saved = shorten::shorten(&mut solution, goal, config);
+            tracing::info!(moves = solution.moves(), saved, "solution shortened");
+        }
+        if let Err(e) = verify::verify_with_goal(&board, &solution.path, goal) {
+            return SearchOutcome::Error(SolverError::InvalidSolution(e));
+        }
+        return SearchOutcome::Solved(solution);
+    }
+}
+
+/// How a search ended.
+#[derive(Clone, Debug)]
+pub enum SearchOutcome {
+    Solved(Solution),
+    Unsolvable, // the goal cannot be reached from the board, nothing was searched
+    Cancelled,  // the observer stopped the search
+    LimitExceeded {
+        expanded: usize, // boards expanded before the search gave up
+    },
+    Error(SolverError),
+}
+
+impl SearchOutcome {
+    pub fn solution(self) -> Option<Solution> {
+        return match self {
+            SearchOutcome::Solved(solution) => Some(solution),
+            _ => None,
+        };
+    }
+
+    /// The solution, or what to tell the user when `algorithm` with `config`
+    /// ended this way without one.
+    pub fn into_result(
+        self,
+        algorithm: Algorithm,
+        config: &SearchConfig,
+    ) -> Result<Solution, String> {
+        return match self {
+            SearchOutcome::Solved(solution) => Ok(solution),
+            SearchOutcome::Unsolvable => {
+                Err("Unsolvable: the goal cannot be reached from this board.".to_string())
+            }
+            SearchOutcome::Cancelled => Err("Search cancelled.".to_string()),
+            SearchOutcome::LimitExceeded { .. } => Err(algorithm.failure_message(config)),
+            SearchOutcome::Error(e) => Err(format!("Search failed: {}.", e)),
+        };
+    }
+}
+
+fn per_second(count: usize, elapsed: Duration) -> u64 {
+    if elapsed.is_zero() {
+        return 0;
+    }
+    return (count as f64 / elapsed.as_secs_f64()) as u64;
+}
+
+// Path from the start to `board` and the moves along it, from a map of how
+// every board was first reached.
+fn trace_back(parents: &BoardMap<Option<(Board, Move)>>, board: Board) -> (Vec<Board>, Vec<Move>) {
+    let mut path = vec![board];
+    let mut directions: Vec<Move> = Vec::new();
+    while let Some(Some((parent, mv))) = parents.get(&path[path.len() - 1]) {
+        path.push(*parent);
+        directions.push(*mv);
+    }
+    path.reverse();
+    directions.reverse();
+    return (path, directions);
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Algorithm::ALL
+            .into_iter()
+            .find(|a| a.name() == s)
+            .ok_or_else(|| format!("unknown algorithm: {}", s));
+    }
+}
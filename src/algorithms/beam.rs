@@ -0,0 +1,98 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{self, FrontierHistogram, Progress, SearchConfig, SearchStats, Solution},
+    board::{Board, BoardMap},
+    goal::GoalContext,
+    notation::Move,
+};
+
+use std::{collections::hash_map::Entry, time::Instant};
+
+/// Paths longer than this are not worth following any further.
+const MAX_DEPTH: i64 = 1000;
+
+/// Beam search: a breadth-first search that only keeps the `beam_width`
+/// boards with the lowest heuristic estimate at every depth. Memory stays
+/// bounded and it is fast, but the solution can be far from the shortest and
+/// a narrow beam can lose track of the goal entirely.
+pub fn search_observed(
+    starting_board: Board,
+    goal: &GoalContext,
+    config: SearchConfig,
+    observer: &mut dyn FnMut(&Progress) -> bool,
+) -> Option<Solution> {
+    if !goal.is_reachable(&starting_board) {
+        return None;
+    }
+
+    let started = Instant::now();
+    let mut stats = SearchStats::default();
+
+    // how every board was first reached, the start has no parent
+    let mut parents: BoardMap<Option<(Board, Move)>> = BoardMap::default();
+    parents.insert(starting_board, None);
+
+    let initial_h = config.heuristic.estimate(&starting_board, goal);
+    let mut best_h = initial_h;
+    let mut beam: Vec<(i64, Board)> = vec![(initial_h, starting_board)];
+
+    for g in 0..MAX_DEPTH {
+        let mut candidates: Vec<(i64, Board)> = Vec::new();
+        for (h, board) in &beam {
+            if goal.is_goal(board) {
+                let (path, directions) = super::trace_back(&parents, *board);
+                stats.elapsed = started.elapsed();
+                return Some(Solution {
+                    steps: a_star::annotate(&path, goal, config.heuristic),
+                    path,
+                    directions,
+                    phases: Vec::new(),
+                    shortened: 0,
+                    guaranteed_optimal: false,
+                    stats,
+                });
+            }
+
+            stats.expanded += 1;
+            best_h = best_h.min(*h);
+            let progress = Progress {
+                board: *board,
+                parent: parents[board].map(|(parent, _)| parent),
+                g,
+                h: *h,
+                f: g + h,
+                frontier: beam.len(),
+                frontier_f: FrontierHistogram::default(),
+                expanded: stats.expanded,
+                initial_h,
+                best_h,
+                memory: beam.len() * size_of::<(i64, Board)>()
+                    + parents.len() * size_of::<(Board, Option<(Board, Move)>)>(),
+            };
+            if !observer(&progress) {
+                return None;
+            }
+
+            for next in board.get_possible_next_states() {
+                if let Entry::Vacant(entry) = parents.entry(next.board) {
+                    entry.insert(Some((*board, next.mv)));
+                    candidates.push((config.heuristic.estimate(&next.board, goal), next.board));
+                    stats.generated += 1;
+                }
+            }
+        }
+
+        // the sort is stable, so ties keep the order they were generated in
+        candidates.sort_by_key(|(h, _)| *h);
+        candidates.truncate(config.beam_width.max(1));
+        if candidates.is_empty() {
+            return None;
+        }
+        beam = candidates;
+        stats.peak_frontier = stats.peak_frontier.max(beam.len());
+    }
+
+    return None;
+}
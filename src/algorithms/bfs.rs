@@ -0,0 +1,94 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{self, FrontierHistogram, Progress, SearchConfig, SearchStats, Solution},
+    algorithms::MAX_UNINFORMED_EXPANDED,
+    board::{Board, BoardMap},
+    cost::CostModel,
+    goal::GoalContext,
+    notation::Move,
+};
+
+use std::{
+    collections::{VecDeque, hash_map::Entry},
+    time::Instant,
+};
+
+/// Breadth-first search: boards are expanded in order of their distance from
+/// the start, so the first path to reach the goal is a shortest one. The
+/// heuristic is only used to annotate the result and the progress reports.
+pub fn search_observed(
+    starting_board: Board,
+    goal: &GoalContext,
+    config: SearchConfig,
+    observer: &mut dyn FnMut(&Progress) -> bool,
+) -> Option<Solution> {
+    if !goal.is_reachable(&starting_board) {
+        return None;
+    }
+
+    let started = Instant::now();
+    let mut stats = SearchStats::default();
+
+    // how every board was first reached, the start has no parent
+    let mut parents: BoardMap<Option<(Board, Move)>> = BoardMap::default();
+    let mut queue: VecDeque<(Board, i64)> = VecDeque::new();
+    parents.insert(starting_board, None);
+    queue.push_back((starting_board, 0));
+
+    let initial_h = config.heuristic.estimate(&starting_board, goal);
+    let mut best_h = initial_h;
+
+    while let Some((board, g)) = queue.pop_front() {
+        if goal.is_goal(&board) {
+            let (path, directions) = super::trace_back(&parents, board);
+            stats.elapsed = started.elapsed();
+            return Some(Solution {
+                steps: a_star::annotate(&path, goal, config.heuristic),
+                path,
+                directions,
+                phases: Vec::new(),
+                shortened: 0,
+                guaranteed_optimal: config.cost == CostModel::Unit,
+                stats,
+            });
+        }
+
+        stats.expanded += 1;
+        if stats.expanded > MAX_UNINFORMED_EXPANDED {
+            return None;
+        }
+
+        let h = config.heuristic.estimate(&board, goal);
+        best_h = best_h.min(h);
+        let progress = Progress {
+            board,
+            parent: parents[&board].map(|(parent, _)| parent),
+            g,
+            h,
+            f: g,
+            frontier: queue.len(),
+            frontier_f: FrontierHistogram::default(),
+            expanded: stats.expanded,
+            initial_h,
+            best_h,
+            memory: queue.len() * size_of::<(Board, i64)>()
+                + parents.len() * size_of::<(Board, Option<(Board, Move)>)>(),
+        };
+        if !observer(&progress) {
+            return None;
+        }
+
+        for next in board.get_possible_next_states() {
+            if let Entry::Vacant(entry) = parents.entry(next.board) {
+                entry.insert(Some((board, next.mv)));
+                queue.push_back((next.board, g + 1));
+                stats.generated += 1;
+            }
+        }
+        stats.peak_frontier = stats.peak_frontier.max(queue.len());
+    }
+
+    return None;
+}
@@ -0,0 +1,107 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{self, Progress, SearchConfig, SearchStats, Solution},
+    board::Board,
+    explain::Phase,
+    goal::GoalContext,
+};
+
+use std::time::Instant;
+
+/// The way people solve the puzzle by hand: put the top row in place, then
+/// the next one, and so on until only the bottom two rows are left, then
+/// finish those. Every stage is a small A* search that puts one more tile in
+/// place and leaves the placed ones where they are, so the solution is longer
+/// than the shortest one but each move belongs to a stage that is easy to
+/// follow.
+pub fn search_observed(
+    starting_board: Board,
+    goal: &GoalContext,
+    config: SearchConfig,
+    observer: &mut dyn FnMut(&Progress) -> bool,
+) -> Option<Solution> {
+    if !goal.is_reachable(&starting_board) {
+        return None;
+    }
+
+    let started = Instant::now();
+    let mut stats = SearchStats::default();
+    let mut path = vec![starting_board];
+    let mut directions = Vec::new();
+    let mut phases = Vec::new();
+
+    for (label, stage_goal) in stages(goal) {
+        let board = path[path.len() - 1];
+        let expanded_before = stats.expanded;
+        // the stages report one running count of expanded boards
+        let mut stage_observer = |progress: &Progress| {
+            return observer(&Progress {
+                expanded: expanded_before + progress.expanded,
+                ..progress.clone()
+            });
+        };
+        let stage = a_star::search_observed(board, &stage_goal, config, &mut stage_observer)?;
+        stats.expanded += stage.stats.expanded;
+        stats.generated += stage.stats.generated;
+        stats.duplicates += stage.stats.duplicates;
+        stats.repushed += stage.stats.repushed;
+        stats.peak_frontier = stats.peak_frontier.max(stage.stats.peak_frontier);
+        let end = directions.len() + stage.moves();
+        match phases.last_mut() {
+            // the tiles of a row make up a single phase
+            Some(Phase {
+                label: last,
+                end: last_end,
+                ..
+            }) if *last == label => *last_end = end,
+            _ if stage.moves() > 0 => phases.push(Phase {
+                label,
+                start: directions.len(),
+                end,
+            }),
+            _ => {}
+        }
+        path.extend(&stage.path[1..]);
+        directions.extend(stage.directions);
+    }
+
+    stats.elapsed = started.elapsed();
+    return Some(Solution {
+        steps: a_star::annotate(&path, goal, config.heuristic),
+        path,
+        directions,
+        phases,
+        shortened: 0,
+        guaranteed_optimal: false,
+        stats,
+    });
+}
+
+// What to put in place at each stage, with the tiles of the earlier stages:
+// one more tile at a time, row by row down to the last two rows, then `goal`
+// itself.
+fn stages(goal: &GoalContext) -> Vec<(String, GoalContext)> {
+    let board = goal.goal();
+    let mut stages = Vec::new();
+    let mut tiles: Vec<i64> = Vec::new();
+    for row in 0..board.rows().saturating_sub(2) {
+        let label = match row {
+            0 => "placing top row".to_string(),
+            row => format!("placing row {}", row + 1),
+        };
+        for tile in (0..board.cols()).filter_map(|c| board.get(row, c)) {
+            if goal.is_target(tile) {
+                tiles.push(tile);
+                stages.push((label.clone(), GoalContext::partial(*board, &tiles)));
+            }
+        }
+    }
+    let last_rows = board.rows().min(2);
+    stages.push((
+        format!("finishing bottom {}x{}", last_rows, board.cols()),
+        goal.clone(),
+    ));
+    return stages;
+}
@@ -0,0 +1,134 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{self, FrontierHistogram, Progress, SearchConfig, SearchStats, Solution},
+    algorithms::MAX_UNINFORMED_EXPANDED,
+    board::Board,
+    cost::CostModel,
+    goal::GoalContext,
+    notation::Move,
+};
+
+use std::time::Instant;
+
+/// Iterative-deepening depth-first search: depth-first searches limited to 0,
+/// 1, 2, ... moves, so it finds a shortest path like breadth-first search
+/// while only keeping the current path in memory. The heuristic is only used
+/// to annotate the result and the progress reports.
+pub fn search_observed(
+    starting_board: Board,
+    goal: &GoalContext,
+    config: SearchConfig,
+    observer: &mut dyn FnMut(&Progress) -> bool,
+) -> Option<Solution> {
+    if !goal.is_reachable(&starting_board) {
+        return None;
+    }
+
+    let initial_h = config.heuristic.estimate(&starting_board, goal);
+    let mut search = Search {
+        goal,
+        config,
+        observer,
+        stats: SearchStats::default(),
+        path: vec![starting_board],
+        directions: Vec::new(),
+        initial_h,
+        best_h: initial_h,
+    };
+
+    let started = Instant::now();
+    for limit in 0.. {
+        match search.descend(limit) {
+            Outcome::Found => break,
+            Outcome::Cutoff => continue,
+            Outcome::Aborted => return None,
+        }
+    }
+
+    let mut stats = search.stats;
+    stats.elapsed = started.elapsed();
+    return Some(Solution {
+        steps: a_star::annotate(&search.path, goal, config.heuristic),
+        path: search.path,
+        directions: search.directions,
+        phases: Vec::new(),
+        shortened: 0,
+        guaranteed_optimal: config.cost == CostModel::Unit,
+        stats,
+    });
+}
+
+enum Outcome {
+    Found,
+    Cutoff,  // nothing within the depth limit
+    Aborted, // stopped by the observer or the expansion limit
+}
+
+struct Search<'a> {
+    goal: &'a GoalContext,
+    config: SearchConfig,
+    observer: &'a mut dyn FnMut(&Progress) -> bool,
+    stats: SearchStats,
+    path: Vec<Board>, // from the start to the board being expanded
+    directions: Vec<Move>,
+    initial_h: i64,
+    best_h: i64,
+}
+
+impl Search<'_> {
+    // Depth-first from the last board of the path, at most `limit` more moves.
+    fn descend(&mut self, limit: usize) -> Outcome {
+        let board = self.path[self.path.len() - 1];
+        if self.goal.is_goal(&board) {
+            return Outcome::Found;
+        }
+        if limit == 0 {
+            return Outcome::Cutoff;
+        }
+
+        self.stats.expanded += 1;
+        if self.stats.expanded > MAX_UNINFORMED_EXPANDED {
+            return Outcome::Aborted;
+        }
+        let g = self.directions.len() as i64;
+        let h = self.config.heuristic.estimate(&board, self.goal);
+        self.best_h = self.best_h.min(h);
+        let progress = Progress {
+            board,
+            parent: self.path.len().checked_sub(2).map(|i| self.path[i]),
+            g,
+            h,
+            f: g,
+            frontier: 0,
+            frontier_f: FrontierHistogram::default(),
+            expanded: self.stats.expanded,
+            initial_h: self.initial_h,
+            best_h: self.best_h,
+            memory: self.path.len() * (size_of::<Board>() + size_of::<Move>()),
+        };
+        if !(self.observer)(&progress) {
+            return Outcome::Aborted;
+        }
+
+        for next in board.get_possible_next_states() {
+            // a path that visits a board twice is never the shortest one
+            if self.path.contains(&next.board) {
+                continue;
+            }
+            self.stats.generated += 1;
+            self.path.push(next.board);
+            self.directions.push(next.mv);
+            // the path is all this search keeps
+            self.stats.peak_frontier = self.stats.peak_frontier.max(self.path.len());
+            match self.descend(limit - 1) {
+                Outcome::Cutoff => {}
+                outcome => return outcome,
+            }
+            self.path.pop();
+            self.directions.pop();
+        }
+        return Outcome::Cutoff;
+    }
+}
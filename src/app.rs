@@ -0,0 +1,2372 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    ascii, clipboard,
+    config::Config,
+    crash,
+    eta::Estimate,
+    events::AppEvent,
+    history::{History, HistoryEntry},
+    jobs::Job,
+    leaderboard::{Leaderboard, Solve},
+    play::PlayState,
+    race::Race,
+    replay::{Playback, Replay},
+    session::{self, SavedPlay, SavedSolution, Screen, Session},
+    theme::Theme,
+    tutorial::{Lesson, Tutorial},
+    versus::{Outcome, Versus},
+};
+use crossterm::event::KeyCode;
+use eight_puzzle_solver::{
+    a_star::{self, Progress, SearchConfig, Solution},
+    algorithms::{Algorithm, SearchOutcome},
+    board::{self, Board},
+    book,
+    cost::CostModel,
+    export,
+    goal::{GoalContext, GoalPreset},
+    heuristic::Heuristic,
+    hint,
+    lrta::Agent,
+    notation::{self, Move},
+    optimal::OptimalPaths,
+    oracle::{self, Oracle},
+    puzzles::{Collection, Puzzle},
+    rng,
+    symmetry::Symmetry,
+    validate::{self, CellOrder},
+};
+use ratatui::widgets::ListState;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+// Heuristic weights offered in the Input screen; anything above 1 is Weighted A*
+const WEIGHTS: [i64; 4] = [1, 2, 3, 5];
+
+// Pace of the search visualization, and how often a normal search reports
+const VISUALIZE_DELAY: Duration = Duration::from_millis(50);
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+pub const MEGABYTE: usize = 1024 * 1024;
+
+// steps skipped by PageUp and PageDown in Result mode
+const STEP_PAGE: usize = 10;
+// a tile slides to its next cell over this many frames of this length
+pub const SLIDE_FRAMES: u16 = 6;
+const SLIDE_FRAME: Duration = Duration::from_millis(30);
+// time between two ticks while the agent moves, one move per tick
+const AGENT_TICK: Duration = Duration::from_millis(100);
+// pace of the demo shown while the editor sits unused, and how long it
+// shows the goal before it scrambles the next board
+const DEMO_MOVE: Duration = Duration::from_millis(400);
+const DEMO_PAUSE: Duration = Duration::from_secs(3);
+// time between two ticks while the screen changes on its own
+const TICK_RATE: Duration = Duration::from_millis(250);
+// and while nothing changes until a key is pressed
+const IDLE_TICK: Duration = Duration::from_secs(2);
+
+// Where the summary popup's export action writes the solution
+const EXPORT_PATH: &str = "solution.txt";
+// and where it writes the playback as an asciicast
+const CAST_EXPORT_PATH: &str = "solution.cast";
+
+// Where play mode exports the game as a replay
+const REPLAY_EXPORT_PATH: &str = "replay.json";
+
+pub enum AppMode {
+    Input,
+    Searching,
+    Summary,
+    Result,
+    Play,
+    Seed,        // prompt for the seed of a scramble
+    GoalTiles,   // prompt for the tiles a search has to put in place
+    Versus,      // two configurations solving the same board
+    History,     // puzzles solved before
+    Analytics,   // charts of the solves in the history
+    Jobs,        // searches sent to the background
+    Presets,     // puzzles of a collection
+    Race,        // the player against the solver
+    Leaderboard, // personal bests of manual play
+    Agent,       // a real-time search agent moving on its own
+}
+
+// The demo that scrambles, solves and plays back random boards while the
+// editor sits unused, and what it covers up.
+pub struct Attract {
+    board: Board,           // being edited when the demo started
+    seed: u64,              // of the next scramble
+    ended: Option<Instant>, // when the last solution finished playing
+}
+
+// Everything a search thread needs, taken from the app when it starts.
+struct SearchRequest {
+    board: Board,
+    goal: GoalContext,
+    algorithm: Algorithm,
+    config: SearchConfig,
+    visualize: bool,
+    known: Option<Solution>, // found without searching, e.g. by turning a solution
+    max_memory: Option<usize>,
+}
+
+impl SearchRequest {
+    // Starts the search on a thread of its own. It reports to the returned
+    // receivers, and stops once the progress one is dropped.
+    fn spawn(
+        self,
+        paused: Arc<AtomicBool>,
+        memory_capped: Arc<AtomicBool>,
+    ) -> (Receiver<SearchOutcome>, Receiver<Progress>) {
+        let SearchRequest {
+            board,
+            goal,
+            algorithm,
+            config,
+            visualize,
+            known,
+            max_memory,
+        } = self;
+        let (tx, rx) = mpsc::channel();
+        let (tx_progress, rx_progress) = mpsc::channel();
+
+        tracing::info!(board = %board.code(), visualize, "search requested");
+        thread::spawn(move || {
+            let mut last_report = Instant::now();
+            let mut observer = |progress: &Progress| {
+                if max_memory.is_some_and(|max| progress.memory > max) {
+                    memory_capped.store(true, Ordering::Relaxed);
+                    return false;
+                }
+                // the queue and the visited boards wait here untouched
+                while paused.load(Ordering::Relaxed) {
+                    thread::sleep(PROGRESS_INTERVAL);
+                    if tx_progress.send(progress.clone()).is_err() {
+                        return false;
+                    }
+                }
+                if visualize {
+                    // slow down so every expansion can be watched
+                    thread::sleep(VISUALIZE_DELAY);
+                } else if last_report.elapsed() < PROGRESS_INTERVAL {
+                    return true;
+                }
+                last_report = Instant::now();
+                // keep going only while the UI is still listening
+                tx_progress.send(progress.clone()).is_ok()
+            };
+            // near-goal boards are answered from the book, and turned boards
+            // by turning their solution, unless the search itself is what the
+            // user wants to watch
+            let from_book = match visualize {
+                true => None,
+                false => known.or_else(|| book::lookup_solution(&board, &goal, &config)),
+            };
+            let mut result = match from_book {
+                Some(solution) => SearchOutcome::Solved(solution),
+                None => algorithm.solve_observed(board, &goal, config, &mut observer),
+            };
+            // beam search keeps only so many boards, so it fits where the others did not
+            if memory_capped.load(Ordering::Relaxed) && algorithm != Algorithm::Beam {
+                tracing::warn!("memory cap reached, falling back to beam search");
+                result = Algorithm::Beam.solve_observed(board, &goal, config, &mut observer);
+            }
+            if let SearchOutcome::Error(e) = &result {
+                tracing::error!("search failed: {}", e);
+            }
+            // the UI stops listening when the search is aborted
+            let _ = tx.send(result);
+        });
+        return (rx, rx_progress);
+    }
+}
+
+// A tile sliding from step `from` to the current step, `frame` frames in.
+pub struct Slide {
+    pub from: usize,
+    pub frame: u16,
+}
+
+/// Everything the TUI shows and how keys and ticks change it. Nothing here
+/// touches the terminal, `main` draws the app and feeds it events.
+pub struct App {
+    pub mode: AppMode,
+    // Input state
+    pub input_board: Board,
+    pub cursor_pos: (usize, usize), // (row, col)
+    pub error_msg: Option<String>,
+    // goal of `goal_preset` for the configured board size, turned over along
+    // with the board by `transform`
+    pub goal: GoalContext,
+    pub goal_preset: GoalPreset,
+    pub goal_input: Board, // the goal as typed into the editor, `goal` once it is valid
+    pub goal_cursor: (usize, usize),
+    pub editing_goal: bool, // the editor keys go to the goal grid instead of the start
+    pub seed: Option<u64>,  // the seed the input board was scrambled from
+    pub seed_input: String,
+    pub scramble_moves: usize,
+    pub goal_tiles: Vec<i64>, // the only tiles a search puts in place, empty for all
+    pub goal_tiles_input: String,
+    pub search_config: SearchConfig,
+    pub algorithm: Algorithm,
+    // exact distances, built in the background on startup
+    pub oracle: Option<Oracle>,
+    pub rx_oracle: Option<Receiver<Oracle>>,
+
+    // the last solution turned over with the board, so that board is solved
+    // without a search
+    pub symmetric: Option<Solution>,
+
+    // Search state
+    pub search_board: Board,
+    pub skip_summary: bool, // the search opens its solution in the step viewer
+    pub rx_result: Option<Receiver<SearchOutcome>>,
+    pub rx_progress: Option<Receiver<Progress>>,
+    pub progress: Option<Progress>,
+    pub estimate: Estimate,      // of how far along the search is
+    pub paused: Arc<AtomicBool>, // the search waits while this is set
+    pub max_memory_mb: u64,      // 0 for no cap
+    pub queue_searches: bool,    // solves wait for the background searches
+    // set once the search hits the memory cap and falls back to beam search
+    pub memory_capped: Arc<AtomicBool>,
+    pub visualize: bool,
+    pub spinner_idx: usize,
+    pub ascii: bool, // plain ASCII borders and no colors
+    pub theme: Theme,
+
+    // Result state
+    pub solution: Solution,
+    pub current_step: usize,
+    pub show_options: bool,     // list the moves available at each step
+    pub show_diagnostics: bool, // where the search spent its effort, over the result
+    pub step_list: ListState,
+    pub status_msg: Option<String>,
+    pub animate: bool,
+    pub slide: Option<Slide>,          // the tile moving between two steps
+    pub playback: Option<Playback>,    // of a replay shown in the step viewer
+    pub optimal: Option<OptimalPaths>, // every shortest solution of the start board
+
+    // Manual play state, macros survive between sessions
+    pub play: PlayState,
+    pub hint_distance: u8,
+    pub cell_order: CellOrder, // of pasted boards
+
+    // Side by side comparison, only while it is on screen
+    pub versus: Option<Versus>,
+
+    // Racing the solver, only while it is on screen
+    pub race: Option<Race>,
+    pub race_pace: Duration,
+
+    // The LRTA* agent, one move per tick unless paused
+    pub agent: Option<Agent>,
+    pub agent_paused: bool,
+
+    // The demo, after the editor sits unused this long
+    pub attract: Option<Attract>,
+    pub attract_after: Option<Duration>,
+    pub last_key: Instant,
+    pub deterministic: bool, // the demo scrambles the same boards every run
+
+    // The guided tour, while it is taken
+    pub tutorial: Option<Tutorial>,
+
+    // Every solve, kept on disk across sessions
+    pub history: History,
+    pub history_path: Option<PathBuf>,
+    pub session_solves: usize, // entries added to the history since the TUI started
+    pub history_list: ListState,
+    pub leaderboard: Leaderboard,
+    pub leaderboard_path: Option<PathBuf>,
+    pub leaderboard_list: ListState,
+
+    // Searches left running while another puzzle is set up
+    pub jobs: Vec<Job>,
+    pub jobs_list: ListState,
+
+    // Puzzles to pick from, only those of the configured board size
+    pub presets: Vec<(Puzzle, Board)>,
+    pub presets_name: String,
+    pub presets_list: ListState,
+
+    // Panics on the worker threads, shown in a popup over any screen
+    pub rx_crash: Option<Receiver<crash::Report>>,
+    pub crash: Option<crash::Report>,
+    pub crash_msg: Option<String>, // outcome of copying the report
+}
+
+impl App {
+    pub fn new(config: &Config) -> App {
+        App {
+            mode: AppMode::Input,
+            input_board: Board::empty(config.board_size.rows, config.board_size.cols),
+            cursor_pos: (0, 0),
+            error_msg: None,
+            goal: config.goal(),
+            goal_preset: config.goal,
+            goal_input: *config.goal().goal(),
+            goal_cursor: (0, 0),
+            editing_goal: false,
+            seed: None,
+            seed_input: String::new(),
+            scramble_moves: board::DEFAULT_SCRAMBLE_MOVES,
+            goal_tiles: Vec::new(),
+            goal_tiles_input: String::new(),
+            search_config: config.search,
+            algorithm: config.algorithm,
+            oracle: None,
+            rx_oracle: None,
+            symmetric: None,
+            search_board: Board::empty(config.board_size.rows, config.board_size.cols),
+            skip_summary: false,
+            rx_result: None,
+            rx_progress: None,
+            progress: None,
+            estimate: Estimate::new(),
+            paused: Arc::new(AtomicBool::new(false)),
+            max_memory_mb: config.max_memory_mb,
+            queue_searches: config.queue_searches,
+            memory_capped: Arc::new(AtomicBool::new(false)),
+            visualize: false,
+            spinner_idx: 0,
+            ascii: config.ascii.unwrap_or_else(ascii::terminal_lacks_color),
+            theme: config.theme,
+            solution: Solution::default(),
+            current_step: 0,
+            show_options: false,
+            show_diagnostics: false,
+            step_list: ListState::default(),
+            status_msg: None,
+            animate: config.animate,
+            slide: None,
+            playback: None,
+            optimal: None,
+            play: PlayState::new(),
+            hint_distance: config.hint_distance,
+            cell_order: config.cell_order,
+            versus: None,
+            race: None,
+            agent: None,
+            agent_paused: false,
+            attract: None,
+            attract_after: (config.attract_after > 0)
+                .then(|| Duration::from_secs(config.attract_after)),
+            last_key: Instant::now(),
+            deterministic: config.deterministic,
+            tutorial: None,
+            race_pace: Duration::from_millis(config.race_pace_ms),
+            history: History::default(),
+            history_path: None,
+            session_solves: 0,
+            history_list: ListState::default(),
+            leaderboard: Leaderboard::default(),
+            leaderboard_path: None,
+            leaderboard_list: ListState::default(),
+            jobs: Vec::new(),
+            jobs_list: ListState::default(),
+            presets: Vec::new(),
+            presets_name: String::new(),
+            presets_list: ListState::default(),
+            rx_crash: None,
+            crash: None,
+            crash_msg: None,
+        }
+    }
+
+    fn cycle_heuristic(&mut self) {
+        let all = Heuristic::ALL;
+        let idx = all
+            .iter()
+            .position(|h| *h == self.search_config.heuristic)
+            .unwrap_or(0);
+        self.search_config.heuristic = all[(idx + 1) % all.len()];
+    }
+
+    fn cycle_algorithm(&mut self) {
+        let all = Algorithm::ALL;
+        let idx = all.iter().position(|a| *a == self.algorithm).unwrap_or(0);
+        self.algorithm = all[(idx + 1) % all.len()];
+    }
+
+    fn cycle_weight(&mut self) {
+        let idx = WEIGHTS
+            .iter()
+            .position(|w| *w == self.search_config.weight)
+            .unwrap_or(0);
+        self.search_config.weight = WEIGHTS[(idx + 1) % WEIGHTS.len()];
+    }
+
+    fn cycle_cost(&mut self) {
+        let all = CostModel::ALL;
+        let idx = all
+            .iter()
+            .position(|c| *c == self.search_config.cost)
+            .unwrap_or(0);
+        self.search_config.cost = all[(idx + 1) % all.len()];
+    }
+
+    fn cycle_goal(&mut self) {
+        let all = GoalPreset::ALL;
+        let idx = all.iter().position(|g| *g == self.goal_preset).unwrap_or(0);
+        self.set_goal_preset(all[(idx + 1) % all.len()]);
+        self.start_oracle();
+    }
+
+    fn set_goal_preset(&mut self, preset: GoalPreset) {
+        let goal = self.goal.goal();
+        self.set_goal(GoalContext::for_preset(preset, goal.rows(), goal.cols()));
+        self.goal_preset = preset;
+    }
+
+    // The exact distances were to the old goal, whoever needs them has to
+    // wait for the new ones.
+    fn set_goal(&mut self, goal: GoalContext) {
+        self.goal_input = *goal.goal();
+        self.goal = goal;
+        self.oracle = None;
+        self.rx_oracle = None;
+    }
+
+    // Makes the goal typed into the editor the one searches head for. Both
+    // boards are valid by then, so they hold the same tiles.
+    fn commit_goal(&mut self) {
+        if self.goal_input != *self.goal.goal() {
+            self.set_goal(GoalContext::new(self.goal_input));
+            self.start_oracle();
+        }
+    }
+
+    // Commits the goal grid before something heads for the goal, or says
+    // what is wrong with it.
+    fn accept_goal(&mut self) -> bool {
+        if !self.goal_input.is_valid() {
+            self.error_msg = Some(format!(
+                "Invalid Goal: Must contain 1-{} unique & 1 empty.",
+                self.goal_input.max_tile()
+            ));
+            return false;
+        }
+        self.commit_goal();
+        return true;
+    }
+
+    // The grid of the editor the keys go to, and its cursor.
+    fn focused_grid(&mut self) -> (&mut Board, &mut (usize, usize)) {
+        return match self.editing_goal {
+            true => (&mut self.goal_input, &mut self.goal_cursor),
+            false => (&mut self.input_board, &mut self.cursor_pos),
+        };
+    }
+
+    /// Rotates or mirrors the board being edited, and the goal with it, so
+    /// the puzzle stays the same: a solution of the board is carried over to
+    /// the turned one instead of searching again.
+    pub fn transform(&mut self, symmetry: Symmetry) {
+        let goal = self.search_goal();
+        let solved = |solution: &Solution| {
+            return solution.path.first() == Some(&self.input_board)
+                && solution.path.last().is_some_and(|last| goal.is_goal(last));
+        };
+        let known = self
+            .symmetric
+            .take()
+            .filter(solved)
+            .or_else(|| Some(self.solution.clone()).filter(solved));
+
+        let (rows, cols) = (self.input_board.rows(), self.input_board.cols());
+        self.cursor_pos = symmetry.cell(self.cursor_pos, rows, cols);
+        self.goal_cursor = symmetry.cell(self.goal_cursor, rows, cols);
+        self.input_board = symmetry.board(&self.input_board);
+        // a goal edit not committed yet is turned along, not thrown away
+        let goal_input = symmetry.board(&self.goal_input);
+        self.set_goal(symmetry.goal(&self.goal));
+        self.goal_input = goal_input;
+        let goal = self.search_goal();
+        self.symmetric =
+            known.map(|solution| symmetry.solution(&solution, &goal, self.search_config.heuristic));
+        self.start_oracle();
+    }
+
+    /// What is worth keeping for the next launch.
+    pub fn snapshot(&self) -> Session {
+        let screen = match self.mode {
+            AppMode::Summary | AppMode::Result => Screen::Result,
+            AppMode::Play => Screen::Play,
+            _ => Screen::Input,
+        };
+        // only a game that was actually started
+        let play = match screen == Screen::Play || !self.play.moves.is_empty() {
+            true => Some(SavedPlay {
+                start: self.play.start.code(),
+                moves: notation::to_letters(&self.play.moves),
+                macros_used: self.play.macros_used,
+                solver_moves: self.play.solver_moves,
+                elapsed_ms: self.play.elapsed().as_millis() as u64,
+                move_times_ms: self
+                    .play
+                    .move_times
+                    .iter()
+                    .map(|at| at.as_millis() as u64)
+                    .collect(),
+            }),
+            false => None,
+        };
+        let goal = self.goal.goal();
+        return Session {
+            board_size: format!("{}x{}", goal.rows(), goal.cols()),
+            goal: self.goal_preset.name().to_string(),
+            goal_board: goal.code(),
+            goal_tiles: self.goal_tiles.clone(),
+            board: self.input_board.code(),
+            seed: self.seed,
+            scramble_moves: self.scramble_moves,
+            heuristic: self.search_config.heuristic.name().to_string(),
+            weight: self.search_config.weight,
+            visualize: self.visualize,
+            screen,
+            current_step: self.current_step,
+            macros: self
+                .play
+                .macros
+                .iter()
+                .map(|m| m.as_deref().map(notation::to_letters).unwrap_or_default())
+                .collect(),
+            solution: SavedSolution::new(&self.solution),
+            play,
+        };
+    }
+
+    /// Picks up where `session` left off. Sessions saved for another board
+    /// size are ignored, and so is anything that no longer makes sense.
+    pub fn restore(&mut self, session: Session) {
+        let goal = *self.goal.goal();
+        let (rows, cols) = (goal.rows(), goal.cols());
+        if session.board_size != format!("{}x{}", rows, cols) {
+            return;
+        }
+
+        // sessions from before there was a choice were all for the standard goal
+        let preset = match session.goal.as_str() {
+            "" => Ok(GoalPreset::Standard),
+            name => name.parse(),
+        };
+        if let Ok(preset) = preset {
+            self.set_goal_preset(preset);
+        }
+        // a goal typed into the editor, or the preset turned over with the board
+        if let Some(board) = session::board_from_code(&session.goal_board, rows, cols)
+            && board.is_valid()
+            && board != *self.goal.goal()
+        {
+            self.set_goal(GoalContext::new(board));
+        }
+        let partial = GoalContext::partial(*self.goal.goal(), &session.goal_tiles);
+        if partial.is_partial() && !partial.targets().is_empty() {
+            self.goal_tiles = partial.targets();
+            self.goal_tiles_input = join_tiles(&self.goal_tiles);
+        }
+        if let Ok(heuristic) = session.heuristic.parse() {
+            self.search_config.heuristic = heuristic;
+        }
+        if session.weight >= 1 {
+            self.search_config.weight = session.weight;
+        }
+        self.visualize = session.visualize;
+        if let Some(board) = session::board_from_code(&session.board, rows, cols) {
+            self.input_board = board;
+            self.seed = session.seed;
+        }
+        if session.scramble_moves > 0 {
+            self.scramble_moves = session.scramble_moves;
+        }
+        for (slot, text) in self.play.macros.iter_mut().zip(&session.macros) {
+            *slot = match notation::parse_lurd(text) {
+                Ok(moves) if !moves.is_empty() => Some(moves),
+                _ => None,
+            };
+        }
+
+        let mut screen = Screen::Input;
+        if let Some(solution) = session
+            .solution
+            .and_then(|saved| saved.restore(&self.goal, self.search_config.heuristic))
+        {
+            self.current_step = session.current_step.min(solution.moves());
+            self.solution = solution;
+            if session.screen == Screen::Result {
+                screen = Screen::Result;
+            }
+        }
+        if let Some(saved) = session.play
+            && let Some(start) = session::board_from_code(&saved.start, rows, cols)
+            && let Ok(moves) = notation::parse_lurd(&saved.moves)
+            && let Some(boards) = notation::apply_moves(&start, &moves)
+        {
+            self.play.restart(start, &self.goal);
+            self.play.board = boards[boards.len() - 1];
+            self.play.move_times = saved
+                .move_times_ms
+                .iter()
+                .map(|ms| Duration::from_millis(*ms))
+                .collect();
+            self.play.move_times.resize(moves.len(), Duration::ZERO); // from older sessions
+            self.play.moves = moves;
+            self.play.macros_used = saved.macros_used;
+            self.play.solver_moves = saved.solver_moves;
+            self.play
+                .resume_clock(Duration::from_millis(saved.elapsed_ms));
+            if session.screen == Screen::Play {
+                screen = Screen::Play;
+            }
+        }
+        self.mode = match screen {
+            Screen::Input => AppMode::Input,
+            Screen::Result => AppMode::Result,
+            Screen::Play => AppMode::Play,
+        };
+    }
+
+    // Adds the solution that just came in to the history and saves it.
+    fn record_solve(&mut self, algorithm: Algorithm, config: SearchConfig, solution: &Solution) {
+        // solutions for only some of the tiles would not replay to the goal
+        if solution.path.last() != Some(self.goal.goal()) {
+            return;
+        }
+        let Some(solution) = SavedSolution::new(solution) else {
+            return;
+        };
+        let goal = self.goal.goal();
+        self.history.push(HistoryEntry {
+            board_size: format!("{}x{}", goal.rows(), goal.cols()),
+            algorithm: algorithm.name().to_string(),
+            heuristic: config.heuristic.name().to_string(),
+            weight: config.weight,
+            solution,
+        });
+        self.session_solves += 1;
+        if let Some(path) = &self.history_path
+            && let Err(e) = self.history.save(path)
+        {
+            self.status_msg = Some(format!("Cannot save the history: {:#}", e));
+        }
+    }
+
+    fn open_history(&mut self) {
+        if self.history.entries.is_empty() {
+            self.error_msg = Some("No puzzles solved yet.".to_string());
+            return;
+        }
+        self.error_msg = None;
+        self.history_list.select(Some(0));
+        self.mode = AppMode::History;
+    }
+
+    fn open_analytics(&mut self) {
+        if self.history.entries.is_empty() {
+            self.error_msg = Some("No puzzles solved yet.".to_string());
+            return;
+        }
+        self.error_msg = None;
+        self.mode = AppMode::Analytics;
+    }
+
+    // Shows the selected history entry (newest first) in the Result view.
+    fn load_history_entry(&mut self) {
+        let newest_first = self.history.entries.iter().rev();
+        let Some(entry) = self
+            .history_list
+            .selected()
+            .and_then(|i| newest_first.clone().nth(i))
+        else {
+            return;
+        };
+        let goal = self.goal.goal();
+        let size = format!("{}x{}", goal.rows(), goal.cols());
+        if entry.board_size != size {
+            self.error_msg = Some(format!(
+                "Solved on a {} board, restart with --board-size {}.",
+                entry.board_size, entry.board_size
+            ));
+            self.mode = AppMode::Input;
+            return;
+        }
+        let heuristic = entry
+            .heuristic
+            .parse()
+            .unwrap_or(self.search_config.heuristic);
+        match entry.solution.restore(&self.goal, heuristic) {
+            Some(solution) => {
+                self.input_board = solution.path[0];
+                self.seed = None;
+                self.solution = solution;
+                self.current_step = 0;
+                self.status_msg = None;
+                self.mode = AppMode::Result;
+            }
+            None => {
+                self.error_msg = Some("This history entry is damaged.".to_string());
+                self.mode = AppMode::Input;
+            }
+        }
+    }
+
+    /// The newest solution in the history of the board being edited that
+    /// heads for the current goal.
+    pub fn previous_solve(&self) -> Option<Solution> {
+        if !self.input_board.is_valid() || !self.goal_tiles.is_empty() {
+            return None;
+        }
+        return self.history.solves_of(&self.input_board).find_map(|entry| {
+            let heuristic = entry
+                .heuristic
+                .parse()
+                .unwrap_or(self.search_config.heuristic);
+            let solution = entry.solution.restore(&self.goal, heuristic)?;
+            return (solution.path.last() == Some(self.goal.goal())).then_some(solution);
+        });
+    }
+
+    /// A note when the board being edited was solved before or is one of the
+    /// presets, so it need not be searched again.
+    pub fn known_board(&self) -> Option<String> {
+        let preset = self
+            .presets
+            .iter()
+            .find(|(_, board)| *board == self.input_board)
+            .map(|(puzzle, _)| puzzle);
+        let mut note = match preset {
+            Some(puzzle) if !puzzle.name.is_empty() => format!("Preset \"{}\"", puzzle.name),
+            Some(_) => "A preset".to_string(),
+            None => String::new(),
+        };
+        match self.previous_solve() {
+            Some(solution) => {
+                let solved = format!("solved in {} moves", solution.moves());
+                note = match note.is_empty() {
+                    true => format!("Previously {}", solved),
+                    false => format!("{}, previously {}", note, solved),
+                };
+                note.push_str(" - press l to load that solution.");
+            }
+            None if !note.is_empty() => note.push('.'),
+            None => return None,
+        }
+        return Some(note);
+    }
+
+    // Starts the demo once the editor has sat unused long enough, and moves
+    // it on to the next board once a solution has played.
+    fn tick_attract(&mut self) {
+        let Some(attract) = &mut self.attract else {
+            let idle = self
+                .attract_after
+                .is_some_and(|after| self.last_key.elapsed() >= after);
+            if idle && matches!(self.mode, AppMode::Input) {
+                tracing::info!("demo started");
+                self.attract = Some(Attract {
+                    board: self.input_board,
+                    seed: rng::fresh_seed(self.deterministic),
+                    ended: None,
+                });
+                self.next_demo();
+            }
+            return;
+        };
+        let next = match self.mode {
+            AppMode::Result if self.current_step >= self.solution.moves() => {
+                attract.ended.get_or_insert_with(Instant::now).elapsed() >= DEMO_PAUSE
+            }
+            AppMode::Result | AppMode::Searching => false,
+            _ => true, // the search failed
+        };
+        if next {
+            self.next_demo();
+        }
+    }
+
+    // Scrambles and solves the next board of the demo.
+    fn next_demo(&mut self) {
+        let Some(attract) = &mut self.attract else {
+            return;
+        };
+        let board = board::scramble_from(self.goal.goal(), attract.seed, self.scramble_moves);
+        attract.seed = attract.seed.wrapping_add(1);
+        self.input_board = board;
+        self.playback = None;
+        self.start_search(board);
+        self.skip_summary = true;
+    }
+
+    // Goes back to the editor as the demo found it.
+    fn stop_attract(&mut self) {
+        let Some(attract) = self.attract.take() else {
+            return;
+        };
+        tracing::info!("demo stopped");
+        self.rx_result = None;
+        self.rx_progress = None;
+        self.playback = None;
+        self.slide = None;
+        self.input_board = attract.board;
+        self.error_msg = None;
+        self.status_msg = None;
+        self.mode = AppMode::Input;
+    }
+
+    // Shows the solution of the board being edited from the history.
+    fn load_previous_solve(&mut self) {
+        if let Some(solution) = self.previous_solve() {
+            self.solution = solution;
+            self.current_step = 0;
+            self.status_msg = None;
+            self.error_msg = None;
+            self.mode = AppMode::Result;
+        }
+    }
+
+    // Adds the game just solved by hand to the leaderboard and saves it.
+    // Games the solver finished do not count.
+    fn record_play(&mut self) {
+        let Some(time) = self.play.time else {
+            return;
+        };
+        if self.play.solver_moves > 0 {
+            return;
+        }
+        let goal = self.goal.goal();
+        let improved = self.leaderboard.record(Solve {
+            board_size: format!("{}x{}", goal.rows(), goal.cols()),
+            start: self.play.start.code(),
+            seed: self
+                .scramble_seed(&self.play.start)
+                .map(|seed| (seed, self.scramble_moves)),
+            optimal: self.play.review.as_ref().map(|r| r.optimal),
+            moves: self.play.moves.len(),
+            time,
+        });
+        let best = match (improved.time, improved.moves) {
+            (true, true) => Some("New best time and fewest moves!"),
+            (true, false) => Some("New best time!"),
+            (false, true) => Some("New fewest moves!"),
+            (false, false) => None,
+        };
+        if let Some(best) = best {
+            self.play.message = Some(match &self.play.message {
+                Some(review) => format!("{} {}", best, review),
+                None => best.to_string(),
+            });
+        }
+        if let Some(path) = &self.leaderboard_path
+            && let Err(e) = self.leaderboard.save(path)
+        {
+            self.play.message = Some(format!("Cannot save the leaderboard: {:#}", e));
+        }
+    }
+
+    fn export_replay(&mut self) {
+        let replay = Replay::from_play(&self.play);
+        self.play.message = Some(match replay.save(Path::new(REPLAY_EXPORT_PATH)) {
+            Ok(()) => format!("Saved the game to {}.", REPLAY_EXPORT_PATH),
+            Err(e) => format!("Cannot save the game: {:#}", e),
+        });
+    }
+
+    // Plays `replay` back in the step viewer.
+    pub fn open_replay(&mut self, replay: &Replay) {
+        match replay.solution(&self.goal, self.search_config.heuristic) {
+            Ok((solution, times)) => {
+                self.input_board = solution.path[0];
+                self.seed = None;
+                self.solution = solution;
+                self.current_step = 0;
+                self.status_msg = None;
+                self.playback = Some(Playback::new(times));
+                self.mode = AppMode::Result;
+            }
+            Err(e) => self.error_msg = Some(format!("Cannot open the replay: {:#}", e)),
+        }
+    }
+
+    fn open_leaderboard(&mut self) {
+        if self.leaderboard.records.is_empty() {
+            self.error_msg = Some("No puzzles solved by hand yet.".to_string());
+            return;
+        }
+        self.error_msg = None;
+        self.leaderboard_list.select(Some(0));
+        self.mode = AppMode::Leaderboard;
+    }
+
+    // Plays the selected leaderboard puzzle again.
+    fn replay_record(&mut self) {
+        let ranked = self.leaderboard.ranked();
+        let Some(record) = self.leaderboard_list.selected().and_then(|i| ranked.get(i)) else {
+            return;
+        };
+        let goal = self.goal.goal();
+        let Some(start) = session::board_from_code(&record.start, goal.rows(), goal.cols()) else {
+            self.error_msg = Some(format!(
+                "Solved on a {} board, restart with --board-size {}.",
+                record.board_size, record.board_size
+            ));
+            self.mode = AppMode::Input;
+            return;
+        };
+        self.seed = record.seed;
+        if let Some(moves) = record.scramble_moves {
+            self.scramble_moves = moves;
+        }
+        self.input_board = start;
+        self.play.restart(start, &self.goal);
+        self.error_msg = None;
+        self.mode = AppMode::Play;
+    }
+
+    /// The seed `board` was scrambled from, if it still is the scramble.
+    pub fn scramble_seed(&self, board: &Board) -> Option<u64> {
+        let seed = self.seed?;
+        return (board::scramble_from(self.goal.goal(), seed, self.scramble_moves) == *board)
+            .then_some(seed);
+    }
+
+    pub fn scramble(&mut self, seed: u64) {
+        self.input_board = board::scramble_from(self.goal.goal(), seed, self.scramble_moves);
+        self.seed = Some(seed);
+        self.error_msg = None;
+    }
+
+    // Loads the hardest board after the one being edited, if it is one.
+    // Keeps the puzzles that fit the board, in the order of the collection.
+    pub fn set_presets(&mut self, collection: Collection) {
+        self.presets = collection
+            .puzzles
+            .into_iter()
+            .filter_map(|puzzle| {
+                let board = puzzle.board(&self.goal).ok()?;
+                return Some((puzzle, board));
+            })
+            .collect();
+        self.presets_name = collection.name;
+    }
+
+    fn open_presets(&mut self) {
+        if self.presets.is_empty() {
+            let goal = self.goal.goal();
+            self.error_msg = Some(format!(
+                "No presets for {}x{} boards.",
+                goal.rows(),
+                goal.cols()
+            ));
+            return;
+        }
+        self.error_msg = None;
+        self.presets_list.select(Some(0));
+        self.mode = AppMode::Presets;
+    }
+
+    fn load_preset(&mut self) {
+        let Some((_, board)) = self
+            .presets_list
+            .selected()
+            .and_then(|i| self.presets.get(i))
+        else {
+            return;
+        };
+        self.input_board = *board;
+        self.seed = None;
+        self.mode = AppMode::Input;
+    }
+
+    fn start_race(&mut self) {
+        if !self.input_board.is_valid() {
+            self.error_msg = Some(format!(
+                "Invalid Board: Must contain 1-{} unique & 1 empty.",
+                self.input_board.max_tile()
+            ));
+            return;
+        }
+        if !self.accept_goal() {
+            return;
+        }
+        let moves = hint::hint(&self.input_board, &self.goal, self.oracle.as_ref(), u8::MAX);
+        let path = moves.and_then(|moves| notation::apply_moves(&self.input_board, &moves));
+        match path {
+            Some(path) if path.len() > 1 => {
+                self.race = Some(Race::new(path, &self.goal, self.race_pace));
+                self.error_msg = None;
+                self.mode = AppMode::Race;
+            }
+            Some(_) => self.error_msg = Some("The board is already solved.".to_string()),
+            None => self.error_msg = Some("Unsolvable: the goal cannot be reached".to_string()),
+        }
+    }
+
+    fn start_agent(&mut self) {
+        if !self.input_board.is_valid() {
+            self.error_msg = Some(format!(
+                "Invalid Board: Must contain 1-{} unique & 1 empty.",
+                self.input_board.max_tile()
+            ));
+            return;
+        }
+        if !self.accept_goal() {
+            return;
+        }
+        let goal = self.search_goal();
+        // the agent would wander forever
+        if !goal.is_reachable(&self.input_board) {
+            self.error_msg = Some("Unsolvable: the goal cannot be reached".to_string());
+            return;
+        }
+        self.agent = Some(Agent::new(
+            self.input_board,
+            &goal,
+            self.search_config.heuristic,
+            self.search_config.cost,
+        ));
+        self.agent_paused = false;
+        self.error_msg = None;
+        self.mode = AppMode::Agent;
+    }
+
+    fn load_hardest(&mut self) {
+        if !self.accept_goal() {
+            return;
+        }
+        let Some(oracle) = &self.oracle else {
+            self.error_msg = Some(match self.rx_oracle {
+                Some(_) => "The exact distances are still being computed.".to_string(),
+                None => format!(
+                    "The hardest boards are only known for boards of up to {} cells.",
+                    oracle::MAX_CELLS
+                ),
+            });
+            return;
+        };
+        let hardest = oracle.hardest();
+        let next = match hardest.iter().position(|b| *b == self.input_board) {
+            Some(i) => (i + 1) % hardest.len(),
+            None => 0,
+        };
+        if let Some(board) = hardest.get(next) {
+            self.input_board = *board;
+            self.seed = None;
+            self.error_msg = None;
+        }
+    }
+
+    pub fn start_oracle(&mut self) {
+        if self.input_board.rows() * self.input_board.cols() > oracle::MAX_CELLS {
+            return; // difficulty falls back to the estimate
+        }
+        let (tx, rx) = mpsc::channel();
+        self.rx_oracle = Some(rx);
+        let goal = self.goal.clone();
+        thread::spawn(move || {
+            // the app may already be gone, nothing to report then
+            let _ = tx.send(Oracle::build(&goal));
+        });
+    }
+
+    /// Text describing how hard the input board is, or `None` while the board
+    /// is still incomplete or invalid.
+    pub fn difficulty(&self) -> Option<String> {
+        if !self.input_board.is_valid() || !self.goal_input.is_valid() {
+            return None;
+        }
+
+        // the exact distances are to the whole goal, and only to the one
+        // committed, not to an edit of the goal grid
+        let edited = self.goal_input != *self.goal.goal();
+        let oracle = self.oracle.as_ref();
+        if let Some(oracle) = oracle.filter(|_| self.goal_tiles.is_empty() && !edited) {
+            return Some(match oracle.distance(&self.input_board) {
+                Some(d) => format!("Optimal solution: {} moves", d),
+                None => "Unsolvable: the goal cannot be reached".to_string(),
+            });
+        }
+
+        let goal = match (edited, self.goal_tiles.is_empty()) {
+            (true, true) => GoalContext::new(self.goal_input),
+            (true, false) => GoalContext::partial(self.goal_input, &self.goal_tiles),
+            (false, _) => self.search_goal(),
+        };
+        if !goal.is_reachable(&self.input_board) {
+            return Some("Unsolvable: the goal cannot be reached".to_string());
+        }
+
+        let bound = Heuristic::LinearConflict.estimate(&self.input_board, &goal);
+        Some(format!("Estimated solution: at least {} moves", bound))
+    }
+
+    /// The goal searches stop at: the whole goal, or only the selected tiles.
+    pub fn search_goal(&self) -> GoalContext {
+        if self.goal_tiles.is_empty() {
+            return self.goal.clone();
+        }
+        return GoalContext::partial(*self.goal.goal(), &self.goal_tiles);
+    }
+
+    // Takes the tiles typed into the prompt, e.g. "1 2 3" for the top row.
+    fn set_goal_tiles(&mut self) {
+        let max_tile = self.goal.goal().max_tile();
+        let tiles: Result<Vec<i64>, _> = self
+            .goal_tiles_input
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<i64>())
+            .collect();
+        match tiles {
+            Ok(tiles) if tiles.iter().all(|n| (1..=max_tile).contains(n)) => {
+                let goal = GoalContext::partial(*self.goal.goal(), &tiles);
+                // every tile is the same as the whole goal
+                self.goal_tiles = match goal.is_partial() {
+                    true => goal.targets(),
+                    false => Vec::new(),
+                };
+                self.error_msg = None;
+            }
+            _ => {
+                self.error_msg = Some(format!(
+                    "Goal tiles must be numbers from 1 to {}.",
+                    max_tile
+                ))
+            }
+        }
+    }
+
+    /// Solves `board` as if it had been typed into the editor, then goes
+    /// straight to the step viewer instead of the summary.
+    pub fn solve_on_launch(&mut self, board: Board) {
+        self.input_board = board;
+        self.seed = None;
+        self.start_search(board);
+        self.skip_summary = true;
+    }
+
+    fn start_search(&mut self, board: Board) {
+        self.mode = AppMode::Searching;
+        self.error_msg = None;
+        self.skip_summary = false;
+
+        let goal = self.search_goal();
+        let symmetric = self.symmetric.take().filter(|solution| {
+            solution.path.first() == Some(&board)
+                && solution.path.last().is_some_and(|last| goal.is_goal(last))
+        });
+        self.search_board = board;
+        self.progress = None;
+        self.estimate = Estimate::new();
+        self.paused = Arc::new(AtomicBool::new(false));
+        self.memory_capped = Arc::new(AtomicBool::new(false));
+        let request = SearchRequest {
+            board,
+            goal,
+            algorithm: self.algorithm,
+            config: self.search_config,
+            visualize: self.visualize,
+            known: symmetric,
+            max_memory: self.max_memory(),
+        };
+        let (rx, rx_progress) =
+            request.spawn(Arc::clone(&self.paused), Arc::clone(&self.memory_capped));
+        self.rx_result = Some(rx);
+        self.rx_progress = Some(rx_progress);
+    }
+
+    fn max_memory(&self) -> Option<usize> {
+        return (self.max_memory_mb > 0).then_some(self.max_memory_mb as usize * MEGABYTE);
+    }
+
+    // Whether a solve has to wait for the searches in the background.
+    fn must_queue(&self) -> bool {
+        return self.queue_searches
+            && self
+                .jobs
+                .iter()
+                .any(|job| job.is_running() || job.is_waiting());
+    }
+
+    // Searches `board` now, or after the background searches when they
+    // have to go first.
+    fn solve(&mut self, board: Board) {
+        match self.must_queue() {
+            true => self.queue_search(board),
+            false => self.start_search(board),
+        }
+    }
+
+    // Lines up a search of `board` behind the background searches.
+    fn queue_search(&mut self, board: Board) {
+        tracing::info!(board = %board.code(), "search queued");
+        self.error_msg = None;
+        self.jobs.push(Job::queued(
+            board,
+            self.search_goal(),
+            self.algorithm,
+            self.search_config,
+        ));
+    }
+
+    // Starts the first queued search once no other one runs.
+    fn start_queued(&mut self) {
+        if self.jobs.iter().any(|job| job.is_running()) {
+            return;
+        }
+        let max_memory = self.max_memory();
+        let Some(job) = self.jobs.iter_mut().find(|job| job.is_waiting()) else {
+            return;
+        };
+        tracing::info!(board = %job.board.code(), "queued search started");
+        let request = SearchRequest {
+            board: job.board,
+            goal: job.goal.clone(),
+            algorithm: job.algorithm,
+            config: job.config,
+            visualize: false,
+            known: None,
+            max_memory,
+        };
+        // nothing pauses it, and it keeps to its algorithm past the memory cap
+        let (rx, rx_progress) = request.spawn(
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(AtomicBool::new(false)),
+        );
+        job.start(rx, rx_progress);
+    }
+
+    // Leaves the running search to itself and goes back to the Input screen.
+    fn send_to_background(&mut self) {
+        let (Some(rx_result), Some(rx_progress)) = (self.rx_result.take(), self.rx_progress.take())
+        else {
+            return;
+        };
+        // nothing could resume it from the background
+        self.paused.store(false, Ordering::Relaxed);
+        let mut job = Job::new(
+            self.search_board,
+            self.search_goal(),
+            self.algorithm,
+            self.search_config,
+            rx_result,
+            rx_progress,
+        );
+        job.progress = self.progress.take();
+        tracing::info!(board = %job.board.code(), "search sent to the background");
+        self.jobs.push(job);
+        self.mode = AppMode::Input;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        return self.paused.load(Ordering::Relaxed);
+    }
+
+    fn toggle_pause(&mut self) {
+        let paused = !self.is_paused();
+        self.paused.store(paused, Ordering::Relaxed);
+        tracing::info!(paused, "search pause toggled");
+    }
+
+    // Counts the shortest solutions of the board being viewed.
+    fn count_optimal(&mut self) {
+        // the exact distances are to the whole goal
+        if !self.goal_tiles.is_empty() {
+            self.status_msg = Some(
+                "Counting optimal solutions is not supported for partial goals (g in the editor)."
+                    .to_string(),
+            );
+            return;
+        }
+        let Some(oracle) = &self.oracle else {
+            self.status_msg = Some(match self.rx_oracle {
+                Some(_) => "The exact distances are still being computed.".to_string(),
+                None => format!(
+                    "Counting optimal solutions needs a board of at most {} cells.",
+                    oracle::MAX_CELLS
+                ),
+            });
+            return;
+        };
+        if self.solution.path.last() != Some(self.goal.goal()) {
+            self.status_msg = Some(
+                "Counting optimal solutions needs a solution that ends at the goal.".to_string(),
+            );
+            return;
+        }
+        let start = self.solution.path[0];
+        let Some(length) = oracle.distance(&start) else {
+            return;
+        };
+        let exact = |b: &Board| oracle.distance(b).map_or(i64::MAX, |d| d as i64);
+        let paths = OptimalPaths::count(start, &self.goal, length as usize, &exact);
+        self.status_msg = Some(match paths.index_of(&self.solution.path) {
+            Some(index) => format!(
+                "Optimal solution {} of {} (n/N: next/previous)",
+                index + 1,
+                paths.len()
+            ),
+            None => format!(
+                "Not optimal: {} solutions of {} moves exist (n: show them)",
+                paths.len(),
+                length
+            ),
+        });
+        self.optimal = Some(paths);
+    }
+
+    // Replaces the solution with the next (or previous) shortest one.
+    fn browse_optimal(&mut self, forward: bool) {
+        let Some(paths) = self
+            .optimal
+            .as_ref()
+            .filter(|paths| Some(&paths.start()) == self.solution.path.first())
+        else {
+            return;
+        };
+        let count = paths.len();
+        let index = match (paths.index_of(&self.solution.path), forward) {
+            (None, _) => 0,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        let Some(path) = paths.nth(index) else {
+            return;
+        };
+        self.solution.directions = notation::moves(&path);
+        self.solution.steps = a_star::annotate(&path, &self.goal, self.search_config.heuristic);
+        self.solution.path = path;
+        self.solution.guaranteed_optimal = true;
+        self.current_step = self.current_step.min(self.solution.moves());
+        self.status_msg = Some(format!(
+            "Optimal solution {} of {} (n/N: next/previous)",
+            index + 1,
+            count
+        ));
+    }
+
+    fn open_jobs(&mut self) {
+        if self.jobs.is_empty() {
+            self.error_msg = Some("No searches in the background.".to_string());
+            return;
+        }
+        self.error_msg = None;
+        self.jobs_list.select(Some(0));
+        self.mode = AppMode::Jobs;
+    }
+
+    // Shows the selected background search in the Result view, once solved.
+    fn load_job(&mut self) {
+        let Some(job) = self.jobs_list.selected().and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+        if let Outcome::Solved(solution) = &job.outcome {
+            self.input_board = job.board;
+            self.seed = None;
+            self.solution = solution.clone();
+            self.current_step = 0;
+            self.status_msg = None;
+            self.mode = AppMode::Result;
+        }
+    }
+
+    // Drops the selected background search, stopping it if it still runs.
+    fn remove_job(&mut self) {
+        let Some(i) = self.jobs_list.selected().filter(|i| *i < self.jobs.len()) else {
+            return;
+        };
+        self.jobs.remove(i);
+        if self.jobs.is_empty() {
+            self.mode = AppMode::Input;
+        } else {
+            self.jobs_list.select(Some(i.min(self.jobs.len() - 1)));
+        }
+    }
+
+    fn export_solution(&mut self) {
+        self.status_msg = Some(
+            match fs::write(EXPORT_PATH, export::to_text(&self.solution)) {
+                Ok(()) => format!("Saved the solution to {}.", EXPORT_PATH),
+                Err(e) => format!("Cannot save the solution: {}", e),
+            },
+        );
+    }
+
+    fn export_cast(&mut self) {
+        self.status_msg = Some(
+            match fs::write(CAST_EXPORT_PATH, export::to_asciicast(&self.solution)) {
+                Ok(()) => format!("Saved the playback to {}.", CAST_EXPORT_PATH),
+                Err(e) => format!("Cannot save the playback: {}", e),
+            },
+        );
+    }
+
+    fn paste_board(&mut self) {
+        match clipboard::paste() {
+            Ok(text) => match validate::check_import(&text, &self.goal, self.cell_order) {
+                Ok(board) => {
+                    self.input_board = board;
+                    self.error_msg = None;
+                }
+                Err(e) => self.error_msg = Some(format!("Cannot paste board: {}", e)),
+            },
+            Err(e) => self.error_msg = Some(e),
+        }
+    }
+
+    fn copy_crash_report(&mut self) {
+        if let Some(report) = &self.crash {
+            self.crash_msg = Some(match clipboard::copy(&report.to_text()) {
+                Ok(()) => "Copied the report to the clipboard.".to_string(),
+                Err(e) => e,
+            });
+        }
+    }
+
+    fn copy_solution(&mut self, as_boards: bool) {
+        let text = if as_boards {
+            let boards: Vec<String> = self.solution.path.iter().map(|b| b.to_string()).collect();
+            boards.join("\n\n")
+        } else {
+            notation::to_letters(&self.solution.directions)
+        };
+        self.status_msg = Some(match clipboard::copy(&text) {
+            Ok(()) if as_boards => "Copied all boards to the clipboard.".to_string(),
+            Ok(()) => "Copied the moves to the clipboard.".to_string(),
+            Err(e) => e,
+        });
+    }
+
+    // faster while a tile slides or a replay plays, slower while idle
+    pub fn tick_rate(&self) -> Duration {
+        let playing = self.playback.as_ref().is_some_and(|p| !p.is_paused());
+        let agent_moving = matches!(self.mode, AppMode::Agent) && !self.agent_paused;
+        return match self.slide {
+            Some(_) => SLIDE_FRAME,
+            None if playing => SLIDE_FRAME,
+            None if agent_moving => AGENT_TICK,
+            // as often as the search reports
+            None if matches!(self.mode, AppMode::Searching) => PROGRESS_INTERVAL,
+            None if self.is_busy() => TICK_RATE,
+            None => IDLE_TICK,
+        };
+    }
+
+    /// Whether the screen changes without a key being pressed: something
+    /// moves, a clock runs, or a search or the exact distances are still
+    /// being worked on. Ticks need no new frame otherwise.
+    pub fn is_busy(&self) -> bool {
+        return self.slide.is_some()
+            || self.playback.as_ref().is_some_and(|p| !p.is_paused())
+            || matches!(self.mode, AppMode::Searching)
+            || (matches!(self.mode, AppMode::Agent) && !self.agent_paused)
+            || (matches!(self.mode, AppMode::Play) && self.play.is_clock_running())
+            || self.race.as_ref().is_some_and(|race| race.winner.is_none())
+            || self
+                .versus
+                .as_ref()
+                .is_some_and(|versus| versus.is_running())
+            || self
+                .jobs
+                .iter()
+                .any(|job| job.is_running() || job.is_waiting())
+            || self.rx_oracle.is_some()
+            || self.attract.is_some();
+    }
+
+    fn on_tick(&mut self) {
+        // a panicking search reports here before its channel disconnects
+        if let Some(rx) = &self.rx_crash
+            && let Ok(report) = rx.try_recv()
+        {
+            self.crash = Some(report);
+            self.crash_msg = None;
+        }
+
+        if let Some(versus) = &mut self.versus {
+            versus.poll();
+        }
+
+        if let Some(race) = &mut self.race {
+            race.tick();
+        }
+
+        if let AppMode::Agent = self.mode
+            && !self.agent_paused
+            && let Some(agent) = &mut self.agent
+        {
+            agent.step();
+        }
+
+        if !matches!(self.mode, AppMode::Result | AppMode::Summary) {
+            self.playback = None;
+        }
+        if let Some(step) = self.playback.as_ref().and_then(|p| p.step())
+            && step != self.current_step
+            && step <= self.solution.moves()
+        {
+            self.slide = Some(Slide {
+                from: self.current_step,
+                frame: 0,
+            })
+            .filter(|_| self.animate && step.abs_diff(self.current_step) == 1);
+            self.current_step = step;
+        }
+
+        if let Some(slide) = &mut self.slide {
+            slide.frame += 1;
+            if slide.frame >= SLIDE_FRAMES {
+                self.slide = None;
+            }
+        }
+
+        let mut solved: Vec<(Algorithm, SearchConfig, Solution)> = Vec::new();
+        for job in &mut self.jobs {
+            if job.poll()
+                && let Outcome::Solved(solution) = &job.outcome
+            {
+                tracing::info!(board = %job.board.code(), "background search solved");
+                solved.push((job.algorithm, job.config, solution.clone()));
+            }
+        }
+        for (algorithm, config, solution) in solved {
+            self.record_solve(algorithm, config, &solution);
+        }
+        self.start_queued();
+        self.tick_attract();
+
+        if let Some(rx) = &self.rx_oracle
+            && let Ok(oracle) = rx.try_recv()
+        {
+            tracing::debug!(boards = oracle.len(), "oracle ready");
+            self.oracle = Some(oracle);
+            self.rx_oracle = None;
+        }
+
+        // Update spinner animation
+        if let AppMode::Searching = self.mode {
+            if !self.is_paused() {
+                self.spinner_idx = (self.spinner_idx + 1) % 4;
+            }
+
+            // Only the latest progress report matters
+            if let Some(rx) = &self.rx_progress {
+                while let Ok(progress) = rx.try_recv() {
+                    self.progress = Some(progress);
+                }
+            }
+            self.estimate
+                .update(self.progress.as_ref(), self.is_paused());
+
+            // Check if thread finished
+            if let Some(rx) = &self.rx_result {
+                match rx.try_recv() {
+                    Ok(result) => {
+                        tracing::info!(
+                            solved = matches!(result, SearchOutcome::Solved(_)),
+                            "search result received"
+                        );
+                        let capped = self.memory_capped.load(Ordering::Relaxed);
+                        let algorithm = match capped {
+                            true => Algorithm::Beam,
+                            false => self.algorithm,
+                        };
+                        let cancelled = matches!(result, SearchOutcome::Cancelled);
+                        match result.into_result(algorithm, &self.search_config) {
+                            Ok(solution) => {
+                                self.solution = solution;
+                                self.current_step = 0;
+                                self.status_msg = match capped {
+                                    true => Some(format!(
+                                        "Hit the memory cap of {} MB, solved with beam search instead.",
+                                        self.max_memory_mb
+                                    )),
+                                    false if !self.goal_tiles.is_empty() => Some(format!(
+                                        "Solved for tiles {} only.",
+                                        join_tiles(&self.goal_tiles)
+                                    )),
+                                    false => None,
+                                };
+                                self.mode = match self.skip_summary {
+                                    true => AppMode::Result,
+                                    false => AppMode::Summary,
+                                };
+                                match &mut self.attract {
+                                    Some(attract) => {
+                                        attract.ended = None;
+                                        let times = (1..=self.solution.moves() as u32)
+                                            .map(|i| DEMO_MOVE * i)
+                                            .collect();
+                                        self.playback = Some(Playback::new(times));
+                                    }
+                                    None => self.record_solve(
+                                        algorithm,
+                                        self.search_config,
+                                        &self.solution.clone(),
+                                    ),
+                                }
+                            }
+                            Err(message) => {
+                                self.error_msg = Some(match capped && cancelled {
+                                    true => format!(
+                                        "Stopped at the memory cap of {} MB.",
+                                        self.max_memory_mb
+                                    ),
+                                    false => message,
+                                });
+                                self.mode = AppMode::Input;
+                            }
+                        }
+                        self.rx_result = None;
+                    }
+                    Err(TryRecvError::Empty) => {} // Still working
+                    Err(TryRecvError::Disconnected) => {
+                        tracing::error!("search thread panicked");
+                        self.error_msg = Some("Search thread panicked.".to_string());
+                        self.mode = AppMode::Input;
+                        self.rx_result = None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reacts to a key in the current mode. False when the user quits.
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        tracing::debug!(code = ?key, "key pressed");
+        self.last_key = Instant::now();
+        // any key ends the demo, and does nothing else
+        if self.attract.is_some() {
+            self.stop_attract();
+            return true;
+        }
+        match self.mode {
+            // the crash popup takes every key until it is closed
+            _ if self.crash.is_some() => match key {
+                KeyCode::Char('c') | KeyCode::Char('y') => self.copy_crash_report(),
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => self.crash = None,
+                _ => {}
+            },
+            AppMode::Input => {
+                match key {
+                    KeyCode::Char('q') | KeyCode::Esc => return false,
+                    KeyCode::Tab | KeyCode::BackTab => self.editing_goal = !self.editing_goal,
+                    KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down => {
+                        let (board, cursor) = self.focused_grid();
+                        let (rows, cols) = (board.rows(), board.cols());
+                        match key {
+                            KeyCode::Left if cursor.1 > 0 => cursor.1 -= 1,
+                            KeyCode::Right if cursor.1 < cols - 1 => cursor.1 += 1,
+                            KeyCode::Up if cursor.0 > 0 => cursor.0 -= 1,
+                            KeyCode::Down if cursor.0 < rows - 1 => cursor.0 += 1,
+                            _ => {}
+                        }
+                    }
+                    KeyCode::Char(c) if c.is_ascii_digit() => {
+                        let digit = c.to_digit(10).unwrap() as i64;
+                        let (board, &mut (r, c)) = self.focused_grid();
+                        let max_tile = board.max_tile();
+                        // a second digit makes a two-digit tile on larger boards
+                        let combined = board.get(r, c).map(|n| n * 10 + digit);
+                        if let Some(n) = combined.filter(|n| *n <= max_tile) {
+                            board.set(r, c, Some(n));
+                        } else if (1..=max_tile).contains(&digit) {
+                            board.set(r, c, Some(digit));
+                        } else if digit == 0 {
+                            board.set(r, c, None);
+                        }
+                    }
+                    KeyCode::Char('h') => self.cycle_heuristic(),
+                    KeyCode::Char('w') => self.cycle_weight(),
+                    KeyCode::Char('k') => self.cycle_cost(),
+                    KeyCode::Char('G') => self.cycle_goal(),
+                    KeyCode::Char('o') => self.transform(Symmetry::RotateCw),
+                    KeyCode::Char('f') => self.transform(Symmetry::Mirror),
+                    KeyCode::Char('v') => self.visualize = !self.visualize,
+                    KeyCode::Char('t') => self.theme = self.theme.next(),
+                    KeyCode::Char('p') => self.paste_board(),
+                    KeyCode::Char('x') => self.load_hardest(),
+                    KeyCode::Char('s') => {
+                        self.seed_input.clear();
+                        self.mode = AppMode::Seed;
+                    }
+                    KeyCode::Char('g') => {
+                        self.goal_tiles_input = join_tiles(&self.goal_tiles);
+                        self.mode = AppMode::GoalTiles;
+                    }
+                    KeyCode::Char('H') => self.open_history(),
+                    KeyCode::Char('A') => self.open_analytics(),
+                    KeyCode::Char('L') => self.open_leaderboard(),
+                    KeyCode::Char('l') => self.load_previous_solve(),
+                    KeyCode::Char('T') => self.toggle_tutorial(),
+                    KeyCode::Char('j') => self.open_jobs(),
+                    KeyCode::Char('P') => self.open_presets(),
+                    KeyCode::Char('r') => self.start_race(),
+                    KeyCode::Char('a') => self.start_agent(),
+                    KeyCode::Char('c') => {
+                        if !self.input_board.is_valid() {
+                            self.error_msg = Some(format!(
+                                "Invalid Board: Must contain 1-{} unique & 1 empty.",
+                                self.input_board.max_tile()
+                            ));
+                        } else if self.accept_goal() {
+                            self.versus = Some(Versus::new(
+                                self.input_board,
+                                &self.goal,
+                                self.algorithm,
+                                self.search_config,
+                            ));
+                            self.error_msg = None;
+                            self.mode = AppMode::Versus;
+                        }
+                    }
+                    KeyCode::Char('m') => {
+                        if !self.input_board.is_valid() {
+                            self.error_msg = Some(format!(
+                                "Invalid Board: Must contain 1-{} unique & 1 empty.",
+                                self.input_board.max_tile()
+                            ));
+                        } else if self.accept_goal() {
+                            self.play.restart(self.input_board, &self.goal);
+                            self.error_msg = None;
+                            self.mode = AppMode::Play;
+                        }
+                    }
+                    KeyCode::Backspace | KeyCode::Delete | KeyCode::Char(' ') => {
+                        let (board, &mut (r, c)) = self.focused_grid();
+                        board.set(r, c, None);
+                    }
+                    KeyCode::Enter => {
+                        // Validate and Start Search
+                        if !self.input_board.is_valid() {
+                            self.error_msg = Some(format!(
+                                "Invalid Board: Must contain 1-{} unique & 1 empty.",
+                                self.input_board.max_tile()
+                            ));
+                        } else if self.accept_goal() {
+                            self.solve(self.input_board);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            AppMode::Searching => match key {
+                KeyCode::Char('q') => {
+                    // dropping the progress channel stops the search
+                    self.mode = AppMode::Input;
+                    self.rx_result = None;
+                    self.rx_progress = None;
+                }
+                // a visualized search would crawl along unseen
+                KeyCode::Char('b') if !self.visualize => self.send_to_background(),
+                KeyCode::Char('p') => self.toggle_pause(),
+                _ => {}
+            },
+            AppMode::Summary => match key {
+                KeyCode::Enter | KeyCode::Char('v') => self.mode = AppMode::Result,
+                KeyCode::Char('e') => self.export_solution(),
+                KeyCode::Char('E') => self.export_cast(),
+                KeyCode::Char('c') => self.copy_solution(false),
+                KeyCode::Char('i') => self.show_diagnostics = !self.show_diagnostics,
+                KeyCode::Char('r') => {
+                    self.cycle_algorithm();
+                    self.mode = AppMode::Input;
+                    self.solve(self.solution.path[0]);
+                }
+                KeyCode::Char('d') | KeyCode::Char('q') | KeyCode::Esc => {
+                    self.mode = AppMode::Input;
+                }
+                _ => {}
+            },
+            AppMode::Result => {
+                let before = self.current_step;
+                match key {
+                    KeyCode::Char('s') => self.mode = AppMode::Summary,
+                    KeyCode::Char('q') | KeyCode::Esc => {
+                        self.mode = AppMode::Input; // Return to editor
+                    }
+                    KeyCode::Left | KeyCode::Up if self.current_step > 0 => {
+                        self.current_step -= 1;
+                    }
+                    KeyCode::Right | KeyCode::Down if self.current_step < self.solution.moves() => {
+                        self.current_step += 1;
+                    }
+                    KeyCode::PageUp => {
+                        self.current_step = self.current_step.saturating_sub(STEP_PAGE);
+                    }
+                    KeyCode::PageDown => {
+                        self.current_step =
+                            (self.current_step + STEP_PAGE).min(self.solution.moves());
+                    }
+                    KeyCode::Home => self.current_step = 0,
+                    KeyCode::End => self.current_step = self.solution.moves(),
+                    KeyCode::Char('y') => self.copy_solution(false),
+                    KeyCode::Char('Y') => self.copy_solution(true),
+                    KeyCode::Char('o') => self.show_options = !self.show_options,
+                    KeyCode::Char('i') => self.show_diagnostics = !self.show_diagnostics,
+                    KeyCode::Char('a') => self.count_optimal(),
+                    KeyCode::Char('n') => self.browse_optimal(true),
+                    KeyCode::Char('N') => self.browse_optimal(false),
+                    KeyCode::Char(' ') => {
+                        if let Some(playback) = &mut self.playback {
+                            let ended = self.current_step >= self.solution.moves();
+                            if ended {
+                                self.current_step = 0; // from the start again
+                                playback.resume(0);
+                            } else if playback.is_paused() {
+                                playback.resume(self.current_step);
+                            } else {
+                                playback.pause();
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                // stepping by hand takes over from the playback
+                if self.current_step != before
+                    && key != KeyCode::Char(' ')
+                    && let Some(playback) = &mut self.playback
+                {
+                    playback.pause();
+                }
+                // only single steps slide, jumps snap
+                if self.current_step != before {
+                    self.slide = Some(Slide {
+                        from: before,
+                        frame: 0,
+                    })
+                    .filter(|_| self.animate && self.current_step.abs_diff(before) == 1);
+                }
+            }
+            AppMode::Seed => match key {
+                KeyCode::Char(c) if c.is_ascii_digit() && self.seed_input.len() < 20 => {
+                    self.seed_input.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.seed_input.pop();
+                }
+                KeyCode::Enter => {
+                    match self.seed_input.parse() {
+                        Ok(seed) => self.scramble(seed),
+                        Err(_) => self.error_msg = Some("Invalid seed.".to_string()),
+                    }
+                    self.mode = AppMode::Input;
+                }
+                KeyCode::Esc => self.mode = AppMode::Input,
+                _ => {}
+            },
+            AppMode::GoalTiles => match key {
+                KeyCode::Char(c)
+                    if (c.is_ascii_digit() || c == ' ' || c == ',')
+                        && self.goal_tiles_input.len() < 40 =>
+                {
+                    self.goal_tiles_input.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.goal_tiles_input.pop();
+                }
+                KeyCode::Enter => {
+                    self.set_goal_tiles();
+                    self.mode = AppMode::Input;
+                }
+                KeyCode::Esc => self.mode = AppMode::Input,
+                _ => {}
+            },
+            AppMode::Play => {
+                let solved = self.play.is_solved();
+                match key {
+                    KeyCode::Char('q') | KeyCode::Esc => self.mode = AppMode::Input,
+                    KeyCode::Left => self.play.make_move(Move::Left),
+                    KeyCode::Up => self.play.make_move(Move::Up),
+                    KeyCode::Right => self.play.make_move(Move::Right),
+                    KeyCode::Down => self.play.make_move(Move::Down),
+                    KeyCode::Char('r') => self.play.toggle_recording(),
+                    KeyCode::Char('h') => self.play.hint(self.oracle.as_ref(), self.hint_distance),
+                    KeyCode::Char('f') => self.play.finish(self.oracle.as_ref()),
+                    KeyCode::Char('e') => self.export_replay(),
+                    KeyCode::Char(c @ '1'..='9') => {
+                        self.play.use_slot(c.to_digit(10).unwrap() as usize - 1);
+                    }
+                    _ => {}
+                }
+                if !solved && self.play.is_solved() {
+                    self.play.review(self.oracle.as_ref());
+                    self.record_play();
+                }
+            }
+            AppMode::Leaderboard => match key {
+                KeyCode::Char('q') | KeyCode::Esc => self.mode = AppMode::Input,
+                KeyCode::Up => self.leaderboard_list.select_previous(),
+                KeyCode::Down => self.leaderboard_list.select_next(),
+                KeyCode::Home => self.leaderboard_list.select_first(),
+                KeyCode::End => self.leaderboard_list.select_last(),
+                KeyCode::Enter => self.replay_record(),
+                _ => {}
+            },
+            AppMode::History => match key {
+                KeyCode::Char('q') | KeyCode::Esc => self.mode = AppMode::Input,
+                KeyCode::Up => self.history_list.select_previous(),
+                KeyCode::Down => self.history_list.select_next(),
+                KeyCode::Home => self.history_list.select_first(),
+                KeyCode::End => self.history_list.select_last(),
+                KeyCode::Enter => self.load_history_entry(),
+                _ => {}
+            },
+            AppMode::Analytics => {
+                if let KeyCode::Char('q') | KeyCode::Esc = key {
+                    self.mode = AppMode::Input;
+                }
+            }
+            AppMode::Jobs => match key {
+                KeyCode::Char('q') | KeyCode::Esc => self.mode = AppMode::Input,
+                KeyCode::Up => self.jobs_list.select_previous(),
+                KeyCode::Down => self.jobs_list.select_next(),
+                KeyCode::Home => self.jobs_list.select_first(),
+                KeyCode::End => self.jobs_list.select_last(),
+                KeyCode::Enter => self.load_job(),
+                KeyCode::Char('d') | KeyCode::Delete => self.remove_job(),
+                _ => {}
+            },
+            AppMode::Agent => match key {
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    self.agent = None;
+                    self.mode = AppMode::Input;
+                }
+                KeyCode::Char('p') => self.agent_paused = !self.agent_paused,
+                KeyCode::Char('r') => {
+                    if let Some(agent) = &mut self.agent {
+                        agent.restart();
+                    }
+                }
+                _ => {}
+            },
+            AppMode::Race => {
+                if let Some(race) = &mut self.race {
+                    match key {
+                        KeyCode::Char('q') | KeyCode::Esc => {
+                            self.race = None;
+                            self.mode = AppMode::Input;
+                        }
+                        KeyCode::Left => race.make_move(Move::Left),
+                        KeyCode::Up => race.make_move(Move::Up),
+                        KeyCode::Right => race.make_move(Move::Right),
+                        KeyCode::Down => race.make_move(Move::Down),
+                        KeyCode::Char('r') => {
+                            *race = Race::new(race.solver_path.clone(), &race.goal, self.race_pace);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            AppMode::Presets => match key {
+                KeyCode::Char('q') | KeyCode::Esc => self.mode = AppMode::Input,
+                KeyCode::Up => self.presets_list.select_previous(),
+                KeyCode::Down => self.presets_list.select_next(),
+                KeyCode::Home => self.presets_list.select_first(),
+                KeyCode::End => self.presets_list.select_last(),
+                KeyCode::Enter => self.load_preset(),
+                _ => {}
+            },
+            AppMode::Versus => {
+                if let Some(versus) = &mut self.versus {
+                    let idle = !versus.is_running();
+                    match key {
+                        KeyCode::Char('q') | KeyCode::Esc => {
+                            self.versus = None; // stops the searches
+                            self.mode = AppMode::Input;
+                        }
+                        KeyCode::Tab | KeyCode::Left | KeyCode::Right => {
+                            versus.switch_focus();
+                        }
+                        KeyCode::Char('a') if idle => versus.cycle_algorithm(),
+                        KeyCode::Char('h') if idle => versus.cycle_heuristic(),
+                        KeyCode::Char('w') if idle => versus.cycle_weight(&WEIGHTS),
+                        KeyCode::Enter if idle => versus.start(),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        return true;
+    }
+
+    /// Reacts to an event of the main loop. False when the user quits.
+    pub fn handle_event(&mut self, event: AppEvent) -> bool {
+        let running = match event {
+            AppEvent::Key(key) => self.handle_key(key.code),
+            AppEvent::Resize(..) | AppEvent::Error(_) => true,
+            AppEvent::Tick => {
+                self.on_tick();
+                true
+            }
+        };
+        self.advance_tutorial();
+        return running;
+    }
+
+    /// Starts the guided tour, or ends it if it is already being taken.
+    pub fn toggle_tutorial(&mut self) {
+        self.tutorial = match self.tutorial {
+            Some(_) => None,
+            None => Some(Tutorial::new(self.goal.goal())),
+        };
+    }
+
+    // Moves the tour on to the next lesson once the app is where the current
+    // one asks, and ends it after the last.
+    fn advance_tutorial(&mut self) {
+        while let Some(tutorial) = &self.tutorial {
+            let done = match tutorial.lesson {
+                Lesson::TypeBoard => self.input_board == tutorial.board,
+                Lesson::Solve => {
+                    matches!(self.mode, AppMode::Summary | AppMode::Result)
+                        && self.solution.path.first() == Some(&tutorial.board)
+                }
+                Lesson::Summary => matches!(self.mode, AppMode::Result),
+                Lesson::StepThrough => {
+                    matches!(self.mode, AppMode::Result)
+                        && self.current_step == self.solution.moves()
+                }
+                Lesson::Return => matches!(self.mode, AppMode::Input),
+            };
+            if !done {
+                return;
+            }
+            let next = tutorial.lesson.next();
+            tracing::debug!(?next, "tutorial lesson done");
+            match (next, &mut self.tutorial) {
+                (Some(lesson), Some(tutorial)) => tutorial.lesson = lesson,
+                _ => self.tutorial = None,
+            }
+        }
+    }
+}
+
+/// Tiles as "1 2 3".
+pub fn join_tiles(tiles: &[i64]) -> String {
+    return tiles
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        return App::new(&Config {
+            ascii: Some(true),
+            ..Config::default()
+        });
+    }
+
+    // Types a board into the editor the way a user would, cell by cell.
+    fn type_board(app: &mut App, code: &str) {
+        let cols = app.input_board.cols();
+        for (i, digit) in code.chars().enumerate() {
+            assert!(app.handle_key(KeyCode::Char(digit)));
+            if (i + 1) % cols != 0 {
+                app.handle_key(KeyCode::Right);
+            } else {
+                app.handle_key(KeyCode::Down);
+                for _ in 1..cols {
+                    app.handle_key(KeyCode::Left);
+                }
+            }
+        }
+    }
+
+    // Ticks until the search started by the last key is over.
+    fn finish_search(app: &mut App) {
+        let started = Instant::now();
+        while matches!(app.mode, AppMode::Searching) {
+            assert!(
+                started.elapsed() < Duration::from_secs(30),
+                "search too slow"
+            );
+            thread::sleep(Duration::from_millis(5));
+            app.handle_event(AppEvent::Tick);
+        }
+    }
+
+    #[test]
+    fn input_solve_result_flow() {
+        let mut app = app();
+        type_board(&mut app, "120453786");
+        assert_eq!(app.input_board.code(), "120453786");
+
+        app.handle_key(KeyCode::Enter);
+        assert!(matches!(app.mode, AppMode::Searching));
+        finish_search(&mut app);
+        assert!(matches!(app.mode, AppMode::Summary));
+        assert_eq!(app.solution.moves(), 2);
+
+        app.handle_key(KeyCode::Enter);
+        assert!(matches!(app.mode, AppMode::Result));
+        app.handle_key(KeyCode::End);
+        assert_eq!(app.solution.path[app.current_step], *app.goal.goal());
+        app.handle_key(KeyCode::Left);
+        assert_eq!(app.current_step, 1);
+
+        app.handle_key(KeyCode::Char('q'));
+        assert!(matches!(app.mode, AppMode::Input));
+        assert!(!app.handle_key(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn boards_solved_on_launch_open_in_the_step_viewer() {
+        let mut app = app();
+        app.solve_on_launch("120453786".parse().unwrap());
+        assert!(matches!(app.mode, AppMode::Searching));
+        finish_search(&mut app);
+        assert!(matches!(app.mode, AppMode::Result));
+        assert_eq!(app.input_board.code(), "120453786");
+        assert_eq!(app.solution.moves(), 2);
+
+        // searches started from the TUI show the summary as usual
+        app.handle_key(KeyCode::Char('q'));
+        app.handle_key(KeyCode::Enter);
+        finish_search(&mut app);
+        assert!(matches!(app.mode, AppMode::Summary));
+    }
+
+    #[test]
+    fn boards_solved_before_are_loaded_from_the_history() {
+        let mut app = app();
+        type_board(&mut app, "120453786");
+        assert!(app.known_board().is_none());
+        app.handle_key(KeyCode::Enter);
+        finish_search(&mut app);
+        app.handle_key(KeyCode::Char('q'));
+        assert!(matches!(app.mode, AppMode::Input));
+
+        let note = app.known_board().unwrap();
+        assert!(note.starts_with("Previously solved in 2 moves"), "{}", note);
+        app.handle_key(KeyCode::Char('l'));
+        assert!(matches!(app.mode, AppMode::Result));
+        assert_eq!(app.solution.moves(), 2);
+        assert_eq!(app.solution.path[0].code(), "120453786");
+    }
+
+    #[test]
+    fn the_demo_plays_until_a_key_is_pressed() {
+        let mut app = app();
+        type_board(&mut app, "120453786");
+        app.attract_after = Some(Duration::ZERO);
+        app.handle_event(AppEvent::Tick);
+        assert!(app.attract.is_some());
+        finish_search(&mut app);
+        assert!(matches!(app.mode, AppMode::Result));
+        assert!(app.playback.is_some());
+        // demo solves are not the user's
+        assert!(app.history.entries.is_empty());
+
+        assert!(app.handle_key(KeyCode::Char('q')));
+        assert!(matches!(app.mode, AppMode::Input));
+        assert!(app.attract.is_none());
+        assert_eq!(app.input_board.code(), "120453786");
+    }
+
+    #[test]
+    fn the_tour_follows_a_first_solve() {
+        let mut app = app();
+        let lesson = |app: &App| app.tutorial.as_ref().map(|t| t.lesson);
+        app.toggle_tutorial();
+        let board = app.tutorial.as_ref().unwrap().board.code();
+        type_board(&mut app, &board);
+        app.handle_event(AppEvent::Key(KeyCode::Enter.into()));
+        assert_eq!(lesson(&app), Some(Lesson::Solve));
+        finish_search(&mut app);
+        assert_eq!(lesson(&app), Some(Lesson::Summary));
+        app.handle_event(AppEvent::Key(KeyCode::Enter.into()));
+        assert_eq!(lesson(&app), Some(Lesson::StepThrough));
+        app.handle_event(AppEvent::Key(KeyCode::End.into()));
+        assert_eq!(lesson(&app), Some(Lesson::Return));
+        app.handle_event(AppEvent::Key(KeyCode::Char('q').into()));
+        assert!(app.tutorial.is_none());
+    }
+
+    #[test]
+    fn invalid_boards_are_not_searched() {
+        let mut app = app();
+        type_board(&mut app, "112345678");
+        app.handle_key(KeyCode::Enter);
+        assert!(matches!(app.mode, AppMode::Input));
+        assert!(app.error_msg.is_some());
+    }
+
+    #[test]
+    fn goals_typed_next_to_the_start_are_searched_for() {
+        let mut app = app();
+        type_board(&mut app, "123456780");
+        app.handle_key(KeyCode::Tab);
+        type_board(&mut app, "123456778");
+        app.handle_key(KeyCode::Enter);
+        assert!(matches!(app.mode, AppMode::Input));
+        assert!(app.error_msg.take().unwrap().starts_with("Invalid Goal"));
+
+        app.handle_key(KeyCode::Right);
+        app.handle_key(KeyCode::Char('0'));
+        assert_eq!(app.goal_input.code(), "123456708");
+        assert_eq!(app.input_board.code(), "123456780");
+        app.handle_key(KeyCode::Enter);
+        finish_search(&mut app);
+        assert!(matches!(app.mode, AppMode::Summary));
+        assert_eq!(app.solution.moves(), 1);
+        assert_eq!(*app.goal.goal(), app.goal_input);
+    }
+
+    #[test]
+    fn custom_and_partial_goals_survive_a_restart() {
+        let mut app = app();
+        type_board(&mut app, "123456780");
+        app.handle_key(KeyCode::Tab);
+        type_board(&mut app, "123456708");
+        app.handle_key(KeyCode::Enter);
+        finish_search(&mut app);
+        app.goal_tiles = vec![1, 2, 3];
+
+        let text = toml::to_string(&app.snapshot()).unwrap();
+        let mut restored = self::app();
+        restored.restore(toml::from_str(&text).unwrap());
+        assert_eq!(restored.goal.goal().code(), "123456708");
+        assert_eq!(restored.goal_input, *restored.goal.goal());
+        assert_eq!(restored.goal_tiles, vec![1, 2, 3]);
+        assert_eq!(restored.goal_tiles_input, "1 2 3");
+        assert_eq!(restored.solution.path.last(), Some(restored.goal.goal()));
+    }
+
+    #[test]
+    fn every_action_heads_for_the_goal_in_the_grid() {
+        let mut app = app();
+        type_board(&mut app, "123456780");
+        app.handle_key(KeyCode::Tab);
+        type_board(&mut app, "123456708");
+        assert_eq!(
+            app.difficulty().unwrap(),
+            "Estimated solution: at least 1 moves"
+        );
+        app.handle_key(KeyCode::Tab);
+        app.handle_key(KeyCode::Char('o'));
+        // the edit is turned along with the start board, still uncommitted
+        assert_eq!(app.goal_input.code(), "741052863");
+        assert_ne!(*app.goal.goal(), app.goal_input);
+
+        app.handle_key(KeyCode::Tab);
+        app.goal_cursor = (0, 0);
+        type_board(&mut app, "123456780");
+        app.handle_key(KeyCode::Tab);
+        app.handle_key(KeyCode::Char('m'));
+        assert!(matches!(app.mode, AppMode::Play));
+        assert_eq!(app.goal.goal().code(), "123456780");
+    }
+
+    #[test]
+    fn optimal_solutions_are_not_counted_towards_partial_goals() {
+        let mut app = app();
+        app.goal_tiles = vec![1, 2, 3];
+        type_board(&mut app, "123456078");
+        app.handle_key(KeyCode::Enter);
+        finish_search(&mut app);
+        app.handle_key(KeyCode::Enter);
+        assert!(matches!(app.mode, AppMode::Result));
+        app.handle_key(KeyCode::Char('a'));
+        assert!(app.status_msg.unwrap().contains("partial goals"));
+        assert!(app.optimal.is_none());
+    }
+
+    #[test]
+    fn unsolvable_boards_report_why() {
+        let mut app = app();
+        type_board(&mut app, "213456780");
+        app.handle_key(KeyCode::Enter);
+        finish_search(&mut app);
+        assert!(matches!(app.mode, AppMode::Input));
+        assert!(app.error_msg.unwrap().starts_with("Unsolvable"));
+    }
+
+    #[test]
+    fn aborted_searches_leave_no_result() {
+        let mut app = app();
+        app.algorithm = Algorithm::Iddfs;
+        type_board(&mut app, "867254301");
+        app.handle_key(KeyCode::Enter);
+        app.handle_key(KeyCode::Char('q'));
+        assert!(matches!(app.mode, AppMode::Input));
+        app.handle_event(AppEvent::Tick);
+        assert!(matches!(app.mode, AppMode::Input));
+        assert!(app.error_msg.is_none());
+    }
+
+    #[test]
+    fn queued_solves_wait_for_the_background_searches() {
+        let mut app = app();
+        app.queue_searches = true;
+        app.algorithm = Algorithm::Iddfs;
+        type_board(&mut app, "867254301");
+        app.handle_key(KeyCode::Enter);
+        app.handle_key(KeyCode::Char('b'));
+        assert!(matches!(app.mode, AppMode::Input));
+
+        app.algorithm = Algorithm::AStar;
+        app.cursor_pos = (0, 0);
+        type_board(&mut app, "120453786");
+        app.handle_key(KeyCode::Enter);
+        assert!(matches!(app.mode, AppMode::Input));
+        app.handle_event(AppEvent::Tick);
+        assert!(app.jobs[1].is_waiting());
+
+        // stopping the slow search lets the queued one run
+        app.handle_key(KeyCode::Char('j'));
+        app.handle_key(KeyCode::Char('d'));
+        let started = Instant::now();
+        while !matches!(app.jobs[0].outcome, Outcome::Solved(_)) {
+            assert!(
+                started.elapsed() < Duration::from_secs(10),
+                "search too slow"
+            );
+            thread::sleep(Duration::from_millis(5));
+            app.handle_event(AppEvent::Tick);
+        }
+        assert_eq!(app.jobs[0].board.code(), "120453786");
+    }
+
+    #[test]
+    fn solving_again_from_the_summary_takes_the_next_algorithm() {
+        let mut app = app();
+        type_board(&mut app, "123456708");
+        app.handle_key(KeyCode::Enter);
+        finish_search(&mut app);
+        assert!(matches!(app.mode, AppMode::Summary));
+        app.handle_key(KeyCode::Char('r'));
+        assert_eq!(app.algorithm, Algorithm::ALL[1]);
+        finish_search(&mut app);
+        assert!(matches!(app.mode, AppMode::Summary));
+        assert_eq!(app.solution.moves(), 1);
+
+        // behind a background search, it waits its turn
+        app.queue_searches = true;
+        app.jobs.push(Job::queued(
+            app.input_board,
+            app.search_goal(),
+            app.algorithm,
+            app.search_config,
+        ));
+        app.handle_key(KeyCode::Char('r'));
+        assert!(matches!(app.mode, AppMode::Input));
+        assert_eq!(app.jobs.len(), 2);
+        assert_eq!(app.jobs[1].algorithm, Algorithm::ALL[2]);
+    }
+}
@@ -0,0 +1,57 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use ratatui::{buffer::Buffer, style::Color, symbols::border};
+use std::env;
+
+/// Tile borders drawn with plain ASCII.
+pub const BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// Border of the tile under the cursor, which cannot stand out by color.
+pub const CURSOR_BORDER: border::Set = border::Set {
+    top_left: "#",
+    top_right: "#",
+    bottom_left: "#",
+    bottom_right: "#",
+    vertical_left: "#",
+    vertical_right: "#",
+    horizontal_top: "=",
+    horizontal_bottom: "=",
+};
+
+/// Whether the terminal is known to show no colors: `NO_COLOR` is set, or
+/// `TERM` is `dumb`.
+pub fn terminal_lacks_color() -> bool {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+    return no_color || dumb;
+}
+
+/// Drops the colors of everything drawn and replaces the line and block
+/// characters with ASCII, keeping bold, reversed and the other modifiers.
+pub fn to_ascii(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        cell.set_fg(Color::Reset).set_bg(Color::Reset);
+        if cell.symbol().is_ascii() {
+            continue;
+        }
+        let replacement = match cell.symbol().chars().next() {
+            Some('─' | '━' | '═' | '╌' | '┄') => "-",
+            Some('│' | '┃' | '║' | '╎' | '┆') => "|",
+            Some('\u{2500}'..='\u{257F}') => "+", // corners and junctions
+            Some('█' | '▉' | '▊' | '▋' | '▌' | '▓' | '▒') => "#",
+            Some('\u{2580}'..='\u{259F}') => " ", // less than half a block
+            _ => "?",
+        };
+        cell.set_symbol(replacement);
+    }
+}
@@ -1,124 +1,457 @@
 #![deny(unused_variables)]
 #![deny(unused_imports)]
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+use crate::{goal::GoalContext, notation::Move, parity, rng, validate};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::{BuildHasherDefault, Hash, Hasher},
+    str::FromStr,
+};
+
+/// How many random moves a scramble makes unless told otherwise.
+pub const DEFAULT_SCRAMBLE_MOVES: usize = 40;
+
+/// Largest number of rows or columns a board can have.
+pub const MAX_SIDE: usize = 4;
+
+/// Smallest number of rows or columns: a single row or column leaves the
+/// blank nowhere to go but back and forth.
+pub const MIN_SIDE: usize = 2;
+
+/// Most cells a board can have.
+pub const MAX_CELLS: usize = MAX_SIDE * MAX_SIDE;
+
+// `Board::blank` of a board without an empty cell
+const NO_BLANK: u8 = u8::MAX;
+
+// ZOBRIST[cell][tile] is a random number for `tile` being in `cell`; the hash
+// of a board is all of those for its tiles xor-ed together, so a move only
+// swaps two of them out and in again. The blank counts as 0.
+const ZOBRIST: [[u64; MAX_CELLS]; MAX_CELLS] = {
+    let mut table = [[0; MAX_CELLS]; MAX_CELLS];
+    let mut cell = 0;
+    while cell < MAX_CELLS {
+        let mut tile = 1;
+        while tile < MAX_CELLS {
+            table[cell][tile] = splitmix64((cell * MAX_CELLS + tile) as u64);
+            tile += 1;
+        }
+        cell += 1;
+    }
+    table
+};
+
+// A well-mixed 64-bit number for every input, fixed at compile time.
+const fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    return z ^ (z >> 31);
+}
+
+const fn zobrist_of(cell: usize, tile: u8) -> u64 {
+    // out-of-range tiles only ever come with invalid boards
+    return ZOBRIST[cell][tile as usize % MAX_CELLS];
+}
+
+const fn zobrist_hash(tiles: &[u8; MAX_CELLS]) -> u64 {
+    let mut hash = 0;
+    let mut cell = 0;
+    while cell < MAX_CELLS {
+        hash ^= zobrist_of(cell, tiles[cell]);
+        cell += 1;
+    }
+    return hash;
+}
+
+/// A sliding puzzle of `rows` x `cols` cells, stored as one byte per cell in
+/// row-major order with 0 for the blank. Cells past `rows * cols` stay 0.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "SavedBoard")]
 pub struct Board {
-    pub b: [[Option<i64>; 3]; 3],
+    rows: u8,
+    cols: u8,
+    tiles: [u8; MAX_CELLS],
+    #[serde(skip)]
+    blank: u8, // index of the empty cell, kept up to date by every change
+    #[serde(skip)]
+    zobrist: u64, // Zobrist hash of the tiles, kept up to date by every change
+}
+
+// A board as read back from a file, checked before it becomes a `Board`.
+#[derive(Deserialize)]
+struct SavedBoard {
+    rows: u8,
+    cols: u8,
+    tiles: [u8; MAX_CELLS],
+}
+
+impl TryFrom<SavedBoard> for Board {
+    type Error = String;
+
+    fn try_from(saved: SavedBoard) -> Result<Self, Self::Error> {
+        let side = MIN_SIDE as u8..=MAX_SIDE as u8;
+        if !side.contains(&saved.rows) || !side.contains(&saved.cols) {
+            return Err(format!(
+                "unsupported board size {}x{}",
+                saved.rows, saved.cols
+            ));
+        }
+        let mut board = Board::empty(saved.rows as usize, saved.cols as usize);
+        // cells past the shape stay empty, whatever the file says
+        let cells = (saved.rows * saved.cols) as usize;
+        board.tiles[..cells].copy_from_slice(&saved.tiles[..cells]);
+        board.zobrist = zobrist_hash(&board.tiles);
+        board.locate_blank();
+        // a damaged file may hold any bytes, which would index past the goal
+        validate::check_tiles(&board).map_err(|rejection| rejection.to_string())?;
+        return Ok(board);
+    }
+}
+
+// the blank index is derived from the tiles, so it is left out of comparisons
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        return self.rows == other.rows && self.cols == other.cols && self.tiles == other.tiles;
+    }
+}
+
+impl Eq for Board {}
+
+// equal boards have the same tiles and so the same Zobrist hash, which is
+// all a hasher gets to see
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.zobrist);
+    }
+}
+
+/// Hasher for boards that takes their Zobrist hash as it is instead of
+/// hashing it again.
+#[derive(Default)]
+pub struct ZobristHasher(u64);
+
+impl Hasher for ZobristHasher {
+    fn finish(&self) -> u64 {
+        return self.0;
+    }
+
+    fn write_u64(&mut self, hash: u64) {
+        self.0 ^= hash;
+    }
+
+    // anything but a board, FNV-1a
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x0100_0000_01B3);
+        }
+    }
+}
+
+/// Sets and maps of boards hashed with [`ZobristHasher`].
+pub type BoardSet = HashSet<Board, BuildHasherDefault<ZobristHasher>>;
+pub type BoardMap<V> = HashMap<Board, V, BuildHasherDefault<ZobristHasher>>;
+
+/// A board one move away from another one.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Neighbor {
+    pub mv: Move, // how the blank moved to get here
+    pub board: Board,
+}
+
+/// The standard 3x3 goal.
+pub const STANDARD_GOAL: Board = Board {
+    rows: 3,
+    cols: 3,
+    tiles: [1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 0, 0, 0],
+    blank: 8,
+    zobrist: zobrist_hash(&[1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 0, 0, 0]),
+};
+
+impl Default for Board {
+    fn default() -> Self {
+        return Board::empty(3, 3);
+    }
 }
 
 impl Board {
+    pub fn empty(rows: usize, cols: usize) -> Board {
+        assert!(
+            (MIN_SIDE..=MAX_SIDE).contains(&rows) && (MIN_SIDE..=MAX_SIDE).contains(&cols),
+            "unsupported board size {}x{}",
+            rows,
+            cols
+        );
+        return Board {
+            rows: rows as u8,
+            cols: cols as u8,
+            tiles: [0; MAX_CELLS],
+            blank: 0,
+            zobrist: 0,
+        };
+    }
+
+    /// The standard goal for the shape: tiles in order, blank in the
+    /// bottom-right corner.
+    pub fn goal(rows: usize, cols: usize) -> Board {
+        return Board::from_cells(rows, cols, &parity::standard_goal(rows, cols));
+    }
+
+    /// Builds a board from its cells in row-major order.
+    pub fn from_cells(rows: usize, cols: usize, cells: &[Option<i64>]) -> Board {
+        let mut board = Board::empty(rows, cols);
+        for (i, cell) in cells.iter().enumerate().take(rows * cols) {
+            board.set(i / cols, i % cols, *cell);
+        }
+        return board;
+    }
+
+    pub fn rows(&self) -> usize {
+        return self.rows as usize;
+    }
+
+    pub fn cols(&self) -> usize {
+        return self.cols as usize;
+    }
+
+    /// The tile at (`row`, `col`), `None` for the blank.
+    pub fn get(&self, row: usize, col: usize) -> Option<i64> {
+        return match self.tiles[row * self.cols() + col] {
+            0 => None,
+            n => Some(n as i64),
+        };
+    }
+
+    /// Puts `tile` at (`row`, `col`), `None` for the blank.
+    pub fn set(&mut self, row: usize, col: usize, tile: Option<i64>) {
+        let cell = row * self.cols() + col;
+        let tile = tile.unwrap_or(0) as u8;
+        self.zobrist ^= zobrist_of(cell, self.tiles[cell]) ^ zobrist_of(cell, tile);
+        self.tiles[cell] = tile;
+        self.locate_blank();
+    }
+
+    // the first empty cell, for boards that are still being edited
+    fn locate_blank(&mut self) {
+        let cells = self.rows() * self.cols();
+        self.blank = match self.tiles[..cells].iter().position(|t| *t == 0) {
+            Some(index) => index as u8,
+            None => NO_BLANK,
+        };
+    }
+
+    /// The cells in row-major order, `None` for the empty cell.
+    pub fn cells(&self) -> Vec<Option<i64>> {
+        return self.tiles[..self.rows() * self.cols()]
+            .iter()
+            .map(|t| match t {
+                0 => None,
+                n => Some(*n as i64),
+            })
+            .collect();
+    }
+
+    /// Highest tile number on a board of this shape.
+    pub fn max_tile(&self) -> i64 {
+        return (self.rows() * self.cols()) as i64 - 1;
+    }
+
     pub fn is_valid(&self) -> bool {
-        let mut seen = [false; 9]; // Index 0 for None, 1-8 for Numbers
-        let mut count = 0;
-
-        for r in 0..3 {
-            for c in 0..3 {
-                match self.b[r][c] {
-                    Some(n) => {
-                        if !(1..=8).contains(&n) {
-                            // number outside 1-8
-                            return false;
-                        }
-
-                        if seen[n as usize] {
-                            // the number appears twice in the board
-                            return false;
-                        }
-
-                        seen[n as usize] = true;
+        let mut seen = vec![false; self.rows() * self.cols()]; // Index 0 for None, the rest for Numbers
+
+        for cell in self.cells() {
+            match cell {
+                Some(n) => {
+                    if !(1..=self.max_tile()).contains(&n) {
+                        // number outside the tile range
+                        return false;
                     }
-                    None => {
-                        if seen[0] {
-                            // the board has 2 (or more) empty cells
-                            return false;
-                        }
 
-                        seen[0] = true;
+                    if seen[n as usize] {
+                        // the number appears twice in the board
+                        return false;
                     }
+
+                    seen[n as usize] = true;
+                }
+                None => {
+                    if seen[0] {
+                        // the board has 2 (or more) empty cells
+                        return false;
+                    }
+
+                    seen[0] = true;
                 }
-                count += 1;
             }
         }
 
-        return count == 9 && seen[0];
+        return seen[0];
     }
 
-    fn find_number(&self, number: i64) -> Option<(usize, usize)> {
-        for r in 0..3 {
-            for c in 0..3 {
-                if let Some(n) = self.b[r][c]
-                    && n == number
-                {
-                    return Some((r, c));
-                }
-            }
+    pub fn find_empty_cell(&self) -> Option<(usize, usize)> {
+        if self.blank == NO_BLANK {
+            return None;
         }
+        let index = self.blank as usize;
+        return Some((index / self.cols(), index % self.cols()));
+    }
 
-        // guaranteed to find the number
-        return None;
+    /// Manhattan distance between the tiles of the two boards.
+    pub fn distance(&self, other_board: &Board) -> i64 {
+        // one lookup table for the other board instead of a scan per tile
+        return GoalContext::new(*other_board).manhattan(self);
     }
 
-    fn find_empty_cell(&self) -> Option<(usize, usize)> {
-        for r in 0..3 {
-            for c in 0..3 {
-                if self.b[r][c].is_none() {
-                    return Some((r, c));
-                }
+    /// The tiles packed 4 bits per cell into one number, row-major from the
+    /// lowest bits. Unique for every board of a shape; benchmarked against
+    /// the Zobrist hash as a key for sets of boards.
+    pub fn packed(&self) -> u64 {
+        return self
+            .tiles
+            .iter()
+            .enumerate()
+            .fold(0, |packed, (cell, tile)| {
+                packed | (*tile as u64 & 0xF) << (4 * cell)
+            });
+    }
+
+    /// Compact form of the board: the cells row by row, 0 for the empty cell
+    /// (e.g. "724506831"). Boards with two-digit tiles separate the cells with
+    /// commas. This is also what the board parser accepts.
+    pub fn code(&self) -> String {
+        let cells: Vec<String> = self
+            .cells()
+            .iter()
+            .map(|cell| cell.unwrap_or(0).to_string())
+            .collect();
+        let separator = if self.max_tile() > 9 { "," } else { "" };
+        return cells.join(separator);
+    }
+
+    /// The board turned a quarter clockwise; a 2x3 board becomes 3x2.
+    pub fn rotate_cw(&self) -> Board {
+        let mut rotated = Board::empty(self.cols(), self.rows());
+        for r in 0..self.rows() {
+            for c in 0..self.cols() {
+                rotated.set(c, self.rows() - 1 - r, self.get(r, c));
             }
         }
-
-        // guaranteed to find empty cell
-        return None;
+        return rotated;
     }
 
-    pub fn distance(&self, other_board: &Board) -> i64 {
-        let mut total_distance: i64 = 0;
-        for number in 1..=8 {
-            let self_location = self.find_number(number);
-            let other_board_location = other_board.find_number(number);
-
-            // use Manhattan distance
-            let distance: i64 = (self_location.unwrap().0 as i64
-                - other_board_location.unwrap().0 as i64)
-                .abs()
-                + (self_location.unwrap().1 as i64 - other_board_location.unwrap().1 as i64).abs();
-            total_distance += distance;
+    /// The board mirrored left to right.
+    pub fn mirror(&self) -> Board {
+        let mut mirrored = Board::empty(self.rows(), self.cols());
+        for r in 0..self.rows() {
+            for c in 0..self.cols() {
+                mirrored.set(r, self.cols() - 1 - c, self.get(r, c));
+            }
         }
-        return total_distance;
+        return mirrored;
     }
 
     pub fn copy_and_swap(&self, src_pos: (usize, usize), dest_pos: (usize, usize)) -> Board {
-        let mut copied_b = self.b;
-
-        let temp: Option<i64> = copied_b[src_pos.0][src_pos.1];
-        copied_b[src_pos.0][src_pos.1] = copied_b[dest_pos.0][dest_pos.1];
-        copied_b[dest_pos.0][dest_pos.1] = temp;
+        let mut copied = *self;
+        let src = src_pos.0 * self.cols() + src_pos.1;
+        let dest = dest_pos.0 * self.cols() + dest_pos.1;
+        copied.tiles.swap(src, dest);
+        copied.zobrist ^= zobrist_of(src, self.tiles[src])
+            ^ zobrist_of(dest, self.tiles[dest])
+            ^ zobrist_of(src, self.tiles[dest])
+            ^ zobrist_of(dest, self.tiles[src]);
+        // a move swaps the blank with a tile, anything else needs a rescan
+        let blank = self.blank as usize;
+        if src == blank && copied.tiles[src] != 0 {
+            copied.blank = dest as u8;
+        } else if dest == blank && copied.tiles[dest] != 0 {
+            copied.blank = src as u8;
+        } else {
+            copied.locate_blank();
+        }
+        return copied;
+    }
 
-        return Board { b: copied_b };
+    /// Every board one move away, each with the move that leads to it.
+    pub fn get_possible_next_states(&self) -> Vec<Neighbor> {
+        let Some(blank) = self.find_empty_cell() else {
+            return Vec::new();
+        };
+        // this order decides ties in the search and the seeded scrambles
+        return [Move::Up, Move::Left, Move::Right, Move::Down]
+            .into_iter()
+            .filter_map(|mv| {
+                let target = mv.target(blank, self.rows(), self.cols())?;
+                Some(Neighbor {
+                    mv,
+                    board: self.copy_and_swap(blank, target),
+                })
+            })
+            .collect();
     }
+}
 
-    pub fn get_possible_next_states(&self) -> Vec<Board> {
-        let mut list: Vec<Board> = Vec::new();
+/// Walks `n_moves` random moves away from the standard 3x3 goal, never
+/// undoing the previous move. The same seed always gives the same board, on
+/// every platform, so a seed is enough to share a puzzle.
+pub fn scramble(seed: u64, n_moves: usize) -> Board {
+    return scramble_from(&STANDARD_GOAL, seed, n_moves);
+}
 
-        let empty_cell_location = self.find_empty_cell().unwrap();
-        let neighbors: [(i8, i8); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
-        for neighbor in neighbors {
-            let new_r: i8 = empty_cell_location.0 as i8 + neighbor.0;
-            let new_c: i8 = empty_cell_location.1 as i8 + neighbor.1;
+/// Same as [`scramble`], starting from `goal` (and so keeping its shape).
+pub fn scramble_from(goal: &Board, seed: u64, n_moves: usize) -> Board {
+    return scramble_with(goal, &mut rng::seeded(seed), n_moves);
+}
 
-            // if outside, skip
-            if new_r < 0 || new_r >= self.b.len().try_into().unwrap() {
-                continue;
-            }
-            if new_c < 0 || new_c >= self.b.len().try_into().unwrap() {
-                continue;
-            }
+/// Same as [`scramble_from`], drawing the moves from `rng`.
+pub fn scramble_with(goal: &Board, rng: &mut impl Rng, n_moves: usize) -> Board {
+    let mut previous: Option<Board> = None;
+    let mut board = *goal;
+    for _ in 0..n_moves {
+        let next: Vec<Board> = board
+            .get_possible_next_states()
+            .into_iter()
+            .map(|n| n.board)
+            .filter(|b| Some(*b) != previous)
+            .collect();
+        // a board without a blank has no moves at all
+        if next.is_empty() {
+            break;
+        }
+        previous = Some(board);
+        board = next[(rng.next_u64() % next.len() as u64) as usize];
+    }
+    return board;
+}
 
-            // generate new board by swapping the content
-            let new_location = (new_r as usize, new_c as usize);
-            list.push(self.copy_and_swap(empty_cell_location, new_location));
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // two-digit tiles get every column padded to the same width
+        let width = self.max_tile().to_string().len();
+        for r in 0..self.rows() {
+            let cells: Vec<String> = (0..self.cols())
+                .map(|c| match self.get(r, c) {
+                    Some(n) => format!("{:>width$}", n),
+                    None => format!("{:>width$}", "_"),
+                })
+                .collect();
+            write!(f, "{}", cells.join(" "))?;
+            if r < self.rows() - 1 {
+                writeln!(f)?;
+            }
         }
+        return Ok(());
+    }
+}
+
+// See `validate::parse` for the accepted format; always a 3x3 board.
+impl FromStr for Board {
+    type Err = validate::Rejection;
 
-        return list;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return validate::parse(s);
     }
 }
@@ -0,0 +1,179 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{self, SearchConfig, SearchStats, Solution},
+    board::Board,
+    cost::CostModel,
+    goal::GoalContext,
+    notation,
+    oracle::Oracle,
+    symmetry::{self, GoalSymmetry},
+};
+
+use std::{collections::HashMap, sync::OnceLock, time::Instant};
+
+mod table;
+
+/// How far from the goal the book reaches.
+pub const BOOK_DEPTH: u8 = 10;
+
+/// Optimal moves for every board close to the goal, so endgames and hints
+/// don't need a full search. Boards are keyed on their canonical form under
+/// the goal's symmetries, so mirrored twins share one entry.
+pub struct OpeningBook {
+    goal: Board,
+    symmetries: Vec<GoalSymmetry>,
+    distances: HashMap<u64, u8>, // by packed canonical board
+}
+
+impl OpeningBook {
+    pub fn build(goal: &GoalContext, depth: u8) -> OpeningBook {
+        let symmetries = symmetry::goal_symmetries(goal);
+        let distances = Oracle::build_within(goal, depth)
+            .distances()
+            .map(|(board, distance)| (symmetry::canonical(board, &symmetries).packed(), distance))
+            .collect();
+        return OpeningBook {
+            goal: *goal.goal(),
+            symmetries,
+            distances,
+        };
+    }
+
+    /// The book for the standard goal, read from the table shipped in
+    /// `book/table.rs` rather than searched for at startup.
+    pub fn standard() -> &'static OpeningBook {
+        static BOOK: OnceLock<OpeningBook> = OnceLock::new();
+        return BOOK.get_or_init(|| {
+            let goal = GoalContext::standard();
+            return OpeningBook {
+                goal: *goal.goal(),
+                symmetries: symmetry::goal_symmetries(&goal),
+                distances: table::DISTANCES.iter().copied().collect(),
+            };
+        });
+    }
+
+    pub fn goal(&self) -> &Board {
+        return &self.goal;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.distances.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.distances.is_empty();
+    }
+
+    pub fn distance(&self, board: &Board) -> Option<u8> {
+        if board.rows() != self.goal.rows() || board.cols() != self.goal.cols() {
+            return None;
+        }
+        let canonical = symmetry::canonical(board, &self.symmetries);
+        return self.distances.get(&canonical.packed()).copied();
+    }
+
+    /// The board after the optimal first move, or `None` if the board is not in
+    /// the book (or already solved).
+    pub fn first_move(&self, board: &Board) -> Option<Board> {
+        let distance = self.distance(board)?;
+        if distance == 0 {
+            return None;
+        }
+        return board
+            .get_possible_next_states()
+            .into_iter()
+            .map(|n| n.board)
+            .find(|next| self.distance(next) == Some(distance - 1));
+    }
+
+    /// The complete optimal path to the goal, if the board is in the book.
+    pub fn path(&self, board: &Board) -> Option<Vec<Board>> {
+        self.distance(board)?;
+        let mut path = vec![*board];
+        while let Some(next) = self.first_move(&path[path.len() - 1]) {
+            path.push(next);
+        }
+        return Some(path);
+    }
+}
+
+/// Looks the board up in the standard book before a full search with
+/// `config` is started. Only answers for the whole standard goal, and for
+/// unit costs since the book knows the fewest moves, not the cheapest ones.
+pub fn lookup_solution(
+    board: &Board,
+    goal: &GoalContext,
+    config: &SearchConfig,
+) -> Option<Solution> {
+    let book = OpeningBook::standard();
+    if book.goal() != goal.goal() || goal.is_partial() || config.cost != CostModel::Unit {
+        return None;
+    }
+
+    let started = Instant::now();
+    let path = book.path(board)?;
+    return Some(Solution {
+        steps: a_star::annotate(&path, goal, config.heuristic),
+        directions: notation::moves(&path),
+        path,
+        phases: Vec::new(),
+        shortened: 0,
+        guaranteed_optimal: true,
+        stats: SearchStats {
+            elapsed: started.elapsed(),
+            ..SearchStats::default()
+        },
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fmt::Write;
+
+    // The shipped table, written out the way `book/table.rs` holds it.
+    fn render(book: &OpeningBook) -> String {
+        let mut entries: Vec<(u64, u8)> = book.distances.iter().map(|(k, v)| (*k, *v)).collect();
+        entries.sort();
+        let mut out = String::new();
+        out.push_str("// Generated by `book::tests::shipped_table_is_up_to_date`; rerun it with\n");
+        out.push_str(
+            "// UPDATE_BOOK_TABLE=1 after changing the book depth or the board packing.\n\n",
+        );
+        out.push_str("/// Distance to the standard goal of every packed canonical board at most\n");
+        out.push_str("/// `BOOK_DEPTH` moves away, sorted by the packed board.\n");
+        out.push_str("pub const DISTANCES: &[(u64, u8)] = &[\n");
+        for (packed, distance) in entries {
+            writeln!(out, "    (0x{:09x}, {}),", packed, distance).unwrap();
+        }
+        out.push_str("];\n");
+        return out;
+    }
+
+    #[test]
+    fn shipped_table_is_up_to_date() {
+        let fresh = render(&OpeningBook::build(&GoalContext::standard(), BOOK_DEPTH));
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/book/table.rs");
+        if std::env::var_os("UPDATE_BOOK_TABLE").is_some() {
+            std::fs::write(path, &fresh).unwrap();
+        }
+        assert_eq!(std::fs::read_to_string(path).unwrap(), fresh);
+    }
+
+    #[test]
+    fn mirrored_twins_share_an_entry_and_both_find_the_goal() {
+        let book = OpeningBook::standard();
+        let oracle = Oracle::build_within(&GoalContext::standard(), BOOK_DEPTH);
+        assert!(book.len() < oracle.len());
+        for (board, distance) in oracle.distances() {
+            assert_eq!(book.distance(board), Some(distance));
+            let path = book.path(board).unwrap();
+            assert_eq!(path.len(), distance as usize + 1);
+            assert_eq!(path.last(), Some(book.goal()));
+        }
+    }
+}
@@ -0,0 +1,361 @@
+// Generated by `book::tests::shipped_table_is_up_to_date`; rerun it with
+// UPDATE_BOOK_TABLE=1 after changing the book depth or the board packing.
+
+/// Distance to the standard goal of every packed canonical board at most
+/// `BOOK_DEPTH` moves away, sorted by the packed board.
+pub const DISTANCES: &[(u64, u8)] = &[
+    (0x025867314, 10),
+    (0x027584631, 10),
+    (0x027864531, 10),
+    (0x028657314, 10),
+    (0x037864251, 10),
+    (0x045827631, 10),
+    (0x045867321, 8),
+    (0x048627531, 10),
+    (0x048637251, 10),
+    (0x048657321, 8),
+    (0x054876321, 10),
+    (0x056847321, 10),
+    (0x057364281, 10),
+    (0x057812634, 10),
+    (0x057824631, 6),
+    (0x057834261, 8),
+    (0x057836214, 10),
+    (0x057841632, 8),
+    (0x057861342, 8),
+    (0x057862314, 8),
+    (0x057864321, 4),
+    (0x058637214, 10),
+    (0x064378251, 10),
+    (0x067381245, 10),
+    (0x067384251, 6),
+    (0x067385214, 8),
+    (0x068347251, 8),
+    (0x068357214, 8),
+    (0x074628531, 10),
+    (0x074638251, 10),
+    (0x074651382, 10),
+    (0x074658321, 8),
+    (0x074681532, 10),
+    (0x084671352, 8),
+    (0x084672315, 10),
+    (0x084675321, 6),
+    (0x085617342, 10),
+    (0x085627314, 8),
+    (0x085647321, 6),
+    (0x087614352, 10),
+    (0x087624315, 10),
+    (0x087651342, 8),
+    (0x087654321, 0),
+    (0x267084531, 9),
+    (0x267584031, 10),
+    (0x267804531, 10),
+    (0x287054631, 9),
+    (0x287504631, 10),
+    (0x287654031, 10),
+    (0x307864251, 9),
+    (0x360847251, 10),
+    (0x367084251, 7),
+    (0x367085214, 9),
+    (0x367204581, 10),
+    (0x367284051, 8),
+    (0x367284501, 9),
+    (0x367284510, 10),
+    (0x367285014, 10),
+    (0x367804251, 8),
+    (0x367805214, 10),
+    (0x367840251, 9),
+    (0x367841250, 10),
+    (0x367854021, 10),
+    (0x367854201, 9),
+    (0x367854210, 10),
+    (0x368047251, 9),
+    (0x368057214, 9),
+    (0x368247051, 10),
+    (0x368257014, 10),
+    (0x368407251, 10),
+    (0x368507214, 10),
+    (0x370864251, 10),
+    (0x387054261, 9),
+    (0x387254061, 10),
+    (0x387504261, 10),
+    (0x504876321, 9),
+    (0x506847321, 9),
+    (0x507364281, 9),
+    (0x507824631, 7),
+    (0x507834261, 9),
+    (0x507841632, 9),
+    (0x507861342, 9),
+    (0x507862314, 9),
+    (0x507864321, 5),
+    (0x508467321, 9),
+    (0x520847631, 10),
+    (0x527684031, 10),
+    (0x527804631, 8),
+    (0x527834061, 10),
+    (0x527834601, 9),
+    (0x527834610, 10),
+    (0x527840631, 9),
+    (0x527841630, 10),
+    (0x537804261, 10),
+    (0x540876321, 10),
+    (0x546807321, 10),
+    (0x547801632, 10),
+    (0x560347281, 10),
+    (0x560387214, 10),
+    (0x560827314, 10),
+    (0x560847321, 8),
+    (0x564378021, 10),
+    (0x567304281, 8),
+    (0x567308214, 10),
+    (0x567340281, 9),
+    (0x567341280, 10),
+    (0x567380214, 9),
+    (0x567381042, 10),
+    (0x567382014, 10),
+    (0x567384021, 6),
+    (0x567384201, 7),
+    (0x567384210, 8),
+    (0x567801342, 10),
+    (0x567804321, 6),
+    (0x567820314, 9),
+    (0x567824301, 7),
+    (0x567824310, 8),
+    (0x568307241, 10),
+    (0x568327014, 10),
+    (0x568347021, 8),
+    (0x568347201, 9),
+    (0x568347210, 10),
+    (0x570364281, 10),
+    (0x604378251, 9),
+    (0x604571382, 9),
+    (0x604578321, 7),
+    (0x604785321, 9),
+    (0x605487321, 9),
+    (0x607284531, 7),
+    (0x607381245, 9),
+    (0x607384251, 5),
+    (0x607385214, 7),
+    (0x607481532, 9),
+    (0x607581342, 7),
+    (0x607582314, 7),
+    (0x607584321, 3),
+    (0x608247531, 9),
+    (0x608347251, 7),
+    (0x608357214, 7),
+    (0x608517342, 9),
+    (0x608527314, 7),
+    (0x608547321, 5),
+    (0x618507342, 10),
+    (0x620578314, 10),
+    (0x628057314, 9),
+    (0x628357014, 10),
+    (0x628507314, 8),
+    (0x628517034, 10),
+    (0x628517304, 9),
+    (0x628517340, 10),
+    (0x628570314, 9),
+    (0x628574310, 10),
+    (0x640378251, 10),
+    (0x640528317, 10),
+    (0x640571382, 10),
+    (0x640578321, 8),
+    (0x640785321, 10),
+    (0x648027531, 9),
+    (0x648037251, 9),
+    (0x648057321, 7),
+    (0x648207531, 10),
+    (0x648237051, 10),
+    (0x648307251, 8),
+    (0x648357021, 8),
+    (0x648357201, 9),
+    (0x648357210, 10),
+    (0x648370251, 9),
+    (0x648371250, 10),
+    (0x648501372, 10),
+    (0x648502317, 10),
+    (0x648507321, 6),
+    (0x648520317, 9),
+    (0x648527031, 8),
+    (0x648527301, 7),
+    (0x648527310, 8),
+    (0x648570321, 7),
+    (0x648571032, 10),
+    (0x648571302, 9),
+    (0x648571320, 8),
+    (0x650378214, 10),
+    (0x650487321, 10),
+    (0x658037214, 9),
+    (0x658237014, 10),
+    (0x658307214, 8),
+    (0x658317024, 10),
+    (0x658317204, 9),
+    (0x658317240, 10),
+    (0x658370214, 9),
+    (0x658374210, 10),
+    (0x670284531, 8),
+    (0x670381245, 10),
+    (0x670384251, 6),
+    (0x670385214, 8),
+    (0x670481532, 10),
+    (0x670514382, 10),
+    (0x670524318, 10),
+    (0x670581342, 8),
+    (0x670582314, 8),
+    (0x670584321, 4),
+    (0x671508342, 10),
+    (0x671580342, 9),
+    (0x671582340, 10),
+    (0x672508314, 10),
+    (0x672580314, 9),
+    (0x672584310, 10),
+    (0x674028531, 9),
+    (0x674038251, 9),
+    (0x674051382, 9),
+    (0x674058321, 7),
+    (0x674081532, 9),
+    (0x674208531, 10),
+    (0x674238051, 10),
+    (0x674280531, 9),
+    (0x674281530, 10),
+    (0x674301285, 10),
+    (0x674308251, 8),
+    (0x674351082, 10),
+    (0x674358021, 8),
+    (0x674358201, 9),
+    (0x674358210, 10),
+    (0x674380251, 7),
+    (0x674381025, 10),
+    (0x674381205, 9),
+    (0x674381250, 8),
+    (0x674501382, 8),
+    (0x674502318, 10),
+    (0x674508321, 6),
+    (0x674510382, 9),
+    (0x674512380, 10),
+    (0x674520318, 9),
+    (0x674528031, 8),
+    (0x674528301, 7),
+    (0x674528310, 8),
+    (0x674580321, 5),
+    (0x674581032, 8),
+    (0x674581302, 7),
+    (0x674581320, 6),
+    (0x674801532, 10),
+    (0x675308214, 10),
+    (0x675380214, 9),
+    (0x675384210, 10),
+    (0x680237514, 10),
+    (0x680247531, 8),
+    (0x680317245, 10),
+    (0x680347251, 6),
+    (0x680357214, 6),
+    (0x680417532, 10),
+    (0x680475321, 10),
+    (0x680517342, 8),
+    (0x680527314, 6),
+    (0x680547321, 4),
+    (0x680754321, 10),
+    (0x684071352, 9),
+    (0x684075321, 7),
+    (0x684305271, 10),
+    (0x684371052, 10),
+    (0x684375021, 8),
+    (0x684375201, 9),
+    (0x684375210, 10),
+    (0x684701352, 10),
+    (0x684705321, 8),
+    (0x684725031, 10),
+    (0x684725301, 9),
+    (0x684725310, 10),
+    (0x684750321, 9),
+    (0x684751320, 10),
+    (0x685027314, 9),
+    (0x685047321, 7),
+    (0x685207314, 10),
+    (0x685307241, 10),
+    (0x685327014, 10),
+    (0x685347021, 8),
+    (0x685347201, 9),
+    (0x685347210, 10),
+    (0x685407321, 8),
+    (0x685427031, 10),
+    (0x685427301, 9),
+    (0x685427310, 10),
+    (0x685470321, 9),
+    (0x685471320, 10),
+    (0x687012534, 9),
+    (0x687015324, 9),
+    (0x687024531, 5),
+    (0x687031245, 9),
+    (0x687034251, 5),
+    (0x687035214, 7),
+    (0x687041325, 9),
+    (0x687041532, 7),
+    (0x687051342, 7),
+    (0x687052314, 7),
+    (0x687054321, 1),
+    (0x687102534, 10),
+    (0x687105324, 10),
+    (0x687201543, 10),
+    (0x687203514, 10),
+    (0x687204531, 6),
+    (0x687205134, 10),
+    (0x687230514, 9),
+    (0x687231045, 10),
+    (0x687234051, 6),
+    (0x687234501, 7),
+    (0x687234510, 8),
+    (0x687235014, 8),
+    (0x687235104, 9),
+    (0x687235140, 10),
+    (0x687240531, 7),
+    (0x687241053, 10),
+    (0x687241503, 9),
+    (0x687241530, 8),
+    (0x687301245, 8),
+    (0x687301452, 10),
+    (0x687302154, 10),
+    (0x687304251, 4),
+    (0x687305214, 6),
+    (0x687310245, 9),
+    (0x687315024, 8),
+    (0x687315204, 7),
+    (0x687315240, 8),
+    (0x687340251, 5),
+    (0x687341025, 8),
+    (0x687341205, 7),
+    (0x687341250, 6),
+    (0x687350214, 5),
+    (0x687351042, 8),
+    (0x687351402, 9),
+    (0x687351420, 10),
+    (0x687352014, 8),
+    (0x687352104, 9),
+    (0x687352140, 10),
+    (0x687354021, 2),
+    (0x687354201, 3),
+    (0x687354210, 4),
+    (0x687401325, 10),
+    (0x687401532, 8),
+    (0x687410532, 9),
+    (0x687412530, 10),
+    (0x687431052, 10),
+    (0x687431502, 9),
+    (0x687431520, 10),
+    (0x687501342, 6),
+    (0x687502314, 6),
+    (0x687504321, 2),
+    (0x687510342, 7),
+    (0x687512034, 8),
+    (0x687512304, 7),
+    (0x687512340, 8),
+    (0x687520314, 5),
+    (0x687524031, 4),
+    (0x687524301, 3),
+    (0x687524310, 4),
+    (0x687540321, 3),
+    (0x687541032, 6),
+    (0x687541302, 5),
+    (0x687541320, 4),
+];
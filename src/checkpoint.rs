@@ -0,0 +1,45 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use anyhow::{Context, Result, bail};
+use eight_puzzle_solver::a_star::Checkpoint;
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// Expanded boards between two checkpoints.
+pub const EVERY: usize = 1_000_000;
+
+// Starts every checkpoint file; the digit changes with the format.
+const MAGIC: &[u8; 8] = b"EPSCKPT2";
+
+/// Writes `checkpoint` next to `path` first, so a crash while writing never
+/// leaves a broken checkpoint behind.
+pub fn save(checkpoint: &Checkpoint, path: &Path) -> Result<()> {
+    let partial = path.with_extension("partial");
+    let file =
+        File::create(&partial).with_context(|| format!("cannot create {}", partial.display()))?;
+    let mut writer = BufWriter::new(file);
+    writer.write_all(MAGIC)?;
+    bincode::serialize_into(&mut writer, checkpoint)
+        .with_context(|| format!("cannot write {}", partial.display()))?;
+    writer.flush()?;
+    fs::rename(&partial, path).with_context(|| format!("cannot replace {}", path.display()))?;
+    return Ok(());
+}
+
+pub fn load(path: &Path) -> Result<Checkpoint> {
+    let file = File::open(path).with_context(|| format!("cannot open {}", path.display()))?;
+    let mut reader = BufReader::new(file);
+    let mut magic = [0u8; 8];
+    if reader.read_exact(&mut magic).is_err() || &magic != MAGIC {
+        bail!(
+            "{} is not a checkpoint, or one from another version",
+            path.display()
+        );
+    }
+    return bincode::deserialize_from(reader)
+        .with_context(|| format!("invalid checkpoint {}", path.display()));
+}
@@ -0,0 +1,918 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    checkpoint,
+    config::{Config, ConfigArgs},
+};
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use eight_puzzle_solver::{
+    a_star::{self, Checkpoint, SearchConfig, SearchProfile, Solution},
+    algorithms::{Algorithm, SearchOutcome},
+    board::{self, Board},
+    book, compare,
+    dot::SearchGraph,
+    export,
+    goal::GoalContext,
+    heuristic::Heuristic,
+    notation,
+    optimal::OptimalPaths,
+    oracle::{self, Oracle},
+    puzzles::{self, Collection},
+    report::{self, Row},
+    rng,
+    tournament::{self, Budget, Entrant},
+    validate::{self, CellOrder},
+};
+use rand::seq::IndexedRandom;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[derive(Parser)]
+#[command(version, about = "Solve the 8-puzzle with A*")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    /// Record every terminal event of the TUI session to FILE
+    #[arg(long, value_name = "FILE")]
+    pub record_input: Option<PathBuf>,
+    /// Replay the terminal events recorded in FILE, then continue with live input
+    #[arg(long, value_name = "FILE")]
+    pub replay_input: Option<PathBuf>,
+    /// Start the TUI with the puzzle scrambled from this seed
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Start the TUI with a puzzle scrambled by this many random moves
+    /// (from --seed, or a random seed)
+    #[arg(long, value_name = "MOVES")]
+    pub scramble: Option<usize>,
+    /// Start the TUI with this board in the editor, row by row with 0 for
+    /// the empty cell
+    #[arg(long, conflicts_with_all = ["seed", "scramble", "open_replay"])]
+    pub board: Option<String>,
+    /// Solve the board given with --board straight away and open the solution
+    /// in the step viewer
+    #[arg(long, requires = "board")]
+    pub auto: bool,
+    /// Start the TUI with the guided tour of a first solve
+    #[arg(long)]
+    pub tutorial: bool,
+    /// Start the TUI from scratch instead of restoring the last session
+    #[arg(long)]
+    pub fresh: bool,
+    /// Use line-based prompts instead of the TUI, for screen readers
+    #[arg(long, conflicts_with_all = ["record_input", "replay_input"])]
+    pub plain: bool,
+    /// Answer JSON requests read from stdin line by line, one JSON response
+    /// per line on stdout, instead of starting the TUI
+    #[arg(long, conflicts_with_all = ["record_input", "replay_input", "plain"])]
+    pub pipe: bool,
+    /// Offer the puzzles of this collection (TOML or JSON) as presets
+    #[arg(long, value_name = "FILE")]
+    pub presets: Option<PathBuf>,
+    /// Play back a game exported from play mode in the step viewer
+    #[arg(long, value_name = "FILE")]
+    pub open_replay: Option<PathBuf>,
+    /// Write a log of what the solver and the TUI are doing to FILE
+    #[arg(long, value_name = "FILE", global = true)]
+    pub log_file: Option<PathBuf>,
+}
+
+impl Cli {
+    /// The options given that only the TUI uses, as written on the command
+    /// line.
+    pub fn tui_flags(&self) -> Vec<&'static str> {
+        let given = [
+            ("--record-input", self.record_input.is_some()),
+            ("--replay-input", self.replay_input.is_some()),
+            ("--seed", self.seed.is_some()),
+            ("--scramble", self.scramble.is_some()),
+            ("--board", self.board.is_some()),
+            ("--auto", self.auto),
+            ("--tutorial", self.tutorial),
+            ("--fresh", self.fresh),
+            ("--presets", self.presets.is_some()),
+            ("--open-replay", self.open_replay.is_some()),
+        ];
+        return given
+            .into_iter()
+            .filter(|(_, given)| *given)
+            .map(|(flag, _)| flag)
+            .collect();
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Solve a board given row by row, with 0 for the empty cell (e.g. 724506831)
+    Solve {
+        /// Board to solve, row by row with 0 for the empty cell
+        board: String,
+        /// How to print the solution
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Also write the explored search tree as a GraphViz DOT file
+        #[arg(long, value_name = "FILE")]
+        dot: Option<PathBuf>,
+        /// Save the state of the search to FILE every million expanded boards
+        #[arg(long, value_name = "FILE", conflicts_with = "dot")]
+        checkpoint: Option<PathBuf>,
+        /// Carry on with the search saved in FILE, checkpointing to it again
+        /// unless --checkpoint names another file
+        #[arg(long, value_name = "FILE", conflicts_with = "dot")]
+        resume: Option<PathBuf>,
+    },
+    /// Cross-check A* solution lengths against the exact BFS oracle
+    CheckOptimality {
+        /// Number of random boards to check
+        #[arg(long, default_value_t = 1000)]
+        samples: usize,
+        /// Only check this heuristic (default: all of them)
+        #[arg(long)]
+        only: Option<Heuristic>,
+        /// Seed for the random boards
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Run every algorithm and heuristic on a set of puzzles and compare them
+    CompareMatrix {
+        /// File with one board per line (default: random scrambles)
+        #[arg(long, value_name = "FILE")]
+        puzzles: Option<PathBuf>,
+        /// Number of random puzzles when no file is given
+        #[arg(long, default_value_t = 20)]
+        random: usize,
+        /// Seed for the random puzzles
+        #[arg(long)]
+        seed: Option<u64>,
+        /// How to print the matrix
+        #[arg(long, value_enum, default_value_t = MatrixFormat::Table)]
+        format: MatrixFormat,
+        /// Also write the matrix as an HTML report
+        #[arg(long, value_name = "FILE")]
+        html: Option<PathBuf>,
+        /// Also write one CSV row per puzzle and combination to FILE
+        #[arg(long, value_name = "FILE")]
+        csv: Option<PathBuf>,
+    },
+    /// Rank algorithms and heuristics by how they do on the same random
+    /// puzzles within the same budget
+    Tournament {
+        /// ALGORITHM:HEURISTIC, or ALGORITHM alone for bfs and iddfs; repeat
+        /// for more (default: every combination)
+        #[arg(long = "entrant", value_name = "ENTRANT")]
+        entrants: Vec<Entrant>,
+        /// Number of random puzzles
+        #[arg(long, default_value_t = 20)]
+        random: usize,
+        /// Seed for the random puzzles
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Boards a search may expand before the puzzle counts as unsolved
+        #[arg(long, value_name = "N")]
+        max_nodes: Option<usize>,
+        /// Milliseconds a search may take before the puzzle counts as unsolved
+        #[arg(long, value_name = "MS")]
+        time_limit: Option<u64>,
+        /// How to print the standings
+        #[arg(long, value_enum, default_value_t = MatrixFormat::Table)]
+        format: MatrixFormat,
+        /// Also write one CSV row per puzzle and entrant to FILE
+        #[arg(long, value_name = "FILE")]
+        csv: Option<PathBuf>,
+    },
+    /// Solve every puzzle of a file with the configured algorithm
+    SolveBatch {
+        /// Puzzle collection (.toml or .json) or file with one board per line
+        puzzles: PathBuf,
+        /// Also write one CSV row per puzzle to FILE
+        #[arg(long, value_name = "FILE")]
+        csv: Option<PathBuf>,
+    },
+    /// Solve a board with A* several times without the TUI and print where
+    /// the time went, for profilers like cargo flamegraph
+    Profile {
+        /// Board to solve, row by row with 0 for the empty cell
+        board: String,
+        /// Number of timed searches, after one untimed warm-up
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+    },
+    /// Facts about puzzles rather than solutions
+    Analyze {
+        #[command(subcommand)]
+        command: AnalyzeCommand,
+    },
+    /// Consistency checks for developers
+    Debug {
+        #[command(subcommand)]
+        command: DebugCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AnalyzeCommand {
+    /// Count the distinct shortest solutions of a board
+    OptimalSolutions {
+        /// Board to analyze, row by row with 0 for the empty cell
+        board: String,
+        /// Also print the first N of them as LURD strings
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        list: usize,
+    },
+    /// List the boards farthest from the goal
+    Hardest {
+        /// Print every board as a grid instead of its one-line code
+        #[arg(long)]
+        grid: bool,
+    },
+    /// Count the boards at every distance from the goal
+    DepthDistribution {
+        /// How to print the counts
+        #[arg(long, value_enum, default_value_t = MatrixFormat::Table)]
+        format: MatrixFormat,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DebugCommand {
+    /// Check that no heuristic overestimates the exact distance to the goal
+    CheckHeuristic {
+        /// Number of random boards to check
+        #[arg(long, default_value_t = 5000)]
+        samples: usize,
+        /// Only check this heuristic (default: all of them)
+        #[arg(long)]
+        only: Option<Heuristic>,
+        /// Seed for the random boards
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+}
+
+pub fn run(command: Command, config: &Config) -> Result<()> {
+    return match command {
+        Command::Solve {
+            board,
+            format,
+            dot,
+            checkpoint,
+            resume,
+        } => match (checkpoint, resume) {
+            (None, None) => solve(board, format, dot, config),
+            (save_to, resume) => solve_checkpointed(board, format, save_to, resume, config),
+        },
+        Command::CheckOptimality {
+            samples,
+            only,
+            seed,
+        } => check_optimality(samples, only, seed, config),
+        Command::CompareMatrix {
+            puzzles,
+            random,
+            seed,
+            format,
+            html,
+            csv,
+        } => compare_matrix(puzzles, random, seed, format, html, csv, config),
+        Command::Tournament {
+            entrants,
+            random,
+            seed,
+            max_nodes,
+            time_limit,
+            format,
+            csv,
+        } => {
+            let budget = Budget {
+                max_expanded: max_nodes,
+                time_limit: time_limit.map(Duration::from_millis),
+            };
+            tournament(entrants, random, seed, budget, format, csv, config)
+        }
+        Command::SolveBatch { puzzles, csv } => solve_batch(&puzzles, csv, config),
+        Command::Profile { board, runs } => profile(board, runs, config),
+        Command::Analyze {
+            command: AnalyzeCommand::OptimalSolutions { board, list },
+        } => optimal_solutions(board, list, config),
+        Command::Analyze {
+            command: AnalyzeCommand::Hardest { grid },
+        } => hardest(grid, config),
+        Command::Analyze {
+            command: AnalyzeCommand::DepthDistribution { format },
+        } => depth_distribution(format, config),
+        Command::Debug {
+            command:
+                DebugCommand::CheckHeuristic {
+                    samples,
+                    only,
+                    seed,
+                },
+        } => check_heuristic(samples, only, seed, config),
+    };
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+pub enum OutputFormat {
+    /// Every board of the solution, one after another
+    Text,
+    /// The moves of the blank as a single LURD string
+    Lurd,
+    /// An asciicast v2 recording of the solution being played back
+    Asciicast,
+    /// A Markdown walkthrough with every board, for write-ups and issues
+    Markdown,
+    /// A JSON document with every move, its board and its comment
+    Json,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+pub enum MatrixFormat {
+    /// An aligned table
+    Table,
+    /// Comma-separated values with a header line
+    Csv,
+}
+
+fn solve(board: String, format: OutputFormat, dot: Option<PathBuf>, config: &Config) -> Result<()> {
+    let goal = config.goal();
+    let board = match validate::check_import(&board, &goal, config.cell_order) {
+        Ok(board) => board,
+        Err(rejection) => bail!("rejected board: {}", rejection),
+    };
+    // the search graph needs a real search, the book would skip it
+    let mut graph = SearchGraph::new();
+    let from_book = match dot {
+        Some(_) => None,
+        None => book::lookup_solution(&board, &goal, &config.search),
+    };
+    let outcome = match from_book {
+        Some(solution) => SearchOutcome::Solved(solution),
+        None => config
+            .algorithm
+            .solve_observed(board, &goal, config.search, &mut |progress| {
+                if dot.is_some() {
+                    graph.record(progress);
+                }
+                true
+            }),
+    };
+    let result = outcome.into_result(config.algorithm, &config.search);
+    if let Some(path) = &dot {
+        fs::write(path, graph.to_dot(result.as_ref().ok()))
+            .with_context(|| format!("cannot write {}", path.display()))?;
+    }
+    let solution = match result {
+        Ok(solution) => solution,
+        Err(message) => bail!("{}", message),
+    };
+
+    print_solution(&solution, format);
+    return Ok(());
+}
+
+fn print_solution(solution: &Solution, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => print!("{}", export::to_text(solution)),
+        OutputFormat::Lurd => println!("{}", notation::to_letters(&solution.directions)),
+        OutputFormat::Asciicast => print!("{}", export::to_asciicast(solution)),
+        OutputFormat::Markdown => print!("{}", export::to_markdown(solution)),
+        OutputFormat::Json => print!("{}", export::to_json(solution)),
+    }
+}
+
+// A* that saves where it stands every `checkpoint::EVERY` expanded boards, so
+// a long search can be picked up again after it was interrupted.
+fn solve_checkpointed(
+    board: String,
+    format: OutputFormat,
+    save_to: Option<PathBuf>,
+    resume: Option<PathBuf>,
+    config: &Config,
+) -> Result<()> {
+    if config.algorithm != Algorithm::AStar {
+        bail!("only a-star searches can be checkpointed");
+    }
+    let goal = config.goal();
+    let board = match validate::check_import(&board, &goal, config.cell_order) {
+        Ok(board) => board,
+        Err(rejection) => bail!("rejected board: {}", rejection),
+    };
+    let start = match &resume {
+        Some(path) => {
+            let saved = checkpoint::load(path)?;
+            if saved.start != board || saved.goal != *goal.goal() {
+                bail!("{} is the search of another puzzle", path.display());
+            }
+            eprintln!(
+                "resuming after {} expanded boards, with the settings the search started with",
+                saved.stats.expanded
+            );
+            saved
+        }
+        None => Checkpoint::new(board, &goal, config.search),
+    };
+    let Some(path) = save_to.or(resume) else {
+        bail!("nowhere to save the checkpoints");
+    };
+
+    let mut failure: Option<anyhow::Error> = None;
+    let result = a_star::resume(
+        start,
+        &goal,
+        checkpoint::EVERY,
+        &mut |saved| match checkpoint::save(saved, &path) {
+            Ok(()) => {
+                eprintln!("checkpoint after {} expanded boards", saved.stats.expanded);
+                true
+            }
+            Err(e) => {
+                failure = Some(e);
+                false
+            }
+        },
+    );
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    let Some(solution) = result else {
+        bail!("{}", config.algorithm.failure_message(&config.search));
+    };
+
+    print_solution(&solution, format);
+    return Ok(());
+}
+
+// The goal for the configured size and the exact distances to it.
+fn exact_distances(config: &Config) -> Result<(GoalContext, Oracle)> {
+    let size = config.board_size;
+    if size.rows * size.cols > oracle::MAX_CELLS {
+        bail!(
+            "exact distances are only available for boards of up to {} cells",
+            oracle::MAX_CELLS
+        );
+    }
+    let goal = config.goal();
+    let oracle = Oracle::build(&goal);
+    return Ok((goal, oracle));
+}
+
+fn sample_boards<'a>(
+    oracle: &'a Oracle,
+    samples: usize,
+    seed: Option<u64>,
+    config: &Config,
+) -> Vec<&'a Board> {
+    let seed = seed.unwrap_or_else(|| config.fresh_seed());
+    eprintln!("random boards from seed {}", seed);
+    // the oracle keeps its boards in no particular order
+    let mut boards: Vec<&Board> = oracle.boards().collect();
+    boards.sort_by_key(|board| board.packed());
+    return boards
+        .sample(&mut rng::seeded(seed), samples)
+        .copied()
+        .collect();
+}
+
+fn check_optimality(
+    samples: usize,
+    heuristic: Option<Heuristic>,
+    seed: Option<u64>,
+    config: &Config,
+) -> Result<()> {
+    let (goal, oracle) = exact_distances(config)?;
+    let heuristics: Vec<Heuristic> = match heuristic {
+        Some(h) => vec![h],
+        None => Heuristic::ALL.to_vec(),
+    };
+    let sample = sample_boards(&oracle, samples, seed, config);
+
+    let mut failures = 0;
+    for board in &sample {
+        let expected = oracle.distance(board).unwrap() as usize;
+        for h in &heuristics {
+            let config = SearchConfig {
+                heuristic: *h,
+                ..SearchConfig::default()
+            };
+            let moves = match a_star::search_with(**board, &goal, config) {
+                Some(solution) => solution.moves(),
+                None => {
+                    println!("{}: no solution found for {}", h.name(), board.code());
+                    failures += 1;
+                    continue;
+                }
+            };
+            if moves != expected {
+                println!(
+                    "{}: {} moves instead of {} for {}",
+                    h.name(),
+                    moves,
+                    expected,
+                    board.code()
+                );
+                failures += 1;
+            }
+        }
+    }
+
+    println!(
+        "checked {} boards x {} heuristics, {} non-optimal",
+        sample.len(),
+        heuristics.len(),
+        failures
+    );
+    if failures > 0 {
+        bail!("found {} non-optimal solutions", failures);
+    }
+    return Ok(());
+}
+
+fn check_heuristic(
+    samples: usize,
+    heuristic: Option<Heuristic>,
+    seed: Option<u64>,
+    config: &Config,
+) -> Result<()> {
+    let (goal, oracle) = exact_distances(config)?;
+    let heuristics: Vec<Heuristic> = match heuristic {
+        Some(h) => vec![h],
+        None => Heuristic::ALL.to_vec(),
+    };
+    let sample = sample_boards(&oracle, samples, seed, config);
+
+    let mut failures = 0;
+    for board in &sample {
+        let exact = oracle.distance(board).unwrap() as i64;
+        for h in &heuristics {
+            let estimate = h.estimate(board, &goal);
+            if estimate > exact {
+                println!(
+                    "{}: estimates {} moves but {} are enough for {}\n{}\n",
+                    h.name(),
+                    estimate,
+                    exact,
+                    board.code(),
+                    board
+                );
+                failures += 1;
+            }
+        }
+    }
+
+    println!(
+        "checked {} boards x {} heuristics, {} overestimates",
+        sample.len(),
+        heuristics.len(),
+        failures
+    );
+    if failures > 0 {
+        bail!("found {} overestimates", failures);
+    }
+    return Ok(());
+}
+
+fn optimal_solutions(board: String, list: usize, config: &Config) -> Result<()> {
+    let goal = config.goal();
+    let board = match validate::check_import(&board, &goal, config.cell_order) {
+        Ok(board) => board,
+        Err(rejection) => bail!("rejected board: {}", rejection),
+    };
+    // exact distances prune every detour, a heuristic only most of them
+    let paths = match config.board_size.rows * config.board_size.cols <= oracle::MAX_CELLS {
+        true => {
+            let oracle = Oracle::build(&goal);
+            let Some(length) = oracle.distance(&board) else {
+                bail!("the goal cannot be reached from this board");
+            };
+            let exact = |b: &Board| oracle.distance(b).map_or(i64::MAX, |d| d as i64);
+            OptimalPaths::count(board, &goal, length as usize, &exact)
+        }
+        false => {
+            let search = SearchConfig {
+                heuristic: Heuristic::LinearConflict,
+                weight: 1,
+                ..config.search
+            };
+            let Some(solution) = a_star::search_with(board, &goal, search) else {
+                bail!("the goal cannot be reached from this board");
+            };
+            let estimate = |b: &Board| Heuristic::LinearConflict.estimate(b, &goal);
+            OptimalPaths::count(board, &goal, solution.moves(), &estimate)
+        }
+    };
+
+    let plural = |n: u128| if n == 1 { "" } else { "s" };
+    println!(
+        "{} optimal solution{} of {} move{}",
+        paths.len(),
+        plural(paths.len()),
+        paths.length(),
+        plural(paths.length() as u128)
+    );
+    for index in 0..paths.len().min(list as u128) {
+        if let Some(path) = paths.nth(index) {
+            println!("{}", notation::to_lurd(&path));
+        }
+    }
+    return Ok(());
+}
+
+fn hardest(grid: bool, config: &Config) -> Result<()> {
+    let (_, oracle) = exact_distances(config)?;
+    let boards = oracle.hardest();
+    if let Some(distance) = boards.first().and_then(|b| oracle.distance(b)) {
+        let plural = if boards.len() == 1 { "" } else { "s" };
+        eprintln!(
+            "{} board{} {} moves from the goal",
+            boards.len(),
+            plural,
+            distance
+        );
+    }
+    for board in &boards {
+        match grid {
+            true => println!("{}\n", board),
+            false => println!("{}", board.code()),
+        }
+    }
+    return Ok(());
+}
+
+fn depth_distribution(format: MatrixFormat, config: &Config) -> Result<()> {
+    let started = Instant::now();
+    let (_, oracle) = exact_distances(config)?;
+    eprintln!(
+        "searched all {} boards in {:.1} ms",
+        oracle.len(),
+        started.elapsed().as_secs_f64() * 1000.0
+    );
+    let counts = oracle.depth_distribution();
+    match format {
+        MatrixFormat::Table => {
+            println!("distance  boards");
+            println!("--------  ------");
+            for (distance, boards) in counts.iter().enumerate() {
+                println!("{:<8}  {}", distance, boards);
+            }
+            let farthest = counts.len() - 1;
+            println!(
+                "\n{} boards, the farthest {} moves from the goal ({} of them)",
+                oracle.len(),
+                farthest,
+                counts[farthest]
+            );
+        }
+        MatrixFormat::Csv => {
+            println!("distance,boards");
+            for (distance, boards) in counts.iter().enumerate() {
+                println!("{},{}", distance, boards);
+            }
+        }
+    }
+    return Ok(());
+}
+
+// Boards from a puzzle collection (`.toml` or `.json`), or from a plain list
+// with one board per line.
+fn load_puzzles(path: &Path, goal: &GoalContext, order: CellOrder) -> Result<Vec<Board>> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    let mut boards = Vec::new();
+    let is_collection = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("toml") || e.eq_ignore_ascii_case("json"));
+    if is_collection {
+        let collection = Collection::parse(&text, puzzles::Format::from_path(path))
+            .with_context(|| format!("invalid puzzle collection {}", path.display()))?;
+        for puzzle in &collection.puzzles {
+            match puzzle.board(goal) {
+                Ok(board) => boards.push(board),
+                Err(rejection) => bail!(
+                    "{}: {}: rejected board: {}",
+                    path.display(),
+                    puzzle.title(),
+                    rejection
+                ),
+            }
+        }
+        return Ok(boards);
+    }
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match validate::check_import(line, goal, order) {
+            Ok(board) => boards.push(board),
+            Err(rejection) => {
+                bail!(
+                    "{}:{}: rejected board: {}",
+                    path.display(),
+                    i + 1,
+                    rejection
+                )
+            }
+        }
+    }
+    return Ok(boards);
+}
+
+fn compare_matrix(
+    puzzles: Option<PathBuf>,
+    random: usize,
+    seed: Option<u64>,
+    format: MatrixFormat,
+    html: Option<PathBuf>,
+    csv: Option<PathBuf>,
+    config: &Config,
+) -> Result<()> {
+    let goal = config.goal();
+    let boards: Vec<Board> = match puzzles {
+        Some(path) => load_puzzles(&path, &goal, config.cell_order)?,
+        None => random_puzzles(&goal, random, seed, config),
+    };
+
+    let rows = compare::benchmark(&boards, &goal, config.search);
+    let entries = compare::summarize(&rows);
+    match format {
+        MatrixFormat::Table => print!("{}", export::matrix_table(&entries)),
+        MatrixFormat::Csv => print!("{}", export::matrix_csv(&entries)),
+    }
+    if let Some(path) = &html {
+        fs::write(path, export::matrix_html(&entries, boards.len()))
+            .with_context(|| format!("cannot write {}", path.display()))?;
+    }
+    if let Some(path) = &csv {
+        fs::write(path, report::to_csv(&rows))
+            .with_context(|| format!("cannot write {}", path.display()))?;
+    }
+    return Ok(());
+}
+
+// `count` scrambles of the goal, one seed per puzzle so the set is
+// reproducible from the first
+fn random_puzzles(
+    goal: &GoalContext,
+    count: usize,
+    seed: Option<u64>,
+    config: &Config,
+) -> Vec<Board> {
+    let seed = seed.unwrap_or_else(|| config.fresh_seed());
+    eprintln!("random puzzles from seed {}", seed);
+    return (0..count as u64)
+        .map(|i| {
+            board::scramble_from(
+                goal.goal(),
+                seed.wrapping_add(i),
+                board::DEFAULT_SCRAMBLE_MOVES,
+            )
+        })
+        .collect();
+}
+
+fn tournament(
+    entrants: Vec<Entrant>,
+    random: usize,
+    seed: Option<u64>,
+    budget: Budget,
+    format: MatrixFormat,
+    csv: Option<PathBuf>,
+    config: &Config,
+) -> Result<()> {
+    let goal = config.goal();
+    let entrants = match entrants.is_empty() {
+        true => Entrant::all(),
+        false => entrants,
+    };
+    let boards = random_puzzles(&goal, random, seed, config);
+
+    let rows = tournament::play(&entrants, &boards, &goal, config.search, budget);
+    // exact distances tell which solves are optimal where they are cheap,
+    // elsewhere the shortest solve of any entrant stands in for them
+    let oracle = (config.board_size.rows * config.board_size.cols <= oracle::MAX_CELLS)
+        .then(|| Oracle::build(&goal));
+    let standings = tournament::standings(&rows, |board| {
+        return oracle
+            .as_ref()
+            .and_then(|o| o.distance(board))
+            .map(usize::from);
+    });
+    match format {
+        MatrixFormat::Table => print!("{}", export::tournament_table(&standings)),
+        MatrixFormat::Csv => print!("{}", export::tournament_csv(&standings)),
+    }
+    if let Some(path) = &csv {
+        fs::write(path, report::to_csv(&rows))
+            .with_context(|| format!("cannot write {}", path.display()))?;
+    }
+    return Ok(());
+}
+
+fn solve_batch(puzzles: &Path, csv: Option<PathBuf>, config: &Config) -> Result<()> {
+    let goal = config.goal();
+    let boards = load_puzzles(puzzles, &goal, config.cell_order)?;
+    let algorithm = config.algorithm;
+    let heuristic = Some(config.search.heuristic).filter(|_| algorithm.is_informed());
+    let width = boards.iter().map(|b| b.code().len()).max().unwrap_or(0);
+    println!(
+        "{:<width$}  {:>6}  {:>10}  {:>10}",
+        "puzzle", "length", "nodes", "time (ms)"
+    );
+    let mut rows: Vec<Row> = Vec::new();
+    for board in boards {
+        let solution = algorithm.solve(board, &goal, config.search).solution();
+        let row = Row::new(board, algorithm, heuristic, solution.as_ref());
+        match row.length {
+            Some(length) => println!(
+                "{:<width$}  {:>6}  {:>10}  {:>10.3}",
+                board.code(),
+                length,
+                row.stats.expanded,
+                row.millis()
+            ),
+            None => println!("{:<width$}  unsolved", board.code()),
+        }
+        rows.push(row);
+    }
+    let solved = rows.iter().filter(|r| r.length.is_some()).count();
+    println!("{} of {} solved", solved, rows.len());
+    if let Some(path) = &csv {
+        fs::write(path, report::to_csv(&rows))
+            .with_context(|| format!("cannot write {}", path.display()))?;
+    }
+    return Ok(());
+}
+
+// The same A* search `runs` times, with the time of each of its parts added
+// up. Nothing else happens in between, so a sampling profiler run on this
+// sees the search and little more.
+fn profile(board: String, runs: usize, config: &Config) -> Result<()> {
+    if config.algorithm != Algorithm::AStar {
+        bail!("only a-star searches can be profiled");
+    }
+    let goal = config.goal();
+    let board = match validate::check_import(&board, &goal, config.cell_order) {
+        Ok(board) => board,
+        Err(rejection) => bail!("rejected board: {}", rejection),
+    };
+    // the warm-up fills the caches and tells whether there is anything to time
+    let Some(solution) = a_star::search_with(board, &goal, config.search) else {
+        bail!("no solution found");
+    };
+    println!(
+        "{}: {} moves, {} boards expanded per run",
+        board.code(),
+        solution.moves(),
+        solution.stats.expanded
+    );
+    if solution.stats.symmetric > 0 {
+        println!(
+            "{} symmetric twins skipped per run",
+            solution.stats.symmetric
+        );
+    }
+
+    let mut profile = SearchProfile::default();
+    for _ in 0..runs {
+        a_star::search_profiled(board, &goal, config.search, &mut profile);
+    }
+    let parts = [
+        ("successors", profile.successors),
+        ("heuristic", profile.heuristic),
+        ("queue", profile.queue),
+        ("visited", profile.visited),
+    ];
+    let measured: Duration = parts.iter().map(|(_, time)| *time).sum();
+    let other = ("other", profile.total.saturating_sub(measured));
+    let total = profile.total.as_secs_f64().max(f64::EPSILON);
+    let per_run = runs.max(1) as f64;
+    println!(
+        "{:<10}  {:>10}  {:>10}  {:>6}",
+        "phase", "total (ms)", "per run", "share"
+    );
+    for (name, time) in parts.iter().chain([&other]) {
+        let millis = time.as_secs_f64() * 1000.0;
+        println!(
+            "{:<10}  {:>10.3}  {:>10.3}  {:>5.1}%",
+            name,
+            millis,
+            millis / per_run,
+            time.as_secs_f64() / total * 100.0
+        );
+    }
+    println!(
+        "{:<10}  {:>10.3}  {:>10.3}",
+        "total",
+        profile.total.as_secs_f64() * 1000.0,
+        profile.total.as_secs_f64() * 1000.0 / per_run
+    );
+    return Ok(());
+}
@@ -0,0 +1,21 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use arboard::Clipboard;
+
+// The clipboard is not always reachable (e.g. over SSH without X forwarding),
+// so every failure is turned into a message for the UI instead of an error.
+
+pub fn copy(text: &str) -> Result<(), String> {
+    let mut clipboard = Clipboard::new().map_err(unavailable)?;
+    return clipboard.set_text(text).map_err(unavailable);
+}
+
+pub fn paste() -> Result<String, String> {
+    let mut clipboard = Clipboard::new().map_err(unavailable)?;
+    return clipboard.get_text().map_err(unavailable);
+}
+
+fn unavailable(e: arboard::Error) -> String {
+    return format!("Clipboard unavailable: {}", e);
+}
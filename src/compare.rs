@@ -0,0 +1,110 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::SearchConfig, algorithms::Algorithm, board::Board, goal::GoalContext,
+    heuristic::Heuristic, report::Row,
+};
+
+use std::time::Duration;
+
+/// Totals for one algorithm and heuristic combination over a puzzle set.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatrixEntry {
+    pub algorithm: Algorithm,
+    pub heuristic: Option<Heuristic>, // `None` for the uninformed algorithms
+    pub solved: usize,
+    pub unsolved: usize,
+    pub expanded: usize, // summed over the solved puzzles, like the two below
+    pub moves: usize,
+    pub elapsed: Duration,
+}
+
+impl MatrixEntry {
+    pub fn avg_expanded(&self) -> f64 {
+        return self.per_solved(self.expanded as f64);
+    }
+
+    pub fn avg_moves(&self) -> f64 {
+        return self.per_solved(self.moves as f64);
+    }
+
+    pub fn avg_millis(&self) -> f64 {
+        return self.per_solved(self.elapsed.as_secs_f64() * 1000.0);
+    }
+
+    fn per_solved(&self, total: f64) -> f64 {
+        if self.solved == 0 {
+            return 0.0;
+        }
+        return total / self.solved as f64;
+    }
+}
+
+/// Solves every board with every algorithm and heuristic, using the rest of
+/// `base` (weight, beam width) for all of them. Uninformed algorithms run
+/// once, without a heuristic.
+pub fn compare_matrix(
+    boards: &[Board],
+    goal: &GoalContext,
+    base: SearchConfig,
+) -> Vec<MatrixEntry> {
+    return summarize(&benchmark(boards, goal, base));
+}
+
+/// Same runs as [`compare_matrix`], one row per board and combination.
+pub fn benchmark(boards: &[Board], goal: &GoalContext, base: SearchConfig) -> Vec<Row> {
+    let mut rows: Vec<Row> = Vec::new();
+    for algorithm in Algorithm::ALL {
+        let heuristics: Vec<Option<Heuristic>> = match algorithm.is_informed() {
+            true => Heuristic::ALL.into_iter().map(Some).collect(),
+            false => vec![None],
+        };
+        for heuristic in heuristics {
+            let config = SearchConfig {
+                heuristic: heuristic.unwrap_or_default(),
+                ..base
+            };
+            for board in boards {
+                let solution = algorithm.solve(*board, goal, config).solution();
+                rows.push(Row::new(*board, algorithm, heuristic, solution.as_ref()));
+            }
+        }
+    }
+    return rows;
+}
+
+/// Totals of the rows of each combination, in the order they first appear.
+pub fn summarize(rows: &[Row]) -> Vec<MatrixEntry> {
+    let mut entries: Vec<MatrixEntry> = Vec::new();
+    for row in rows {
+        let position = entries
+            .iter()
+            .position(|e| e.algorithm == row.algorithm && e.heuristic == row.heuristic);
+        let entry = match position {
+            Some(i) => &mut entries[i],
+            None => {
+                entries.push(MatrixEntry {
+                    algorithm: row.algorithm,
+                    heuristic: row.heuristic,
+                    solved: 0,
+                    unsolved: 0,
+                    expanded: 0,
+                    moves: 0,
+                    elapsed: Duration::ZERO,
+                });
+                entries.last_mut().unwrap()
+            }
+        };
+        match row.length {
+            Some(length) => {
+                entry.solved += 1;
+                entry.expanded += row.stats.expanded;
+                entry.moves += length;
+                entry.elapsed += row.stats.elapsed;
+            }
+            None => entry.unsolved += 1,
+        }
+    }
+    return entries;
+}
@@ -0,0 +1,481 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{race, theme::Theme};
+use anyhow::{Context, Result, anyhow, bail};
+use clap::Args;
+use eight_puzzle_solver::{
+    a_star::{SearchConfig, TieBreak},
+    actions::ActionSet,
+    algorithms::Algorithm,
+    board::{MAX_SIDE, MIN_SIDE},
+    cost::CostModel,
+    goal::{GoalContext, GoalPreset},
+    heuristic::Heuristic,
+    hint, rng,
+    validate::CellOrder,
+};
+use serde::Deserialize;
+use std::{env, fmt, fs, path::PathBuf, str::FromStr};
+
+/// Memory a search in the TUI may take unless configured otherwise, in
+/// megabytes.
+pub const DEFAULT_MAX_MEMORY_MB: u64 = 1024;
+
+/// Settings shared by the TUI and the subcommands.
+///
+/// They are resolved in layers, each one overriding the previous:
+///
+/// 1. built-in defaults
+/// 2. the config file (`--config`, `EPS_CONFIG`, or
+///    `$XDG_CONFIG_HOME/eight-puzzle-solver/config.toml`)
+/// 3. `EPS_*` environment variables
+/// 4. command-line flags
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub board_size: BoardSize,
+    pub goal: GoalPreset,
+    pub algorithm: Algorithm,
+    pub search: SearchConfig,
+    pub hint_distance: u8, // hints show every remaining move this close to the goal
+    pub ascii: Option<bool>, // `None`: only when the terminal shows no colors
+    pub theme: Theme,
+    pub animate: bool,         // slide the tiles between steps of a solution
+    pub race_pace_ms: u64,     // time between two moves of the solver in a race
+    pub max_memory_mb: u64,    // searches in the TUI fall back or stop past this, 0 for no cap
+    pub deterministic: bool,   // every seed not given is the same on every run
+    pub cell_order: CellOrder, // how imported boards are written
+    pub session_summary: bool, // recap the puzzles solved when the TUI quits
+    pub queue_searches: bool,  // solves wait for the background searches instead of joining them
+    pub attract_after: u64,    // seconds the editor sits unused before the demo starts, 0 for never
+}
+
+/// Command-line flags for the last configuration layer.
+#[derive(Args, Default)]
+pub struct ConfigArgs {
+    /// Config file to read instead of the default one
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    /// Board size, e.g. 3 for the 8-puzzle or 2x4 for 2 rows of 4
+    #[arg(long, global = true)]
+    pub board_size: Option<BoardSize>,
+    /// Solved board: `standard`, `blank-first`, or `spiral`
+    #[arg(long, global = true)]
+    pub goal: Option<GoalPreset>,
+    /// Search algorithm to use
+    #[arg(long, global = true)]
+    pub algorithm: Option<Algorithm>,
+    /// Heuristic guiding the search
+    #[arg(long, global = true)]
+    pub heuristic: Option<Heuristic>,
+    /// Heuristic weight, anything above 1 is not guaranteed optimal
+    #[arg(long, global = true)]
+    pub weight: Option<i64>,
+    /// Distance to the goal within which hints show the whole solution
+    #[arg(long, global = true)]
+    pub hint_distance: Option<u8>,
+    /// Boards kept at every depth by the beam search
+    #[arg(long, global = true)]
+    pub beam_width: Option<usize>,
+    /// Which of two boards with the same f(n) A* expands first
+    #[arg(long, global = true)]
+    pub tie_break: Option<TieBreak>,
+    /// What a move costs: `unit`, or `tile-value` for the number of the tile
+    #[arg(long, global = true)]
+    pub cost: Option<CostModel>,
+    /// Let A* also take macro moves of two or three moves of the blank: `single` or `macro`
+    #[arg(long, global = true)]
+    pub actions: Option<ActionSet>,
+    /// Let A* skip boards that are a rotated or mirrored twin of one it
+    /// expanded, where the goal looks the same turned that way
+    #[arg(long, global = true)]
+    pub symmetry: bool,
+    /// Draw with plain ASCII and no colors, for terminals without them
+    #[arg(long, global = true)]
+    pub ascii: bool,
+    /// Colors of the TUI
+    #[arg(long, global = true)]
+    pub theme: Option<Theme>,
+    /// Snap between the steps of a solution instead of sliding the tiles
+    #[arg(long, global = true)]
+    pub no_animation: bool,
+    /// Milliseconds between two moves of the solver when racing it
+    #[arg(long, value_name = "MS", global = true)]
+    pub race_pace: Option<u64>,
+    /// Megabytes a search in the TUI may take before it falls back to beam
+    /// search, 0 for no cap
+    #[arg(long, value_name = "MB", global = true)]
+    pub max_memory: Option<u64>,
+    /// Use a fixed seed for everything that would be random, so every run
+    /// gives the same results
+    #[arg(long, global = true)]
+    pub deterministic: bool,
+    /// How the cells of boards typed, pasted or read from files are ordered:
+    /// `rows`, `columns`, or `auto` for rows unless the lines are clearly columns
+    #[arg(long, global = true)]
+    pub cell_order: Option<CellOrder>,
+    /// Print a recap of the puzzles solved in the TUI when it quits
+    #[arg(long, global = true)]
+    pub session_summary: bool,
+    /// Start a solve only once the searches in the background are done,
+    /// instead of running it alongside them
+    #[arg(long, global = true)]
+    pub queue_searches: bool,
+    /// Seconds the editor may sit unused before a demo solves random boards
+    /// until a key is pressed, 0 for never
+    #[arg(long, value_name = "SECONDS", global = true)]
+    pub attract_after: Option<u64>,
+}
+
+#[derive(Default)]
+struct Layer {
+    board_size: Option<BoardSize>,
+    goal: Option<GoalPreset>,
+    algorithm: Option<Algorithm>,
+    heuristic: Option<Heuristic>,
+    weight: Option<i64>,
+    hint_distance: Option<u8>,
+    beam_width: Option<usize>,
+    tie_break: Option<TieBreak>,
+    cost: Option<CostModel>,
+    actions: Option<ActionSet>,
+    symmetry: Option<bool>,
+    ascii: Option<bool>,
+    theme: Option<Theme>,
+    animate: Option<bool>,
+    race_pace: Option<u64>,
+    max_memory: Option<u64>,
+    deterministic: Option<bool>,
+    cell_order: Option<CellOrder>,
+    session_summary: Option<bool>,
+    queue_searches: Option<bool>,
+    attract_after: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct FileLayer {
+    board_size: Option<FileBoardSize>,
+    goal: Option<String>,
+    algorithm: Option<String>,
+    heuristic: Option<String>,
+    weight: Option<i64>,
+    hint_distance: Option<u8>,
+    beam_width: Option<usize>,
+    tie_break: Option<String>,
+    cost: Option<String>,
+    actions: Option<String>,
+    symmetry: Option<bool>,
+    ascii: Option<bool>,
+    theme: Option<String>,
+    animate: Option<bool>,
+    race_pace: Option<u64>,
+    max_memory: Option<u64>,
+    deterministic: Option<bool>,
+    cell_order: Option<String>,
+    session_summary: Option<bool>,
+    queue_searches: Option<bool>,
+    attract_after: Option<u64>,
+}
+
+// `board-size = 3` and `board-size = "2x4"` are both accepted
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FileBoardSize {
+    Side(usize),
+    Shape(String),
+}
+
+/// Rows and columns of the puzzle, written `3` for a square or `2x4`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BoardSize {
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Default for BoardSize {
+    fn default() -> Self {
+        return BoardSize { rows: 3, cols: 3 };
+    }
+}
+
+impl FromStr for BoardSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_side = |side: &str| {
+            return match side.trim().parse::<usize>() {
+                Ok(n) if (MIN_SIDE..=MAX_SIDE).contains(&n) => Ok(n),
+                _ => Err(format!(
+                    "invalid board size {}: sides must be {}-{}",
+                    s, MIN_SIDE, MAX_SIDE
+                )),
+            };
+        };
+        return match s.split_once('x') {
+            Some((rows, cols)) => Ok(BoardSize {
+                rows: parse_side(rows)?,
+                cols: parse_side(cols)?,
+            }),
+            None => {
+                let side = parse_side(s)?;
+                Ok(BoardSize {
+                    rows: side,
+                    cols: side,
+                })
+            }
+        };
+    }
+}
+
+impl fmt::Display for BoardSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}x{}", self.rows, self.cols);
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        return Config {
+            board_size: BoardSize::default(),
+            goal: GoalPreset::default(),
+            algorithm: Algorithm::default(),
+            search: SearchConfig::default(),
+            hint_distance: hint::DEFAULT_FULL_HINT_DISTANCE,
+            ascii: None,
+            theme: Theme::default(),
+            animate: true,
+            race_pace_ms: race::DEFAULT_PACE_MS,
+            max_memory_mb: DEFAULT_MAX_MEMORY_MB,
+            deterministic: false,
+            cell_order: CellOrder::default(),
+            session_summary: false,
+            queue_searches: false,
+            attract_after: 0,
+        };
+    }
+}
+
+impl Config {
+    pub fn load(args: &ConfigArgs) -> Result<Config> {
+        let mut config = Config::default();
+        config.apply(file_layer(args.config.clone())?);
+        config.apply(env_layer()?);
+        config.apply(Layer {
+            board_size: args.board_size,
+            goal: args.goal,
+            algorithm: args.algorithm,
+            heuristic: args.heuristic,
+            weight: args.weight,
+            hint_distance: args.hint_distance,
+            beam_width: args.beam_width,
+            tie_break: args.tie_break,
+            cost: args.cost,
+            actions: args.actions,
+            symmetry: args.symmetry.then_some(true),
+            ascii: args.ascii.then_some(true),
+            theme: args.theme,
+            animate: args.no_animation.then_some(false),
+            race_pace: args.race_pace,
+            max_memory: args.max_memory,
+            deterministic: args.deterministic.then_some(true),
+            cell_order: args.cell_order,
+            session_summary: args.session_summary.then_some(true),
+            queue_searches: args.queue_searches.then_some(true),
+            attract_after: args.attract_after,
+        });
+        config.search.seed = config.fresh_seed();
+
+        check_weight(config.search.weight).map_err(|e| anyhow!(e))?;
+        if config.search.beam_width < 1 {
+            bail!("beam width must be at least 1");
+        }
+        return Ok(config);
+    }
+
+    fn apply(&mut self, layer: Layer) {
+        if let Some(board_size) = layer.board_size {
+            self.board_size = board_size;
+        }
+        if let Some(goal) = layer.goal {
+            self.goal = goal;
+        }
+        if let Some(algorithm) = layer.algorithm {
+            self.algorithm = algorithm;
+        }
+        if let Some(heuristic) = layer.heuristic {
+            self.search.heuristic = heuristic;
+        }
+        if let Some(weight) = layer.weight {
+            self.search.weight = weight;
+        }
+        if let Some(hint_distance) = layer.hint_distance {
+            self.hint_distance = hint_distance;
+        }
+        if let Some(beam_width) = layer.beam_width {
+            self.search.beam_width = beam_width;
+        }
+        if let Some(tie_break) = layer.tie_break {
+            self.search.tie_break = tie_break;
+        }
+        if let Some(cost) = layer.cost {
+            self.search.cost = cost;
+        }
+        if let Some(actions) = layer.actions {
+            self.search.actions = actions;
+        }
+        if let Some(symmetry) = layer.symmetry {
+            self.search.symmetry = symmetry;
+        }
+        if let Some(ascii) = layer.ascii {
+            self.ascii = Some(ascii);
+        }
+        if let Some(theme) = layer.theme {
+            self.theme = theme;
+        }
+        if let Some(animate) = layer.animate {
+            self.animate = animate;
+        }
+        if let Some(race_pace) = layer.race_pace {
+            self.race_pace_ms = race_pace;
+        }
+        if let Some(max_memory) = layer.max_memory {
+            self.max_memory_mb = max_memory;
+        }
+        if let Some(deterministic) = layer.deterministic {
+            self.deterministic = deterministic;
+        }
+        if let Some(cell_order) = layer.cell_order {
+            self.cell_order = cell_order;
+        }
+        if let Some(session_summary) = layer.session_summary {
+            self.session_summary = session_summary;
+        }
+        if let Some(queue_searches) = layer.queue_searches {
+            self.queue_searches = queue_searches;
+        }
+        if let Some(attract_after) = layer.attract_after {
+            self.attract_after = attract_after;
+        }
+    }
+
+    /// The goal for the configured size.
+    pub fn goal(&self) -> GoalContext {
+        return GoalContext::for_preset(self.goal, self.board_size.rows, self.board_size.cols);
+    }
+
+    /// A seed for whatever random choice was not given one.
+    pub fn fresh_seed(&self) -> u64 {
+        return rng::fresh_seed(self.deterministic);
+    }
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    return Some(base.join("eight-puzzle-solver").join("config.toml"));
+}
+
+fn file_layer(explicit: Option<PathBuf>) -> Result<Layer> {
+    // an explicitly requested file has to exist, the default one is optional
+    let path = match explicit.or_else(|| env::var_os("EPS_CONFIG").map(PathBuf::from)) {
+        Some(path) => path,
+        None => match default_config_path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Layer::default()),
+        },
+    };
+
+    let text = fs::read_to_string(&path)
+        .with_context(|| format!("cannot read config file {}", path.display()))?;
+    let file: FileLayer =
+        toml::from_str(&text).with_context(|| format!("invalid config file {}", path.display()))?;
+
+    let source = path.display().to_string();
+    return Ok(Layer {
+        board_size: match file.board_size {
+            Some(FileBoardSize::Side(side)) => parse_opt(Some(side.to_string()), &source)?,
+            Some(FileBoardSize::Shape(shape)) => parse_opt(Some(shape), &source)?,
+            None => None,
+        },
+        goal: parse_opt(file.goal, &source)?,
+        algorithm: parse_opt(file.algorithm, &source)?,
+        heuristic: parse_opt(file.heuristic, &source)?,
+        weight: file.weight,
+        hint_distance: file.hint_distance,
+        beam_width: file.beam_width,
+        tie_break: parse_opt(file.tie_break, &source)?,
+        cost: parse_opt(file.cost, &source)?,
+        actions: parse_opt(file.actions, &source)?,
+        symmetry: file.symmetry,
+        ascii: file.ascii,
+        theme: parse_opt(file.theme, &source)?,
+        animate: file.animate,
+        race_pace: file.race_pace,
+        max_memory: file.max_memory,
+        deterministic: file.deterministic,
+        cell_order: parse_opt(file.cell_order, &source)?,
+        session_summary: file.session_summary,
+        queue_searches: file.queue_searches,
+        attract_after: file.attract_after,
+    });
+}
+
+fn env_layer() -> Result<Layer> {
+    return Ok(Layer {
+        board_size: parse_opt(env::var("EPS_BOARD_SIZE").ok(), "EPS_BOARD_SIZE")?,
+        goal: parse_opt(env::var("EPS_GOAL").ok(), "EPS_GOAL")?,
+        algorithm: parse_opt(env::var("EPS_ALGORITHM").ok(), "EPS_ALGORITHM")?,
+        heuristic: parse_opt(env::var("EPS_HEURISTIC").ok(), "EPS_HEURISTIC")?,
+        weight: parse_opt(env::var("EPS_WEIGHT").ok(), "EPS_WEIGHT")?,
+        hint_distance: parse_opt(env::var("EPS_HINT_DISTANCE").ok(), "EPS_HINT_DISTANCE")?,
+        beam_width: parse_opt(env::var("EPS_BEAM_WIDTH").ok(), "EPS_BEAM_WIDTH")?,
+        tie_break: parse_opt(env::var("EPS_TIE_BREAK").ok(), "EPS_TIE_BREAK")?,
+        cost: parse_opt(env::var("EPS_COST").ok(), "EPS_COST")?,
+        actions: parse_opt(env::var("EPS_ACTIONS").ok(), "EPS_ACTIONS")?,
+        symmetry: parse_opt(env::var("EPS_SYMMETRY").ok(), "EPS_SYMMETRY")?,
+        ascii: parse_opt(env::var("EPS_ASCII").ok(), "EPS_ASCII")?,
+        theme: parse_opt(env::var("EPS_THEME").ok(), "EPS_THEME")?,
+        animate: parse_opt(env::var("EPS_ANIMATE").ok(), "EPS_ANIMATE")?,
+        race_pace: parse_opt(env::var("EPS_RACE_PACE").ok(), "EPS_RACE_PACE")?,
+        max_memory: parse_opt(env::var("EPS_MAX_MEMORY").ok(), "EPS_MAX_MEMORY")?,
+        deterministic: parse_opt(env::var("EPS_DETERMINISTIC").ok(), "EPS_DETERMINISTIC")?,
+        cell_order: parse_opt(env::var("EPS_CELL_ORDER").ok(), "EPS_CELL_ORDER")?,
+        session_summary: parse_opt(env::var("EPS_SESSION_SUMMARY").ok(), "EPS_SESSION_SUMMARY")?,
+        queue_searches: parse_opt(env::var("EPS_QUEUE_SEARCHES").ok(), "EPS_QUEUE_SEARCHES")?,
+        attract_after: parse_opt(env::var("EPS_ATTRACT_AFTER").ok(), "EPS_ATTRACT_AFTER")?,
+    });
+}
+
+/// Largest heuristic weight: far beyond it the search is greedy anyway, and
+/// weighted estimates must stay well clear of overflowing.
+pub const MAX_WEIGHT: i64 = 1000;
+
+/// Weights below 1 would count the estimate against the board; 0 already
+/// turns A* into a breadth-first search.
+pub fn check_weight(weight: i64) -> Result<(), String> {
+    if !(1..=MAX_WEIGHT).contains(&weight) {
+        return Err(format!(
+            "heuristic weight must be between 1 and {}",
+            MAX_WEIGHT
+        ));
+    }
+    return Ok(());
+}
+
+fn parse_opt<T>(value: Option<String>, source: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    return match value {
+        Some(v) => v
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|e| anyhow!("{}: {}", source, e)),
+        None => Ok(None),
+    };
+}
@@ -0,0 +1,67 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::board::Board;
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// What a move costs. A* finds the solution with the smallest total cost;
+/// with unit costs, that is the one with the fewest moves.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum CostModel {
+    #[default]
+    Unit, // every move costs 1
+    TileValue, // sliding a tile costs its number, the weighted 8-puzzle
+}
+
+impl CostModel {
+    pub const ALL: [CostModel; 2] = [CostModel::Unit, CostModel::TileValue];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            CostModel::Unit => "unit",
+            CostModel::TileValue => "tile-value",
+        };
+    }
+
+    /// Cost of sliding `tile`.
+    pub fn of_tile(&self, tile: i64) -> i64 {
+        return match self {
+            CostModel::Unit => 1,
+            CostModel::TileValue => tile,
+        };
+    }
+
+    /// Cost of the move from `board` to `next`, one move apart: the tile that
+    /// slid is the one in `next` where the blank was.
+    pub fn of_move(&self, board: &Board, next: &Board) -> i64 {
+        let tile = board
+            .find_empty_cell()
+            .and_then(|(r, c)| next.get(r, c))
+            .unwrap_or(1);
+        return self.of_tile(tile);
+    }
+
+    /// Total cost of the moves along `path`.
+    pub fn of_path(&self, path: &[Board]) -> i64 {
+        return path.windows(2).map(|w| self.of_move(&w[0], &w[1])).sum();
+    }
+}
+
+impl FromStr for CostModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return CostModel::ALL
+            .into_iter()
+            .find(|c| c.name() == s)
+            .ok_or_else(|| format!("unknown cost model: {}", s));
+    }
+}
+
+impl fmt::Display for CostModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
@@ -0,0 +1,71 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use std::{
+    backtrace::Backtrace,
+    panic::{self, PanicHookInfo},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+/// A panic on one of the worker threads, with what is needed to report it.
+pub struct Report {
+    pub thread: String,
+    pub message: String,
+    pub location: String,
+    pub backtrace: String,
+}
+
+impl Report {
+    fn new(info: &PanicHookInfo) -> Report {
+        let payload = info.payload();
+        let message = match (
+            payload.downcast_ref::<&str>(),
+            payload.downcast_ref::<String>(),
+        ) {
+            (Some(s), _) => s.to_string(),
+            (_, Some(s)) => s.clone(),
+            (None, None) => "(no message)".to_string(),
+        };
+        return Report {
+            thread: thread::current().name().unwrap_or("<unnamed>").to_string(),
+            message,
+            location: info
+                .location()
+                .map_or_else(|| "unknown".to_string(), |l| l.to_string()),
+            backtrace: Backtrace::force_capture().to_string(),
+        };
+    }
+
+    /// The whole report as plain text, for the clipboard.
+    pub fn to_text(&self) -> String {
+        return format!(
+            "thread '{}' panicked at {}:\n{}\n\nstack backtrace:\n{}",
+            self.thread, self.location, self.message, self.backtrace
+        );
+    }
+}
+
+/// Sends the panics of the worker threads to the returned channel instead of
+/// printing them over the TUI. The main thread keeps the default hook, since
+/// the TUI cannot go on without it.
+pub fn install() -> Receiver<Report> {
+    let (tx, rx) = mpsc::channel();
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some("main") {
+            default_hook(info);
+            return;
+        }
+        let report = Report::new(info);
+        tracing::error!(
+            thread = %report.thread,
+            location = %report.location,
+            "panic: {}",
+            report.message
+        );
+        // the TUI may already be gone
+        let _ = tx.send(report);
+    }));
+    return rx;
+}
@@ -0,0 +1,109 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{Progress, Solution},
+    board::Board,
+};
+
+use std::collections::HashSet;
+
+/// The part of the search tree that was actually explored, recorded from the
+/// progress reports of a search.
+#[derive(Clone, Debug, Default)]
+pub struct SearchGraph {
+    nodes: Vec<(Board, i64, i64)>, // board, g, h
+    edges: Vec<(Board, Board)>,    // parent -> child
+}
+
+impl SearchGraph {
+    pub fn new() -> SearchGraph {
+        return SearchGraph::default();
+    }
+
+    /// Records an expanded board; meant to be called from a search observer.
+    pub fn record(&mut self, progress: &Progress) {
+        self.nodes.push((progress.board, progress.g, progress.h));
+        if let Some(parent) = progress.parent {
+            self.edges.push((parent, progress.board));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        return self.nodes.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.nodes.is_empty();
+    }
+
+    /// GraphViz source of the explored tree, with the solution path (if any)
+    /// drawn in bold red.
+    pub fn to_dot(&self, solution: Option<&Solution>) -> String {
+        let on_path: HashSet<Board> = solution
+            .map(|s| s.path.iter().copied().collect())
+            .unwrap_or_default();
+        let path_edges: HashSet<(Board, Board)> = solution
+            .map(|s| s.path.windows(2).map(|w| (w[0], w[1])).collect())
+            .unwrap_or_default();
+
+        let mut out = String::from("digraph search {\n");
+        out.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+        for (board, g, h) in &self.nodes {
+            let label = format!(
+                "{}\\ng={} h={}",
+                board.to_string().replace('\n', "\\n"),
+                g,
+                h
+            );
+            let style = if on_path.contains(board) {
+                ", color=red, penwidth=2"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\"{}];\n",
+                board.code(),
+                label,
+                style
+            ));
+        }
+        // the goal is found when it is popped, so it never gets expanded
+        let recorded: HashSet<Board> = self.nodes.iter().map(|(b, _, _)| *b).collect();
+        if let Some(s) = solution
+            && let [.., parent, goal] = s.path.as_slice()
+            && !recorded.contains(goal)
+        {
+            let label = format!(
+                "{}\\ng={} h=0",
+                goal.to_string().replace('\n', "\\n"),
+                s.moves()
+            );
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\", color=red, penwidth=2];\n",
+                goal.code(),
+                label
+            ));
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [color=red, penwidth=2];\n",
+                parent.code(),
+                goal.code()
+            ));
+        }
+        for (from, to) in &self.edges {
+            let style = if path_edges.contains(&(*from, *to)) {
+                " [color=red, penwidth=2]"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\"{};\n",
+                from.code(),
+                to.code(),
+                style
+            ));
+        }
+        out.push_str("}\n");
+        return out;
+    }
+}
@@ -0,0 +1,34 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{validate::Rejection, verify::VerifyError};
+
+use std::fmt;
+
+/// Why the solver could not work on a board, instead of a panic in the
+/// thread running the search.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SolverError {
+    ShapeMismatch {
+        board: (usize, usize), // rows and columns
+        goal: (usize, usize),
+    },
+    InvalidBoard(Rejection),      // tiles missing or repeated, no blank, ...
+    InvalidSolution(VerifyError), // a search returned moves that do not check out
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            SolverError::ShapeMismatch { board, goal } => write!(
+                f,
+                "the board is {}x{} but the goal is {}x{}",
+                board.0, board.1, goal.0, goal.1
+            ),
+            SolverError::InvalidBoard(rejection) => write!(f, "invalid board: {}", rejection),
+            SolverError::InvalidSolution(e) => write!(f, "invalid solution: {}", e),
+        };
+    }
+}
+
+impl std::error::Error for SolverError {}
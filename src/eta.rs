@@ -0,0 +1,81 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use eight_puzzle_solver::a_star::Progress;
+use std::time::{Duration, Instant};
+
+// Share of the previous estimate kept at each update, so the ETA does not
+// jump around with every report.
+const SMOOTHING: f64 = 0.9;
+
+// No estimate is shown before the search has run this long.
+const SETTLE: Duration = Duration::from_secs(1);
+
+/// A rough guess of how far a search is and how long it still needs, from
+/// how much closer to the goal (by the heuristic) it has got since the start.
+pub struct Estimate {
+    fraction: f64,      // of the way from the start to the goal, never goes back
+    total: Option<f64>, // expected running time in seconds, smoothed
+    running: Duration,  // time searched, without the pauses
+    last: Option<Instant>,
+}
+
+impl Estimate {
+    pub fn new() -> Estimate {
+        return Estimate {
+            fraction: 0.0,
+            total: None,
+            running: Duration::ZERO,
+            last: None,
+        };
+    }
+
+    /// Takes in the latest progress; called on every tick of the Searching
+    /// screen.
+    pub fn update(&mut self, progress: Option<&Progress>, paused: bool) {
+        let now = Instant::now();
+        if let Some(last) = self.last
+            && !paused
+        {
+            self.running += now - last;
+        }
+        self.last = Some(now);
+        let Some(progress) = progress else {
+            return;
+        };
+        let raw = match progress.initial_h {
+            h if h <= 0 => 1.0,
+            h => (h - progress.best_h).max(0) as f64 / h as f64,
+        };
+        self.fraction = self.fraction.max(raw);
+        if self.fraction > 0.0 && self.running >= SETTLE {
+            let total = self.running.as_secs_f64() / self.fraction;
+            self.total = Some(match self.total {
+                Some(previous) => SMOOTHING * previous + (1.0 - SMOOTHING) * total,
+                None => total,
+            });
+        }
+    }
+
+    pub fn percent(&self) -> u16 {
+        return (self.fraction * 100.0).round() as u16;
+    }
+
+    /// Time the search probably still needs, `None` until there is enough to
+    /// go on.
+    pub fn remaining(&self) -> Option<Duration> {
+        let total = self.total?;
+        return Some(Duration::from_secs_f64(
+            (total - self.running.as_secs_f64()).max(0.0),
+        ));
+    }
+}
+
+/// A remaining time as "about 12s" or "about 3m 05s".
+pub fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    return match seconds {
+        s if s < 60 => format!("about {}s", s.max(1)),
+        s => format!("about {}m {:02}s", s / 60, s % 60),
+    };
+}
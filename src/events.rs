@@ -0,0 +1,112 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::input::Input;
+use anyhow::{Result, anyhow};
+use crossterm::event::{Event, KeyEvent};
+use std::{
+    cell::Cell,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+// How long the input thread waits for the terminal before checking whether
+// it should stop.
+const INPUT_POLL: Duration = Duration::from_millis(250);
+
+/// What the main loop reacts to.
+pub enum AppEvent {
+    Key(KeyEvent),
+    Resize(u16, u16), // the new width and height of the terminal
+    Tick,
+    Error(anyhow::Error), // the terminal could not be read
+}
+
+/// Terminal events and ticks, each from its own thread, so keys queue up
+/// while a frame is drawn instead of waiting for the next poll.
+pub struct Events {
+    rx: Receiver<AppEvent>,
+    tick_rate: Cell<Duration>,
+    tx_tick_rate: Sender<Duration>, // the tick thread stops once this is dropped
+    stop: Arc<AtomicBool>,
+}
+
+impl Events {
+    pub fn start(mut input: Input, tick_rate: Duration) -> Events {
+        let (tx, rx) = mpsc::channel();
+        let (tx_tick_rate, rx_tick_rate) = mpsc::channel();
+        let events = Events {
+            rx,
+            tick_rate: Cell::new(tick_rate),
+            tx_tick_rate,
+            stop: Arc::new(AtomicBool::new(false)),
+        };
+
+        let (input_tx, stop) = (tx.clone(), Arc::clone(&events.stop));
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let event = match input.next(INPUT_POLL) {
+                    Ok(Some(Event::Key(key))) => AppEvent::Key(key),
+                    Ok(Some(Event::Resize(width, height))) => AppEvent::Resize(width, height),
+                    Ok(_) => continue,
+                    Err(e) => AppEvent::Error(e),
+                };
+                if input_tx.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+
+        thread::spawn(move || tick(tx, rx_tick_rate, tick_rate));
+        return events;
+    }
+
+    /// Changes how often ticks come, e.g. faster while a tile slides.
+    pub fn set_tick_rate(&self, tick_rate: Duration) {
+        if self.tick_rate.replace(tick_rate) != tick_rate {
+            // the tick thread outlives the events only while quitting
+            let _ = self.tx_tick_rate.send(tick_rate);
+        }
+    }
+
+    /// Waits for the next event, then takes every other one already queued,
+    /// so a burst of keys is handled before the next frame is drawn.
+    pub fn next_batch(&self) -> Result<Vec<AppEvent>> {
+        let first = self
+            .rx
+            .recv()
+            .map_err(|_| anyhow!("the event threads stopped"))?;
+        let mut events = vec![first];
+        events.extend(self.rx.try_iter());
+        return Ok(events);
+    }
+}
+
+impl Drop for Events {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// Sleeps until the next tick is due, or until the rate changes, so an idle
+// TUI does not wake up more often than it ticks.
+fn tick(tx: Sender<AppEvent>, rx_tick_rate: Receiver<Duration>, mut tick_rate: Duration) {
+    let mut last_tick = Instant::now();
+    loop {
+        match rx_tick_rate.recv_timeout(tick_rate.saturating_sub(last_tick.elapsed())) {
+            Ok(rate) => tick_rate = rate,
+            Err(RecvTimeoutError::Timeout) => {
+                last_tick = Instant::now();
+                if tx.send(AppEvent::Tick).is_err() {
+                    return;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
@@ -0,0 +1,176 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{a_star::Solution, board::Board, goal::GoalContext, notation::Move};
+
+/// A run of consecutive moves that serves one human-meaningful purpose.
+/// Covers the moves `start..end`, i.e. the boards `start..=end` of the path.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Phase {
+    pub label: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Splits a solution path into the phases a human solver would recognize:
+/// positioning the first tile, building the top row, and cycling the
+/// remaining region (2x3 on the standard board) into place.
+///
+/// Optimal solutions often disturb tiles that are already placed, so a
+/// milestone only counts from the point where it stays achieved until the end.
+pub fn explain(path: &[Board], goal: &GoalContext) -> Vec<Phase> {
+    if path.len() < 2 {
+        return Vec::new();
+    }
+
+    let first_tile = first_tile(goal);
+    let goal_board = goal.goal();
+    let top_row: Vec<i64> = (0..goal_board.cols())
+        .filter_map(|c| goal_board.get(0, c))
+        .collect();
+
+    let tile_done = settled_from(path, |b| in_place(b, goal, &[first_tile]));
+    let row_done = settled_from(path, |b| in_place(b, goal, &top_row)).max(tile_done);
+    let last = path.len() - 1;
+
+    let mut phases: Vec<Phase> = Vec::new();
+    let mut push = |label: &str, start: usize, end: usize| {
+        if start < end {
+            phases.push(Phase {
+                label: label.to_string(),
+                start,
+                end,
+            });
+        }
+    };
+
+    push(&format!("position tile {}", first_tile), 0, tile_done);
+    push("build top row", tile_done, row_done);
+
+    // the rest is a cycle of the bottom region as long as the blank stays there
+    let blank_stays_below = path[row_done..]
+        .iter()
+        .all(|b| b.find_empty_cell().is_some_and(|(r, _)| r > 0));
+    if blank_stays_below {
+        let label = format!(
+            "cycle bottom {}x{}",
+            goal_board.rows() - 1,
+            goal_board.cols()
+        );
+        push(&label, row_done, last);
+    } else {
+        push("finish the puzzle", row_done, last);
+    }
+
+    return phases;
+}
+
+/// The phases of `solution`: the stages of a solver that works in stages,
+/// otherwise the ones [`explain`] recognizes on the way to its last board.
+pub fn phases(solution: &Solution) -> Vec<Phase> {
+    if !solution.phases.is_empty() {
+        return solution.phases.clone();
+    }
+    return match solution.path.last() {
+        Some(goal) => explain(&solution.path, &GoalContext::new(*goal)),
+        None => Vec::new(),
+    };
+}
+
+/// The phase containing the move that leads to board `step`; board 0 belongs
+/// to the first phase.
+pub fn phase_at(phases: &[Phase], step: usize) -> Option<&Phase> {
+    let mv = step.saturating_sub(1);
+    return phases.iter().find(|p| p.start <= mv && mv < p.end);
+}
+
+/// One sentence per move describing which tile slides where and what it
+/// achieves, e.g. "Slide 5 left into place".
+pub fn commentary(path: &[Board], goal: &GoalContext) -> Vec<String> {
+    return path
+        .windows(2)
+        .map(|pair| describe_move(&pair[0], &pair[1], goal))
+        .collect();
+}
+
+/// One short label per move, e.g. "Slide 5 left".
+pub fn slides(path: &[Board]) -> Vec<String> {
+    return path
+        .windows(2)
+        .map(|pair| match slide(&pair[0], &pair[1]) {
+            Some(s) => format!("Slide {} {}", s.tile, s.direction),
+            None => "Invalid move".to_string(),
+        })
+        .collect();
+}
+
+struct Slide {
+    tile: i64,
+    direction: &'static str,
+    from: (usize, usize),
+    to: (usize, usize),
+}
+
+fn slide(before: &Board, after: &Board) -> Option<Slide> {
+    let (from, to) = (after.find_empty_cell()?, before.find_empty_cell()?);
+    // the tile slides the opposite way to the blank
+    let direction = match Move::between(to, from)? {
+        Move::Left => "right",
+        Move::Up => "down",
+        Move::Right => "left",
+        Move::Down => "up",
+    };
+    return Some(Slide {
+        tile: before.get(from.0, from.1)?,
+        direction,
+        from,
+        to,
+    });
+}
+
+fn describe_move(before: &Board, after: &Board, goal: &GoalContext) -> String {
+    let Some(Slide {
+        tile,
+        direction,
+        from,
+        to,
+    }) = slide(before, after)
+    else {
+        return "Invalid move".to_string();
+    };
+
+    let target = goal.position_of(tile);
+    let purpose = if to == target {
+        " into place"
+    } else if from == target {
+        " out of place to make room"
+    } else if to.0 == target.0 && from.0 != target.0 {
+        " into its row"
+    } else if to.1 == target.1 && from.1 != target.1 {
+        " into its column"
+    } else {
+        ""
+    };
+    return format!("Slide {} {}{}", tile, direction, purpose);
+}
+
+fn first_tile(goal: &GoalContext) -> i64 {
+    let goal = goal.goal();
+    return (0..goal.cols()).find_map(|c| goal.get(0, c)).unwrap_or(1);
+}
+
+fn in_place(board: &Board, goal: &GoalContext, tiles: &[i64]) -> bool {
+    return tiles.iter().all(|t| {
+        let (r, c) = goal.position_of(*t);
+        board.get(r, c) == Some(*t)
+    });
+}
+
+// Earliest index from which `done` holds for every remaining board.
+fn settled_from(path: &[Board], done: impl Fn(&Board) -> bool) -> usize {
+    let mut idx = path.len();
+    while idx > 0 && done(&path[idx - 1]) {
+        idx -= 1;
+    }
+    return idx;
+}
@@ -0,0 +1,406 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::Solution, board::Board, compare::MatrixEntry, explain, goal::GoalContext, notation,
+    tournament::Standing,
+};
+
+use serde_json::json;
+use std::time::Duration;
+
+// Time each board of an asciicast stays on screen; the solved board stays a
+// little longer before the recording ends.
+const CAST_FRAME: Duration = Duration::from_millis(500);
+const CAST_HOLD: Duration = Duration::from_secs(2);
+
+/// Plain-text walkthrough of a solution: every board grouped by phase with a
+/// comment on the move leading to it, then a short summary.
+pub fn to_text(solution: &Solution) -> String {
+    let phases = explain::phases(solution);
+    let comments = match solution.path.last() {
+        Some(goal) => explain::commentary(&solution.path, &GoalContext::new(*goal)),
+        None => Vec::new(),
+    };
+
+    let mut out = String::new();
+    for (i, step) in solution.path.iter().enumerate() {
+        if let Some(phase) = phases.iter().find(|p| p.start == i) {
+            out.push_str(&format!("== {} ==\n\n", phase.label));
+        }
+        out.push_str(&format!("Step {} / {}", i + 1, solution.path.len()));
+        if let Some(comment) = i.checked_sub(1).and_then(|m| comments.get(m)) {
+            out.push_str(&format!(": {}", comment));
+        }
+        out.push('\n');
+        out.push_str(&format!("{}\n\n", step));
+    }
+    out.push_str(&format!("Moves: {}\n", solution.moves()));
+    out.push_str(&format!(
+        "LURD: {}\n",
+        notation::to_letters(&solution.directions)
+    ));
+    if solution.shortened > 0 {
+        out.push_str(&format!(
+            "Shortened by {} moves after the search\n",
+            solution.shortened
+        ));
+    }
+    if !solution.guaranteed_optimal {
+        out.push_str("Not guaranteed optimal\n");
+    }
+    return out;
+}
+
+/// Markdown walkthrough of a solution, for write-ups and issue reports: a
+/// heading per phase and per step with the comment on its move, the board as
+/// a code block, and how far the goal still is.
+pub fn to_markdown(solution: &Solution) -> String {
+    let phases = explain::phases(solution);
+    let comments = match solution.path.last() {
+        Some(goal) => explain::commentary(&solution.path, &GoalContext::new(*goal)),
+        None => Vec::new(),
+    };
+
+    let mut out = String::from("# Solution\n\n");
+    if let Some(start) = solution.path.first() {
+        out.push_str(&format!("- Start: `{}`\n", start.code()));
+    }
+    out.push_str(&format!("- Moves: {}\n", solution.moves()));
+    out.push_str(&format!(
+        "- LURD: `{}`\n",
+        notation::to_letters(&solution.directions)
+    ));
+    if solution.shortened > 0 {
+        out.push_str(&format!(
+            "- Shortened by {} moves after the search\n",
+            solution.shortened
+        ));
+    }
+    if !solution.guaranteed_optimal {
+        out.push_str("- Not guaranteed optimal\n");
+    }
+
+    for (i, board) in solution.path.iter().enumerate() {
+        if let Some(phase) = phases.iter().find(|p| p.start == i) {
+            out.push_str(&format!("\n## {}\n", phase.label));
+        }
+        out.push_str(&format!("\n### Step {} / {}", i + 1, solution.path.len()));
+        if let Some(comment) = i.checked_sub(1).and_then(|m| comments.get(m)) {
+            out.push_str(&format!(": {}", comment));
+        }
+        out.push_str(&format!("\n\n```text\n{}\n```\n\n", board));
+        let left = solution.moves() - i;
+        out.push_str(&match solution.steps.get(i) {
+            Some(step) => format!("Moves left: {} (heuristic estimate: {})\n", left, step.h),
+            None => format!("Moves left: {}\n", left),
+        });
+    }
+    return out;
+}
+
+/// The solution as a JSON document for other programs: the start and goal
+/// boards, the moves, and every step with the board it leads to and the
+/// comment on its move.
+pub fn to_json(solution: &Solution) -> String {
+    let comments = match solution.path.last() {
+        Some(goal) => explain::commentary(&solution.path, &GoalContext::new(*goal)),
+        None => Vec::new(),
+    };
+    let steps: Vec<_> = solution
+        .directions
+        .iter()
+        .zip(solution.path.iter().skip(1))
+        .zip(&comments)
+        .map(|((mv, board), comment)| {
+            return json!({
+                "move": mv.letter().to_string(),
+                "board": board.code(),
+                "comment": comment,
+            });
+        })
+        .collect();
+    let document = json!({
+        "start": solution.path.first().map(|b| b.code()),
+        "goal": solution.path.last().map(|b| b.code()),
+        "moves": solution.moves(),
+        "lurd": notation::to_letters(&solution.directions),
+        "guaranteed-optimal": solution.guaranteed_optimal,
+        "shortened": solution.shortened,
+        "steps": steps,
+    });
+    return format!("{:#}\n", document);
+}
+
+/// The solution played back as an asciicast v2 recording, one frame per
+/// board with the tile that just slid highlighted, for `asciinema play` or
+/// the asciinema player on a web page.
+pub fn to_asciicast(solution: &Solution) -> String {
+    let slides = explain::slides(&solution.path);
+    let captions: Vec<String> = (0..solution.path.len())
+        .map(|i| match i.checked_sub(1).and_then(|m| slides.get(m)) {
+            Some(slide) => format!("Step {} / {}: {}", i + 1, solution.path.len(), slide),
+            None => format!("Step {} / {}", i + 1, solution.path.len()),
+        })
+        .collect();
+    let frames: Vec<Vec<String>> = solution
+        .path
+        .iter()
+        .enumerate()
+        .map(|(i, board)| {
+            let previous = i.checked_sub(1).map(|p| &solution.path[p]);
+            let mut lines = vec![captions[i].clone(), String::new()];
+            lines.extend(cast_grid(board, previous));
+            return lines;
+        })
+        .collect();
+
+    let grid_width = frames.first().map_or(0, |lines| lines[2].chars().count());
+    let caption_width = captions.iter().map(|c| c.chars().count()).max();
+    let title = format!(
+        "{} moves: {}",
+        solution.moves(),
+        notation::to_letters(&solution.directions)
+    );
+    // written by hand to keep the version first, as the format asks
+    let header = format!(
+        "{{\"version\": 2, \"width\": {}, \"height\": {}, \"title\": {}}}",
+        grid_width.max(caption_width.unwrap_or(0)),
+        frames.first().map_or(0, |lines| lines.len()),
+        json!(title)
+    );
+    let mut out = format!("{}\n", header);
+    let mut time = Duration::ZERO;
+    for lines in &frames {
+        // clear the screen, then draw from the top left
+        let data = format!("\x1b[H\x1b[2J{}", lines.join("\r\n"));
+        out.push_str(&format!("{}\n", json!([time.as_secs_f64(), "o", data])));
+        time += CAST_FRAME;
+    }
+    // an empty event, so the last board stays up before the recording ends
+    time += CAST_HOLD - CAST_FRAME;
+    out.push_str(&format!("{}\n", json!([time.as_secs_f64(), "o", ""])));
+    return out;
+}
+
+// `board` drawn with box-drawing lines, the tile that slid from `previous`
+// in reverse video.
+fn cast_grid(board: &Board, previous: Option<&Board>) -> Vec<String> {
+    let digits = board.max_tile().to_string().len();
+    let width = digits + 2;
+    let slid = previous.and_then(|p| p.find_empty_cell());
+    let rule = |left: &str, middle: &str, right: &str| {
+        let cells = vec!["─".repeat(width); board.cols()];
+        return format!("{}{}{}", left, cells.join(middle), right);
+    };
+    let mut lines = vec![rule("┌", "┬", "┐")];
+    for r in 0..board.rows() {
+        let cells: Vec<String> = (0..board.cols())
+            .map(|c| {
+                let text = match board.get(r, c) {
+                    Some(tile) => format!(" {:>digits$} ", tile),
+                    None => " ".repeat(width),
+                };
+                return match slid == Some((r, c)) {
+                    true => format!("\x1b[7m{}\x1b[0m", text),
+                    false => text,
+                };
+            })
+            .collect();
+        lines.push(format!("│{}│", cells.join("│")));
+        if r + 1 < board.rows() {
+            lines.push(rule("├", "┼", "┤"));
+        }
+    }
+    lines.push(rule("└", "┴", "┘"));
+    return lines;
+}
+
+const MATRIX_HEADER: [&str; 6] = [
+    "algorithm",
+    "heuristic",
+    "solved",
+    "avg nodes",
+    "avg time (ms)",
+    "avg length",
+];
+
+fn matrix_cells(entry: &MatrixEntry) -> [String; 6] {
+    return [
+        entry.algorithm.name().to_string(),
+        heuristic_name(entry).to_string(),
+        format!("{}/{}", entry.solved, entry.solved + entry.unsolved),
+        format!("{:.1}", entry.avg_expanded()),
+        format!("{:.2}", entry.avg_millis()),
+        format!("{:.2}", entry.avg_moves()),
+    ];
+}
+
+// uninformed algorithms are listed without a heuristic
+fn heuristic_name(entry: &MatrixEntry) -> &'static str {
+    return entry.heuristic.map_or("-", |h| h.name());
+}
+
+/// The comparison matrix as an aligned plain-text table.
+pub fn matrix_table(entries: &[MatrixEntry]) -> String {
+    let rows: Vec<[String; 6]> = entries.iter().map(matrix_cells).collect();
+    return aligned_table(MATRIX_HEADER, &rows);
+}
+
+// left-aligned columns two spaces apart, with a rule under the header
+fn aligned_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let mut widths = header.map(|h| h.len());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let format_row = |cells: &[String]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell))
+            .collect();
+        return padded.join("  ").trim_end().to_string();
+    };
+    let mut out = format_row(&header.map(String::from));
+    out.push('\n');
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    out.push_str(&rule.join("  "));
+    out.push('\n');
+    for row in rows {
+        out.push_str(&format_row(row));
+        out.push('\n');
+    }
+    return out;
+}
+
+/// The comparison matrix as CSV, one line per combination.
+pub fn matrix_csv(entries: &[MatrixEntry]) -> String {
+    let mut out =
+        String::from("algorithm,heuristic,solved,unsolved,avg_nodes,avg_time_ms,avg_length\n");
+    for entry in entries {
+        out.push_str(&format!(
+            "{},{},{},{},{:.1},{:.3},{:.2}\n",
+            entry.algorithm.name(),
+            heuristic_name(entry),
+            entry.solved,
+            entry.unsolved,
+            entry.avg_expanded(),
+            entry.avg_millis(),
+            entry.avg_moves()
+        ));
+    }
+    return out;
+}
+
+const TOURNAMENT_HEADER: [&str; 9] = [
+    "rank",
+    "entrant",
+    "points",
+    "solved",
+    "optimal",
+    "fastest",
+    "avg nodes",
+    "avg time (ms)",
+    "avg length",
+];
+
+fn tournament_cells(rank: usize, standing: &Standing) -> [String; 9] {
+    return [
+        rank.to_string(),
+        standing.entrant.to_string(),
+        standing.points().to_string(),
+        format!(
+            "{}/{}",
+            standing.solved,
+            standing.solved + standing.unsolved
+        ),
+        standing.optimal.to_string(),
+        standing.fastest.to_string(),
+        format!("{:.1}", standing.avg_expanded()),
+        format!("{:.2}", standing.avg_millis()),
+        format!("{:.2}", standing.avg_moves()),
+    ];
+}
+
+/// The tournament standings as an aligned plain-text table, best first.
+pub fn tournament_table(standings: &[Standing]) -> String {
+    let rows: Vec<[String; 9]> = standings
+        .iter()
+        .enumerate()
+        .map(|(i, standing)| tournament_cells(i + 1, standing))
+        .collect();
+    return aligned_table(TOURNAMENT_HEADER, &rows);
+}
+
+/// The tournament standings as CSV, one line per entrant, best first.
+pub fn tournament_csv(standings: &[Standing]) -> String {
+    let mut out = String::from(
+        "rank,algorithm,heuristic,points,solved,unsolved,optimal,fastest,avg_nodes,avg_time_ms,avg_length\n",
+    );
+    for (i, standing) in standings.iter().enumerate() {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{:.1},{:.3},{:.2}\n",
+            i + 1,
+            standing.entrant.algorithm.name(),
+            standing.entrant.heuristic.map_or("-", |h| h.name()),
+            standing.points(),
+            standing.solved,
+            standing.unsolved,
+            standing.optimal,
+            standing.fastest,
+            standing.avg_expanded(),
+            standing.avg_millis(),
+            standing.avg_moves()
+        ));
+    }
+    return out;
+}
+
+/// A standalone HTML page with the comparison matrix, for `puzzles` boards.
+pub fn matrix_html(entries: &[MatrixEntry], puzzles: usize) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Solver comparison</title>\n",
+    );
+    out.push_str(
+        "<style>table { border-collapse: collapse; } th, td { border: 1px solid #999; padding: 4px 8px; }</style>\n",
+    );
+    out.push_str("</head>\n<body>\n<h1>Solver comparison</h1>\n");
+    out.push_str(&format!("<p>{} puzzles</p>\n<table>\n<tr>", puzzles));
+    for header in MATRIX_HEADER {
+        out.push_str(&format!("<th>{}</th>", header));
+    }
+    out.push_str("</tr>\n");
+    for entry in entries {
+        out.push_str("<tr>");
+        for cell in matrix_cells(entry) {
+            out.push_str(&format!("<td>{}</td>", cell));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n</body>\n</html>\n");
+    return out;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{a_star, validate};
+
+    #[test]
+    fn json_exports_comment_on_every_move() {
+        let start = validate::parse("123456078").unwrap();
+        let solution = a_star::search(start).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&to_json(&solution)).unwrap();
+        assert_eq!(document["lurd"], "RR");
+        let steps = document["steps"].as_array().unwrap();
+        assert_eq!(steps.len(), 2);
+        let comments = explain::commentary(&solution.path, &GoalContext::standard());
+        for (step, comment) in steps.iter().zip(&comments) {
+            assert_eq!(step["comment"], comment.as_str());
+        }
+        assert_eq!(steps[1]["board"], "123456780");
+    }
+}
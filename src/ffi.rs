@@ -0,0 +1,73 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+// C interface, built with the `ffi` feature. The declarations are in
+// include/eight_puzzle_solver.h, generated with cbindgen.
+
+use crate::{a_star, goal::GoalContext, notation, validate};
+use std::{
+    ffi::{CString, c_char},
+    ptr, slice,
+};
+
+/// Cells of a board passed to [`eps_solve`].
+pub const EPS_CELLS: usize = 9;
+
+/// The board is not a valid 8-puzzle, or the goal cannot be reached from it.
+pub const EPS_INVALID_BOARD: i32 = -1;
+/// A pointer argument is null.
+pub const EPS_NULL_POINTER: i32 = -2;
+/// The search ended without a solution.
+pub const EPS_NO_SOLUTION: i32 = -3;
+
+/// Solves the 8-puzzle `board`, 9 bytes row by row with 0 for the empty cell,
+/// with A* and the default heuristic.
+///
+/// Returns the number of moves and stores the moves of the blank in LURD
+/// notation as a NUL-terminated string in `*moves`, which must be released
+/// with [`eps_free_moves`]. On failure, returns one of the negative `EPS_`
+/// codes and leaves `*moves` null.
+///
+/// # Safety
+///
+/// `board` must point to 9 readable bytes and `moves` to a writable pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn eps_solve(board: *const u8, moves: *mut *mut c_char) -> i32 {
+    if board.is_null() || moves.is_null() {
+        return EPS_NULL_POINTER;
+    }
+    // SAFETY: both pointers are non-null and valid as promised by the caller
+    let (cells, moves) = unsafe { (slice::from_raw_parts(board, EPS_CELLS), &mut *moves) };
+    *moves = ptr::null_mut();
+
+    let goal = GoalContext::standard();
+    let cells: Vec<Option<i64>> = cells
+        .iter()
+        .map(|&tile| (tile != 0).then_some(tile as i64))
+        .collect();
+    let Ok(board) = validate::check_cells(&cells, &goal) else {
+        return EPS_INVALID_BOARD;
+    };
+    let Some(solution) = a_star::search(board) else {
+        return EPS_NO_SOLUTION;
+    };
+    let Ok(lurd) = CString::new(notation::to_lurd(&solution.path)) else {
+        return EPS_NO_SOLUTION; // LURD letters never contain NUL
+    };
+    *moves = lurd.into_raw();
+    return solution.moves() as i32;
+}
+
+/// Releases a move string returned by [`eps_solve`]. Null is ignored.
+///
+/// # Safety
+///
+/// `moves` must be null or a string from [`eps_solve`] not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn eps_free_moves(moves: *mut c_char) {
+    if moves.is_null() {
+        return;
+    }
+    // SAFETY: the string was made by `CString::into_raw` in `eps_solve`
+    drop(unsafe { CString::from_raw(moves) });
+}
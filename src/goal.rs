@@ -0,0 +1,278 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star,
+    board::{Board, MAX_SIDE},
+    parity,
+};
+
+use std::{fmt, str::FromStr};
+
+/// Where the tiles go in the solved board, after the usual conventions.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum GoalPreset {
+    #[default]
+    Standard, // 1, 2, 3, ... row by row, the blank in the bottom-right corner
+    BlankFirst, // the blank in the top-left corner, then 1, 2, 3, ... row by row
+    Spiral,     // 1, 2, 3, ... clockwise around the edge and inwards, the blank last
+}
+
+impl GoalPreset {
+    pub const ALL: [GoalPreset; 3] = [
+        GoalPreset::Standard,
+        GoalPreset::BlankFirst,
+        GoalPreset::Spiral,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            GoalPreset::Standard => "standard",
+            GoalPreset::BlankFirst => "blank-first",
+            GoalPreset::Spiral => "spiral",
+        };
+    }
+
+    /// The solved `rows` x `cols` board.
+    pub fn board(&self, rows: usize, cols: usize) -> Board {
+        let cells = rows * cols;
+        let order: Vec<usize> = match self {
+            GoalPreset::Standard => (0..cells).collect(),
+            GoalPreset::BlankFirst => (1..cells).chain([0]).collect(),
+            GoalPreset::Spiral => spiral(rows, cols),
+        };
+        // the n-th cell of `order` holds tile n + 1, the last one the blank
+        let mut board = vec![None; cells];
+        for (n, cell) in order.iter().take(cells - 1).enumerate() {
+            board[*cell] = Some(n as i64 + 1);
+        }
+        return Board::from_cells(rows, cols, &board);
+    }
+}
+
+// The cells of a `rows` x `cols` grid clockwise from the top-left corner,
+// spiralling inwards.
+fn spiral(rows: usize, cols: usize) -> Vec<usize> {
+    let (mut top, mut bottom, mut left, mut right) = (0, rows, 0, cols);
+    let mut order = Vec::with_capacity(rows * cols);
+    while top < bottom && left < right {
+        order.extend((left..right).map(|c| top * cols + c));
+        order.extend((top + 1..bottom).map(|r| r * cols + right - 1));
+        if top + 1 < bottom {
+            order.extend((left..right - 1).rev().map(|c| (bottom - 1) * cols + c));
+        }
+        if left + 1 < right {
+            order.extend((top + 1..bottom - 1).rev().map(|r| r * cols + left));
+        }
+        (top, bottom, left, right) = (top + 1, bottom - 1, left + 1, right - 1);
+    }
+    return order;
+}
+
+impl FromStr for GoalPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return GoalPreset::ALL
+            .into_iter()
+            .find(|g| g.name() == s)
+            .ok_or_else(|| format!("unknown goal: {}", s));
+    }
+}
+
+impl fmt::Display for GoalPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
+
+/// Everything the heuristics need to know about a goal board, computed once
+/// per goal and shared by every evaluation during (and across) searches.
+/// A partial goal only asks for some of the tiles to be in place; the
+/// searches and heuristics ignore the others.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalContext {
+    goal: Board,
+    // positions[n] = (row, col) of tile n in the goal, index 0 is the blank
+    positions: [(usize, usize); MAX_SIDE * MAX_SIDE],
+    targets: Option<u32>, // bit n set for every tile n that must be in place, `None` for all
+}
+
+impl GoalContext {
+    pub fn new(goal: Board) -> GoalContext {
+        let mut positions = [(0, 0); MAX_SIDE * MAX_SIDE];
+        for r in 0..goal.rows() {
+            for c in 0..goal.cols() {
+                let n = goal.get(r, c).unwrap_or(0);
+                positions[n as usize] = (r, c);
+            }
+        }
+
+        return GoalContext {
+            goal,
+            positions,
+            targets: None,
+        };
+    }
+
+    /// A goal reached as soon as `tiles` are where they are in `goal`. Tiles
+    /// that are not on the board are left out.
+    pub fn partial(goal: Board, tiles: &[i64]) -> GoalContext {
+        let mut context = GoalContext::new(goal);
+        let mask = tiles
+            .iter()
+            .filter(|&&n| (1..=goal.max_tile()).contains(&n))
+            .fold(0u32, |mask, &n| mask | 1 << n);
+        if mask.count_ones() < goal.max_tile() as u32 {
+            context.targets = Some(mask);
+        }
+        return context;
+    }
+
+    pub fn standard() -> GoalContext {
+        return GoalContext::new(a_star::FINISHED);
+    }
+
+    /// The standard goal for a `rows` x `cols` puzzle.
+    pub fn for_size(rows: usize, cols: usize) -> GoalContext {
+        return GoalContext::for_preset(GoalPreset::Standard, rows, cols);
+    }
+
+    /// The goal of `preset` for a `rows` x `cols` puzzle.
+    pub fn for_preset(preset: GoalPreset, rows: usize, cols: usize) -> GoalContext {
+        return GoalContext::new(preset.board(rows, cols));
+    }
+
+    pub fn goal(&self) -> &Board {
+        return &self.goal;
+    }
+
+    pub fn is_goal(&self, board: &Board) -> bool {
+        if self.targets.is_none() {
+            return *board == self.goal;
+        }
+        for r in 0..board.rows() {
+            for c in 0..board.cols() {
+                if let Some(n) = board.get(r, c)
+                    && self.is_target(n)
+                    && self.position_of(n) != (r, c)
+                {
+                    return false;
+                }
+            }
+        }
+        return true;
+    }
+
+    pub fn is_partial(&self) -> bool {
+        return self.targets.is_some();
+    }
+
+    /// Whether `tile` has to be in place for the goal to be reached.
+    pub fn is_target(&self, tile: i64) -> bool {
+        return self.targets.is_none_or(|mask| mask & 1 << tile != 0);
+    }
+
+    /// The tiles that have to be in place, in order.
+    pub fn targets(&self) -> Vec<i64> {
+        return (1..=self.goal.max_tile())
+            .filter(|&n| self.is_target(n))
+            .collect();
+    }
+
+    /// Whether the goal can be reached from `board` at all. With two tiles or
+    /// more left out, some arrangement of those always can.
+    pub fn is_reachable(&self, board: &Board) -> bool {
+        let left_out = self.goal.max_tile() as usize - self.targets().len();
+        return left_out >= 2 || parity::is_solvable(board, &self.goal);
+    }
+
+    /// Whether `tile` is a tile the goal wants in `cell` and is already there.
+    pub fn is_in_place(&self, tile: i64, cell: (usize, usize)) -> bool {
+        return (1..=self.goal.max_tile()).contains(&tile)
+            && self.is_target(tile)
+            && self.position_of(tile) == cell;
+    }
+
+    pub fn position_of(&self, tile: i64) -> (usize, usize) {
+        return self.positions[tile as usize];
+    }
+
+    /// Sum of the Manhattan distances of every tile to its goal position.
+    pub fn manhattan(&self, board: &Board) -> i64 {
+        let mut total_distance: i64 = 0;
+        for r in 0..board.rows() {
+            for c in 0..board.cols() {
+                if let Some(n) = board.get(r, c)
+                    && self.is_target(n)
+                {
+                    let (gr, gc) = self.position_of(n);
+                    total_distance += (r as i64 - gr as i64).abs() + (c as i64 - gc as i64).abs();
+                }
+            }
+        }
+        return total_distance;
+    }
+
+    /// Change of the Manhattan distance when `tile` slides from `from` to
+    /// `to`; every other tile keeps its contribution.
+    pub fn manhattan_delta(&self, tile: i64, from: (usize, usize), to: (usize, usize)) -> i64 {
+        if !self.is_target(tile) {
+            return 0;
+        }
+        let (gr, gc) = self.position_of(tile);
+        let distance = |(r, c): (usize, usize)| {
+            return (r as i64 - gr as i64).abs() + (c as i64 - gc as i64).abs();
+        };
+        return distance(to) - distance(from);
+    }
+
+    /// Manhattan distance plus 2 for every tile that has to leave its goal
+    /// row (or column) to let the other tiles in that line pass each other.
+    pub fn linear_conflict(&self, board: &Board) -> i64 {
+        let mut extra_moves: i64 = 0;
+        for row in 0..board.rows() {
+            let mut order: Vec<usize> = Vec::new();
+            for col in 0..board.cols() {
+                if let Some(n) = board.get(row, col)
+                    && self.is_target(n)
+                {
+                    let (gr, gc) = self.position_of(n);
+                    if gr == row {
+                        order.push(gc);
+                    }
+                }
+            }
+            extra_moves += 2 * (order.len() - longest_increasing(&order)) as i64;
+        }
+        for col in 0..board.cols() {
+            let mut order: Vec<usize> = Vec::new();
+            for row in 0..board.rows() {
+                if let Some(n) = board.get(row, col)
+                    && self.is_target(n)
+                {
+                    let (gr, gc) = self.position_of(n);
+                    if gc == col {
+                        order.push(gr);
+                    }
+                }
+            }
+            extra_moves += 2 * (order.len() - longest_increasing(&order)) as i64;
+        }
+        return self.manhattan(board) + extra_moves;
+    }
+}
+
+// Length of the longest strictly increasing subsequence; the tiles outside of
+// it are the ones that must step out of the line.
+fn longest_increasing(values: &[usize]) -> usize {
+    let mut best = vec![1; values.len()];
+    for i in 0..values.len() {
+        for j in 0..i {
+            if values[j] < values[i] {
+                best[i] = best[i].max(best[j] + 1);
+            }
+        }
+    }
+    return best.into_iter().max().unwrap_or(0);
+}
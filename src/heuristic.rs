@@ -0,0 +1,77 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{board::Board, goal::GoalContext};
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum Heuristic {
+    #[default]
+    Manhattan,
+    LinearConflict,
+}
+
+impl Heuristic {
+    pub const ALL: [Heuristic; 2] = [Heuristic::Manhattan, Heuristic::LinearConflict];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Heuristic::Manhattan => "manhattan",
+            Heuristic::LinearConflict => "linear-conflict",
+        };
+    }
+
+    pub fn estimate(&self, board: &Board, goal: &GoalContext) -> i64 {
+        return match self {
+            Heuristic::Manhattan => goal.manhattan(board),
+            Heuristic::LinearConflict => goal.linear_conflict(board),
+        };
+    }
+
+    /// Estimate for `next`, one move away from `board` whose estimate is `h`.
+    /// Manhattan only re-scores the tile that moved; linear conflicts can
+    /// change along whole lines, so those are estimated from scratch.
+    pub fn estimate_after_move(
+        &self,
+        h: i64,
+        board: &Board,
+        next: &Board,
+        goal: &GoalContext,
+    ) -> i64 {
+        return match self {
+            Heuristic::Manhattan => {
+                // the tile slides into the old blank from where the blank is now
+                let (Some(to), Some(from)) = (board.find_empty_cell(), next.find_empty_cell())
+                else {
+                    return self.estimate(next, goal);
+                };
+                let Some(tile) = next.get(to.0, to.1) else {
+                    return self.estimate(next, goal);
+                };
+                let updated = h + goal.manhattan_delta(tile, from, to);
+                debug_assert_eq!(updated, self.estimate(next, goal));
+                updated
+            }
+            Heuristic::LinearConflict => self.estimate(next, goal),
+        };
+    }
+}
+
+impl FromStr for Heuristic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Heuristic::ALL
+            .into_iter()
+            .find(|h| h.name() == s)
+            .ok_or_else(|| format!("unknown heuristic: {}", s));
+    }
+}
+
+impl fmt::Display for Heuristic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
@@ -0,0 +1,40 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{self, SearchConfig},
+    board::Board,
+    book,
+    goal::GoalContext,
+    notation::{self, Move},
+    oracle::Oracle,
+};
+
+/// Distance to the goal within which a hint gives away every remaining move.
+pub const DEFAULT_FULL_HINT_DISTANCE: u8 = 6;
+
+/// Suggested moves from `board`: the whole rest of an optimal solution when
+/// the goal is at most `full_within` moves away, a single move otherwise.
+///
+/// Distances come from `oracle` (which must be built for `goal`) when one is
+/// given, then from the opening book, and from a search as the last resort.
+/// Returns `None` if the goal cannot be reached, and no moves if the board is
+/// already solved.
+pub fn hint(
+    board: &Board,
+    goal: &GoalContext,
+    oracle: Option<&Oracle>,
+    full_within: u8,
+) -> Option<Vec<Move>> {
+    let mut moves = match oracle {
+        Some(oracle) => notation::moves(&oracle.path(board)?),
+        None => match book::lookup_solution(board, goal, &SearchConfig::default()) {
+            Some(solution) => solution.directions,
+            None => a_star::search_with(*board, goal, SearchConfig::default())?.directions,
+        },
+    };
+    if moves.len() > full_within as usize {
+        moves.truncate(1);
+    }
+    return Some(moves);
+}
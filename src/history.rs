@@ -0,0 +1,194 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::session::{self, SavedSolution};
+
+use anyhow::{Context, Result};
+use eight_puzzle_solver::{algorithms::Algorithm, board::Board};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, io, path::PathBuf};
+
+/// Only the most recent solves are kept.
+pub const MAX_ENTRIES: usize = 100;
+
+/// Every puzzle solved in the TUI, oldest first.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct History {
+    #[serde(default, rename = "entry")]
+    pub entries: Vec<HistoryEntry>,
+    // the entries starting from each board, by board size and packed board
+    #[serde(skip)]
+    index: HashMap<(String, u64), Vec<usize>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct HistoryEntry {
+    pub board_size: String,
+    pub algorithm: String,
+    pub heuristic: String,
+    pub weight: i64,
+    pub solution: SavedSolution,
+}
+
+/// Averages over the solves made with the same settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsStats {
+    pub settings: String,
+    pub solves: usize,
+    pub moves: f64,
+    pub expanded: f64,
+    pub elapsed_ms: f64,
+}
+
+impl HistoryEntry {
+    /// The algorithm, with the heuristic and weight for informed ones, e.g.
+    /// "a-star, manhattan" or "a-star, linear-conflict, weight 2".
+    pub fn settings(&self) -> String {
+        let mut settings = self.algorithm.clone();
+        if self
+            .algorithm
+            .parse::<Algorithm>()
+            .is_ok_and(|a| a.is_informed())
+        {
+            settings.push_str(&format!(", {}", self.heuristic));
+            if self.weight != 1 {
+                settings.push_str(&format!(", weight {}", self.weight));
+            }
+        }
+        return settings;
+    }
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    return Some(session::state_dir()?.join("history.toml"));
+}
+
+impl History {
+    /// The saved history, empty if there is none yet.
+    pub fn load(path: &PathBuf) -> Result<History> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(History::default()),
+            Err(e) => return Err(e).with_context(|| format!("cannot read {}", path.display())),
+        };
+        let mut history: History =
+            toml::from_str(&text).with_context(|| format!("invalid history {}", path.display()))?;
+        history.reindex();
+        return Ok(history);
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        }
+        let text = toml::to_string(self).context("cannot serialize the history")?;
+        fs::write(path, text).with_context(|| format!("cannot write {}", path.display()))?;
+        return Ok(());
+    }
+
+    /// Adds an entry, dropping the oldest ones past [`MAX_ENTRIES`].
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries.push(entry);
+        let excess = self.entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries.drain(..excess);
+        self.reindex();
+    }
+
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (i, entry) in self.entries.iter().enumerate() {
+            let Some((rows, cols)) = entry.board_size.split_once('x') else {
+                continue;
+            };
+            let (Ok(rows), Ok(cols)) = (rows.parse(), cols.parse()) else {
+                continue;
+            };
+            if let Some(start) = session::board_from_code(&entry.solution.start, rows, cols) {
+                self.index
+                    .entry((entry.board_size.clone(), start.packed()))
+                    .or_default()
+                    .push(i);
+            }
+        }
+    }
+
+    /// The entries that start from `board`, newest first.
+    pub fn solves_of(&self, board: &Board) -> impl Iterator<Item = &HistoryEntry> {
+        let size = format!("{}x{}", board.rows(), board.cols());
+        return self
+            .index
+            .get(&(size, board.packed()))
+            .into_iter()
+            .flat_map(|indices| indices.iter().rev())
+            .map(|i| &self.entries[*i]);
+    }
+
+    /// Averages of the solves made with each of the settings, in the order
+    /// the settings were first used.
+    pub fn stats_by_settings(&self) -> Vec<SettingsStats> {
+        let mut stats: Vec<SettingsStats> = Vec::new();
+        for entry in &self.entries {
+            let settings = entry.settings();
+            let i = match stats.iter().position(|s| s.settings == settings) {
+                Some(i) => i,
+                None => {
+                    stats.push(SettingsStats {
+                        settings,
+                        solves: 0,
+                        moves: 0.0,
+                        expanded: 0.0,
+                        elapsed_ms: 0.0,
+                    });
+                    stats.len() - 1
+                }
+            };
+            // sums until they are divided below
+            let s = &mut stats[i];
+            s.solves += 1;
+            s.moves += entry.solution.moves.len() as f64;
+            s.expanded += entry.solution.expanded as f64;
+            s.elapsed_ms += entry.solution.elapsed_ms;
+        }
+        for s in &mut stats {
+            s.moves /= s.solves as f64;
+            s.expanded /= s.solves as f64;
+            s.elapsed_ms /= s.solves as f64;
+        }
+        return stats;
+    }
+
+    /// A recap of the last `count` solves, or `None` if there are none.
+    pub fn recap(&self, count: usize) -> Option<String> {
+        let entries = &self.entries[self.entries.len().saturating_sub(count)..];
+        if entries.is_empty() {
+            return None;
+        }
+        let moves: usize = entries.iter().map(|e| e.solution.moves.len()).sum();
+        let millis: f64 = entries.iter().map(|e| e.solution.elapsed_ms).sum();
+        // in the order they were first used
+        let mut algorithms: Vec<(&str, usize)> = Vec::new();
+        for entry in entries {
+            match algorithms
+                .iter_mut()
+                .find(|(name, _)| *name == entry.algorithm)
+            {
+                Some((_, solves)) => *solves += 1,
+                None => algorithms.push((&entry.algorithm, 1)),
+            }
+        }
+        let algorithms: Vec<String> = algorithms
+            .iter()
+            .map(|(name, solves)| format!("{} ({})", name, solves))
+            .collect();
+        let plural = if entries.len() == 1 { "" } else { "s" };
+        return Some(format!(
+            "{} puzzle{} solved this session, {:.1} moves on average, {:.1} ms of searching\nAlgorithms: {}",
+            entries.len(),
+            plural,
+            moves as f64 / entries.len() as f64,
+            millis,
+            algorithms.join(", ")
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use anyhow::{Context, Result};
+use crossterm::event::{self, Event};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Write},
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A terminal event and when it happened, relative to the start of the session.
+#[derive(Serialize, Deserialize)]
+struct Recorded {
+    at: Duration,
+    event: Event,
+}
+
+/// Where the TUI gets its terminal events from: the live terminal, optionally
+/// preceded by a replayed recording and optionally recording everything.
+pub struct Input {
+    started: Instant,
+    replay: VecDeque<Recorded>,
+    recorder: Option<BufWriter<File>>,
+}
+
+impl Input {
+    pub fn live() -> Input {
+        return Input {
+            started: Instant::now(),
+            replay: VecDeque::new(),
+            recorder: None,
+        };
+    }
+
+    /// Records every event to `path`, replacing its content.
+    pub fn record_to(mut self, path: &Path) -> Result<Input> {
+        let file =
+            File::create(path).with_context(|| format!("cannot create {}", path.display()))?;
+        self.recorder = Some(BufWriter::new(file));
+        return Ok(self);
+    }
+
+    /// Plays back the events recorded in `path` at their original pace before
+    /// switching to the live terminal.
+    pub fn replay_from(mut self, path: &Path) -> Result<Input> {
+        let file = File::open(path).with_context(|| format!("cannot open {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+        loop {
+            match bincode::deserialize_from::<_, Recorded>(&mut reader) {
+                Ok(recorded) => self.replay.push_back(recorded),
+                Err(e) => match *e {
+                    bincode::ErrorKind::Io(ref io) if io.kind() == ErrorKind::UnexpectedEof => {
+                        break;
+                    }
+                    _ => {
+                        return Err(e)
+                            .with_context(|| format!("invalid recording {}", path.display()));
+                    }
+                },
+            }
+        }
+        return Ok(self);
+    }
+
+    /// The next event, or `None` if nothing happened within `timeout`.
+    pub fn next(&mut self, timeout: Duration) -> Result<Option<Event>> {
+        let event = match self.replay.front() {
+            Some(recorded) => {
+                let wait = recorded.at.saturating_sub(self.started.elapsed());
+                if wait > timeout {
+                    thread::sleep(timeout);
+                    return Ok(None);
+                }
+                thread::sleep(wait);
+                self.replay.pop_front().map(|r| r.event)
+            }
+            None if event::poll(timeout)? => Some(event::read()?),
+            None => None,
+        };
+
+        if let (Some(recorder), Some(event)) = (&mut self.recorder, &event) {
+            let recorded = Recorded {
+                at: self.started.elapsed(),
+                event: event.clone(),
+            };
+            bincode::serialize_into(&mut *recorder, &recorded)?;
+            // keep the recording usable even if the app crashes right after
+            recorder.flush()?;
+        }
+        return Ok(event);
+    }
+}
@@ -0,0 +1,106 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::versus::Outcome;
+use eight_puzzle_solver::{
+    a_star::{Progress, SearchConfig},
+    algorithms::{Algorithm, SearchOutcome},
+    board::Board,
+    goal::GoalContext,
+};
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+/// A search left running in the background while another puzzle is set up,
+/// or waiting for the others to finish before it starts.
+pub struct Job {
+    pub board: Board,
+    pub goal: GoalContext,
+    pub algorithm: Algorithm,
+    pub config: SearchConfig,
+    pub outcome: Outcome,
+    pub progress: Option<Progress>, // the latest report while it runs
+    rx_result: Option<Receiver<SearchOutcome>>,
+    rx_progress: Option<Receiver<Progress>>, // the search stops once this is dropped
+}
+
+impl Job {
+    pub fn new(
+        board: Board,
+        goal: GoalContext,
+        algorithm: Algorithm,
+        config: SearchConfig,
+        rx_result: Receiver<SearchOutcome>,
+        rx_progress: Receiver<Progress>,
+    ) -> Job {
+        let mut job = Job::queued(board, goal, algorithm, config);
+        job.start(rx_result, rx_progress);
+        return job;
+    }
+
+    /// A search of `board` that has not started yet.
+    pub fn queued(
+        board: Board,
+        goal: GoalContext,
+        algorithm: Algorithm,
+        config: SearchConfig,
+    ) -> Job {
+        return Job {
+            board,
+            goal,
+            algorithm,
+            config,
+            outcome: Outcome::Waiting,
+            progress: None,
+            rx_result: None,
+            rx_progress: None,
+        };
+    }
+
+    /// Follows the search started for this job.
+    pub fn start(&mut self, rx_result: Receiver<SearchOutcome>, rx_progress: Receiver<Progress>) {
+        self.outcome = Outcome::Running;
+        self.rx_result = Some(rx_result);
+        self.rx_progress = Some(rx_progress);
+    }
+
+    /// Collects the latest progress and the result. True when the search has
+    /// just found a solution.
+    pub fn poll(&mut self) -> bool {
+        while let Some(Ok(progress)) = self.rx_progress.as_ref().map(|rx| rx.try_recv()) {
+            self.progress = Some(progress);
+        }
+        let Some(rx) = &self.rx_result else {
+            return false;
+        };
+        self.outcome = match rx.try_recv() {
+            Ok(result) => Outcome::from_search(result, self.algorithm, &self.config),
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                Outcome::Failed("Search thread panicked.".to_string())
+            }
+        };
+        self.rx_result = None;
+        return matches!(self.outcome, Outcome::Solved(_));
+    }
+
+    pub fn is_running(&self) -> bool {
+        return self.rx_result.is_some();
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        return matches!(self.outcome, Outcome::Waiting);
+    }
+
+    /// How the search is going, in a few words, e.g. "22 moves".
+    pub fn status(&self) -> String {
+        return match &self.outcome {
+            Outcome::Waiting => "queued".to_string(),
+            Outcome::Running => match &self.progress {
+                Some(progress) => format!("{} nodes", progress.expanded),
+                None => "running".to_string(),
+            },
+            Outcome::Solved(solution) => format!("{} moves", solution.moves()),
+            Outcome::Failed(_) => "failed".to_string(),
+        };
+    }
+}
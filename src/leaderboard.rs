@@ -0,0 +1,143 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::session;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf, time::Duration};
+
+/// Personal bests of the puzzles solved by hand, one record per puzzle.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Leaderboard {
+    #[serde(default, rename = "record")]
+    pub records: Vec<Record>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Record {
+    pub board_size: String,
+    pub start: String,
+    pub seed: Option<u64>, // when the puzzle was scrambled from a seed
+    pub scramble_moves: Option<usize>, // with that many random moves
+    pub optimal: Option<usize>, // the difficulty, as the shortest solution
+    pub solves: usize,
+    pub best_time_ms: u64,
+    pub best_time_moves: usize,
+    pub fewest_moves: usize,
+    pub fewest_moves_time_ms: u64,
+}
+
+/// A puzzle just solved by hand.
+pub struct Solve {
+    pub board_size: String,
+    pub start: String,
+    pub seed: Option<(u64, usize)>, // seed and scramble moves
+    pub optimal: Option<usize>,
+    pub moves: usize,
+    pub time: Duration,
+}
+
+/// Which personal bests a solve beat; both for the first solve of a puzzle.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Improved {
+    pub time: bool,
+    pub moves: bool,
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    return Some(session::state_dir()?.join("leaderboard.toml"));
+}
+
+/// `m:ss.s`, the way times are shown in play mode.
+pub fn format_time(time: Duration) -> String {
+    let tenths = time.as_millis() / 100;
+    return format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10);
+}
+
+impl Record {
+    pub fn best_time(&self) -> Duration {
+        return Duration::from_millis(self.best_time_ms);
+    }
+
+    pub fn fewest_moves_time(&self) -> Duration {
+        return Duration::from_millis(self.fewest_moves_time_ms);
+    }
+}
+
+impl Leaderboard {
+    /// The saved leaderboard, empty if there is none yet.
+    pub fn load(path: &PathBuf) -> Result<Leaderboard> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Leaderboard::default()),
+            Err(e) => return Err(e).with_context(|| format!("cannot read {}", path.display())),
+        };
+        return toml::from_str(&text)
+            .with_context(|| format!("invalid leaderboard {}", path.display()));
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        }
+        let text = toml::to_string(self).context("cannot serialize the leaderboard")?;
+        fs::write(path, text).with_context(|| format!("cannot write {}", path.display()))?;
+        return Ok(());
+    }
+
+    /// Adds a solve to the record of its puzzle, starting one if needed.
+    pub fn record(&mut self, solve: Solve) -> Improved {
+        let time_ms = solve.time.as_millis() as u64;
+        let existing = self
+            .records
+            .iter_mut()
+            .find(|r| r.board_size == solve.board_size && r.start == solve.start);
+        let Some(record) = existing else {
+            self.records.push(Record {
+                board_size: solve.board_size,
+                start: solve.start,
+                seed: solve.seed.map(|(seed, _)| seed),
+                scramble_moves: solve.seed.map(|(_, moves)| moves),
+                optimal: solve.optimal,
+                solves: 1,
+                best_time_ms: time_ms,
+                best_time_moves: solve.moves,
+                fewest_moves: solve.moves,
+                fewest_moves_time_ms: time_ms,
+            });
+            return Improved {
+                time: true,
+                moves: true,
+            };
+        };
+        record.solves += 1;
+        record.optimal = record.optimal.or(solve.optimal);
+        if record.seed.is_none()
+            && let Some((seed, moves)) = solve.seed
+        {
+            record.seed = Some(seed);
+            record.scramble_moves = Some(moves);
+        }
+        let mut improved = Improved::default();
+        if time_ms < record.best_time_ms {
+            record.best_time_ms = time_ms;
+            record.best_time_moves = solve.moves;
+            improved.time = true;
+        }
+        if solve.moves < record.fewest_moves {
+            record.fewest_moves = solve.moves;
+            record.fewest_moves_time_ms = time_ms;
+            improved.moves = true;
+        }
+        return improved;
+    }
+
+    /// The records hardest first, then by best time.
+    pub fn ranked(&self) -> Vec<&Record> {
+        let mut records: Vec<&Record> = self.records.iter().collect();
+        records.sort_by_key(|r| (std::cmp::Reverse(r.optimal), r.best_time_ms));
+        return records;
+    }
+}
@@ -0,0 +1,33 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+pub mod a_star;
+pub mod actions;
+pub mod algorithms;
+pub mod board;
+pub mod book;
+pub mod compare;
+pub mod cost;
+pub mod dot;
+pub mod error;
+pub mod explain;
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod goal;
+pub mod heuristic;
+pub mod hint;
+pub mod lrta;
+pub mod notation;
+pub mod optimal;
+pub mod oracle;
+pub mod parity;
+pub mod puzzles;
+pub mod report;
+pub mod review;
+pub mod rng;
+pub mod shorten;
+pub mod symmetry;
+pub mod tournament;
+pub mod validate;
+pub mod verify;
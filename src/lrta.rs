@@ -0,0 +1,105 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{board::Board, cost::CostModel, goal::GoalContext, heuristic::Heuristic};
+
+use std::collections::HashMap;
+
+/// Learning real-time A* (LRTA*): an agent that only ever looks one move
+/// ahead. Each step it raises the estimate of the board it stands on to the
+/// best it can see from there, then moves to the neighbor that looks best.
+/// The raised estimates are kept, so the agent gets out of the dead ends of
+/// the heuristic, and every new trial from the start goes more directly.
+pub struct Agent {
+    pub start: Board,
+    pub board: Board,
+    pub path: Vec<Board>,     // the boards of the current trial, from the start
+    pub trial: usize,         // 1 for the first run from the start
+    pub finished: Vec<usize>, // moves of every trial that reached the goal
+    pub updates: usize,       // times an estimate was raised, over every trial
+    goal: GoalContext,
+    heuristic: Heuristic,
+    cost: CostModel,
+    learned: HashMap<Board, i64>, // estimates raised above the heuristic
+}
+
+impl Agent {
+    pub fn new(start: Board, goal: &GoalContext, heuristic: Heuristic, cost: CostModel) -> Agent {
+        return Agent {
+            start,
+            board: start,
+            path: vec![start],
+            trial: 1,
+            finished: Vec::new(),
+            updates: 0,
+            goal: goal.clone(),
+            heuristic,
+            cost,
+            learned: HashMap::new(),
+        };
+    }
+
+    pub fn goal(&self) -> &GoalContext {
+        return &self.goal;
+    }
+
+    pub fn is_solved(&self) -> bool {
+        return self.goal.is_goal(&self.board);
+    }
+
+    /// Moves made in the current trial.
+    pub fn moves(&self) -> usize {
+        return self.path.len() - 1;
+    }
+
+    /// Number of boards whose estimate was learned.
+    pub fn learned(&self) -> usize {
+        return self.learned.len();
+    }
+
+    /// The estimate of `board`: the learned one, or the heuristic's.
+    pub fn h(&self, board: &Board) -> i64 {
+        return match self.learned.get(board) {
+            Some(h) => *h,
+            None => self.heuristic.estimate(board, &self.goal),
+        };
+    }
+
+    /// Makes one move, learning on the way. Returns `false` once the goal is
+    /// reached, without moving.
+    pub fn step(&mut self) -> bool {
+        if self.is_solved() {
+            return false;
+        }
+        // the first of equally good moves, in the usual order
+        let Some((f, next)) = self
+            .board
+            .get_possible_next_states()
+            .into_iter()
+            .map(|n| {
+                let f = self.cost.of_move(&self.board, &n.board) + self.h(&n.board);
+                (f, n.board)
+            })
+            .min_by_key(|(f, _)| *f)
+        else {
+            return false;
+        };
+        if f > self.h(&self.board) {
+            self.learned.insert(self.board, f);
+            self.updates += 1;
+        }
+        self.board = next;
+        self.path.push(next);
+        return true;
+    }
+
+    /// Goes back to the start for another trial, keeping what was learned.
+    pub fn restart(&mut self) {
+        if self.is_solved() {
+            self.finished.push(self.moves());
+        }
+        self.board = self.start;
+        self.path = vec![self.start];
+        self.trial += 1;
+    }
+}
@@ -1,102 +1,208 @@
 #![deny(unused_variables)]
 #![deny(unused_imports)]
 
-mod a_star;
-mod board;
+mod app;
+mod ascii;
+mod checkpoint;
+mod cli;
+mod clipboard;
+mod config;
+mod crash;
+mod eta;
+mod events;
+mod history;
+mod input;
+mod jobs;
+mod leaderboard;
+mod pipe;
+mod plain;
+mod play;
+mod race;
+mod replay;
+mod session;
+mod theme;
+mod tutorial;
+mod versus;
 
-use anyhow::Result;
-use board::Board;
+use anyhow::{Context, Result, bail};
+use app::{App, AppMode, MEGABYTE, SLIDE_FRAMES, join_tiles};
+use clap::Parser;
+use cli::Cli;
+use config::Config;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use eight_puzzle_solver::{
+    a_star::{FrontierHistogram, HISTOGRAM_BARS, Progress},
+    actions::ActionSet,
+    algorithms::Algorithm,
+    board::{self, Board},
+    cost::CostModel,
+    explain,
+    goal::{GoalContext, GoalPreset},
+    lrta::Agent,
+    notation,
+    puzzles::{self, Collection},
+    validate,
+};
+use events::{AppEvent, Events};
+use history::{History, HistoryEntry};
+use input::Input;
+use jobs::Job;
+use leaderboard::Leaderboard;
+use race::{Race, Winner};
 use ratatui::{
     Frame, Terminal,
     backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
-    widgets::{Block, BorderType, Borders, Gauge, Paragraph},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{
+        Bar, BarChart, Block, BorderType, Borders, Clear, Gauge, List, ListItem, Paragraph,
+        Sparkline, Tabs, Wrap,
+    },
 };
+use replay::Replay;
+use session::Session;
 use std::{
-    io,
-    sync::mpsc::{self, Receiver, TryRecvError},
-    thread,
-    time::{Duration, Instant},
+    fs,
+    io::{self, IsTerminal},
+    sync::Mutex,
 };
+use theme::Palette;
+use tutorial::{Lesson, Tutorial};
+use versus::{Outcome, Versus};
 
-enum AppMode {
-    Input,
-    Searching,
-    Result,
-}
-
-struct App {
-    mode: AppMode,
-    // Input state
-    input_board: Board,
-    cursor_pos: (usize, usize), // (row, col)
-    error_msg: Option<String>,
-
-    // Search state
-    rx_result: Option<Receiver<Option<Vec<Board>>>>,
-    spinner_idx: usize,
-
-    // Result state
-    solution_path: Vec<Board>,
-    current_step: usize,
-}
-
-impl App {
-    fn new() -> App {
-        App {
-            mode: AppMode::Input,
-            input_board: Board::default(),
-            cursor_pos: (0, 0),
-            error_msg: None,
-            rx_result: None,
-            spinner_idx: 0,
-            solution_path: Vec::new(),
-            current_step: 0,
-        }
-    }
+// Size of the chart of the f values on the queue while searching
+const FRONTIER_CHART_HEIGHT: u16 = 10;
+const FRONTIER_BAR_WIDTH: u16 = 4;
 
-    fn on_tick(&mut self) {
-        // Update spinner animation
-        if let AppMode::Searching = self.mode {
-            self.spinner_idx = (self.spinner_idx + 1) % 4;
-
-            // Check if thread finished
-            if let Some(rx) = &self.rx_result {
-                match rx.try_recv() {
-                    Ok(result) => {
-                        match result {
-                            Some(path) => {
-                                self.solution_path = path;
-                                self.current_step = 0;
-                                self.mode = AppMode::Result;
-                            }
-                            None => {
-                                self.error_msg =
-                                    Some("No solution found for this configuration.".to_string());
-                                self.mode = AppMode::Input;
-                            }
-                        }
-                        self.rx_result = None;
-                    }
-                    Err(TryRecvError::Empty) => {} // Still working
-                    Err(TryRecvError::Disconnected) => {
-                        self.error_msg = Some("Search thread panicked.".to_string());
-                        self.mode = AppMode::Input;
-                        self.rx_result = None;
-                    }
+// Rows of the lesson of the tour under the screen it is about, borders included
+const TUTORIAL_HEIGHT: u16 = 5;
+
+// Rows of each chart of the latest solves, borders included
+const ANALYTICS_SPARKLINE_HEIGHT: u16 = 6;
+
+// Smallest terminal the UI can be drawn in without widgets overlapping
+const MIN_WIDTH: u16 = 50;
+const MIN_HEIGHT: u16 = 25;
+
+// Bounds for the size of a single board cell, borders included
+const MIN_CELL_WIDTH: u16 = 5;
+const MAX_CELL_WIDTH: u16 = 11;
+const MIN_CELL_HEIGHT: u16 = 3;
+const MAX_CELL_HEIGHT: u16 = 5;
+
+// The Result view gets a commentary panel on wide enough terminals
+const COMMENTARY_MIN_WIDTH: u16 = 110;
+const COMMENTARY_WIDTH: u16 = 46;
+
+// narrower terminals get a plain list of the moves instead
+const STEP_LIST_WIDTH: u16 = 20;
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    // the TUI owns the terminal, so logs can only go to a file
+    if let Some(path) = &cli.log_file {
+        let file = fs::File::create(path)
+            .with_context(|| format!("cannot create log file {}", path.display()))?;
+        tracing_subscriber::fmt()
+            .with_writer(Mutex::new(file))
+            .with_ansi(false)
+            .with_thread_ids(true)
+            .with_max_level(tracing::Level::DEBUG)
+            .init();
+    }
+    let config = Config::load(&cli.config)?;
+    tracing::debug!(?config, "configuration loaded");
+    match cli.command {
+        Some(command) => cli::run(command, &config),
+        None if cli.plain => plain::run(&config),
+        None if cli.pipe => pipe::run(&config),
+        // the TUI would only fill the pipe or file with escape sequences
+        None if !io::stdout().is_terminal() => match cli.board {
+            // a board to solve needs no prompts, it is solved like `solve BOARD`
+            Some(board) => {
+                let solve = cli::Command::Solve {
+                    board,
+                    format: cli::OutputFormat::Text,
+                    dot: None,
+                    checkpoint: None,
+                    resume: None,
+                };
+                cli::run(solve, &config)
+            }
+            None => {
+                eprintln!("Standard output is not a terminal, starting in plain mode (--plain).");
+                for flag in cli.tui_flags() {
+                    eprintln!("{} only applies to the TUI and is ignored.", flag);
                 }
+                plain::run(&config)
             }
+        },
+        None => {
+            let mut input = Input::live();
+            if let Some(path) = &cli.replay_input {
+                input = input.replay_from(path)?;
+            }
+            if let Some(path) = &cli.record_input {
+                input = input.record_to(path)?;
+            }
+            // a scramble without a seed still gets one, so it can be shared
+            let seed = match (cli.seed, cli.scramble) {
+                (Some(seed), _) => Some(seed),
+                (None, Some(_)) => Some(config.fresh_seed()),
+                (None, None) => None,
+            };
+            let scramble = seed.map(|seed| {
+                let moves = cli.scramble.unwrap_or(board::DEFAULT_SCRAMBLE_MOVES);
+                (seed, moves)
+            });
+            let presets = match &cli.presets {
+                Some(path) => {
+                    let text = fs::read_to_string(path)
+                        .with_context(|| format!("cannot read {}", path.display()))?;
+                    Collection::parse(&text, puzzles::Format::from_path(path))
+                        .with_context(|| format!("invalid puzzle collection {}", path.display()))?
+                }
+                None => Collection::classic(),
+            };
+            let replay = match &cli.open_replay {
+                Some(path) => Some(Replay::load(path)?),
+                None => None,
+            };
+            let board = match &cli.board {
+                Some(text) => match validate::check_import(text, &config.goal(), config.cell_order)
+                {
+                    Ok(board) => Some((board, cli.auto)),
+                    Err(rejection) => bail!("rejected board: {}", rejection),
+                },
+                None => None,
+            };
+            let launch = Launch {
+                scramble,
+                board,
+                replay,
+                fresh: cli.fresh,
+                tutorial: cli.tutorial,
+            };
+            run_tui(&config, input, presets, launch)
         }
     }
 }
 
-fn main() -> Result<()> {
+// Where the TUI starts, as asked on the command line.
+struct Launch {
+    scramble: Option<(u64, usize)>, // seed and number of moves
+    board: Option<(Board, bool)>,   // solved straight away when set
+    replay: Option<Replay>,
+    fresh: bool, // the last session is not restored
+    tutorial: bool,
+}
+
+fn run_tui(config: &Config, input: Input, presets: Collection, launch: Launch) -> Result<()> {
     // Setup Terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -104,114 +210,89 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create App
-    let mut app = App::new();
-    let tick_rate = Duration::from_millis(250);
-    let mut last_tick = Instant::now();
-
-    loop {
-        terminal.draw(|f| draw(f, &mut app))?;
-
-        let timeout = tick_rate
-            .checked_sub(last_tick.elapsed())
-            .unwrap_or_else(|| Duration::from_secs(0));
-
-        if crossterm::event::poll(timeout)?
-            && let Event::Key(key) = event::read()?
-        {
-            match app.mode {
-                AppMode::Input => {
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => break,
-                        KeyCode::Left => {
-                            if app.cursor_pos.1 > 0 {
-                                app.cursor_pos.1 -= 1;
-                            }
-                        }
-                        KeyCode::Right => {
-                            if app.cursor_pos.1 < 2 {
-                                app.cursor_pos.1 += 1;
-                            }
-                        }
-                        KeyCode::Up => {
-                            if app.cursor_pos.0 > 0 {
-                                app.cursor_pos.0 -= 1;
-                            }
-                        }
-                        KeyCode::Down => {
-                            if app.cursor_pos.0 < 2 {
-                                app.cursor_pos.0 += 1;
-                            }
-                        }
-                        KeyCode::Char(c) if c.is_ascii_digit() => {
-                            let digit = c.to_digit(10).unwrap();
-                            if (1..=8).contains(&digit) {
-                                app.input_board.b[app.cursor_pos.0][app.cursor_pos.1] =
-                                    Some(digit as i64);
-                            } else if digit == 0 {
-                                app.input_board.b[app.cursor_pos.0][app.cursor_pos.1] = None;
-                            }
-                        }
-                        KeyCode::Backspace | KeyCode::Delete | KeyCode::Char(' ') => {
-                            app.input_board.b[app.cursor_pos.0][app.cursor_pos.1] = None;
-                        }
-                        KeyCode::Enter => {
-                            // Validate and Start Search
-                            if app.input_board.is_valid() {
-                                app.mode = AppMode::Searching;
-                                app.error_msg = None;
-
-                                let board_clone = app.input_board;
-                                let (tx, rx) = mpsc::channel();
-                                app.rx_result = Some(rx);
-
-                                // Spawn search thread
-                                thread::spawn(move || {
-                                    let result = a_star::search(board_clone);
-                                    tx.send(result).unwrap();
-                                });
-                            } else {
-                                app.error_msg = Some(
-                                    "Invalid Board: Must contain 1-8 unique & 1 empty.".to_string(),
-                                );
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-                AppMode::Searching => {
-                    // Consume keys but do nothing, or allow 'q' to abort
-                    if let KeyCode::Char('q') = key.code {
-                        // Simple abort by resetting app (thread keeps running detached but ignored)
-                        app.mode = AppMode::Input;
-                        app.rx_result = None;
-                    }
+    // Create App, picking up the previous session unless asked not to
+    let mut app = App::new(config);
+    let session_path = session::default_path();
+    if let Some(path) = session_path.as_ref().filter(|_| !launch.fresh) {
+        match Session::load(path) {
+            Ok(Some(session)) => {
+                tracing::info!(path = %path.display(), "session restored");
+                app.restore(session);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("cannot restore the last session: {:#}", e);
+                app.error_msg = Some(format!("Cannot restore the last session: {:#}", e));
+            }
+        }
+    }
+    app.history_path = history::default_path();
+    if let Some(path) = &app.history_path {
+        match History::load(path) {
+            Ok(history) => app.history = history,
+            Err(e) => app.error_msg = Some(format!("Cannot load the history: {:#}", e)),
+        }
+    }
+    app.leaderboard_path = leaderboard::default_path();
+    if let Some(path) = &app.leaderboard_path {
+        match Leaderboard::load(path) {
+            Ok(leaderboard) => app.leaderboard = leaderboard,
+            Err(e) => app.error_msg = Some(format!("Cannot load the leaderboard: {:#}", e)),
+        }
+    }
+    app.set_presets(presets);
+    if let Some((seed, moves)) = launch.scramble {
+        app.scramble_moves = moves;
+        app.scramble(seed);
+    }
+    if let Some(replay) = &launch.replay {
+        app.open_replay(replay);
+    }
+    match launch.board {
+        Some((board, true)) => app.solve_on_launch(board),
+        Some((board, false)) => app.input_board = board,
+        None => {}
+    }
+    if launch.tutorial {
+        app.toggle_tutorial();
+    }
+    app.rx_crash = Some(crash::install());
+    app.start_oracle();
+    tracing::info!("tui started");
+
+    let events = Events::start(input, app.tick_rate());
+    let mut redraw = true;
+    'events: loop {
+        if redraw {
+            terminal.draw(|f| draw(f, &mut app))?;
+        }
+        redraw = false;
+        let mut resized: Option<Rect> = None;
+        for event in events.next_batch()? {
+            match event {
+                AppEvent::Error(e) => return Err(e),
+                // dragging the window sends a burst of these, only the last size matters
+                AppEvent::Resize(width, height) => resized = Some(Rect::new(0, 0, width, height)),
+                // nothing to show for a tick while the app sits idle
+                AppEvent::Tick if !app.is_busy() => {
+                    app.handle_event(AppEvent::Tick);
+                    // unless the tick set something going, like the demo
+                    redraw |= app.is_busy();
                 }
-                AppMode::Result => {
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => {
-                            app.mode = AppMode::Input; // Return to editor
-                        }
-                        KeyCode::Left => {
-                            if app.current_step > 0 {
-                                app.current_step -= 1;
-                            }
-                        }
-                        KeyCode::Right => {
-                            if app.current_step < app.solution_path.len() - 1 {
-                                app.current_step += 1;
-                            }
-                        }
-                        _ => {}
+                event => {
+                    if !app.handle_event(event) {
+                        break 'events;
                     }
+                    redraw = true;
                 }
             }
         }
-
-        if last_tick.elapsed() >= tick_rate {
-            app.on_tick();
-            last_tick = Instant::now();
+        // clears the screen too, so the next frame is drawn in full at the new size
+        if let Some(area) = resized {
+            terminal.resize(area)?;
+            redraw = true;
         }
+        events.set_tick_rate(app.tick_rate());
     }
 
     // Restore Terminal
@@ -223,18 +304,80 @@ fn main() -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    if let Some(path) = &session_path {
+        app.snapshot().save(path)?;
+        tracing::info!(path = %path.display(), "session saved");
+    }
+    // on the terminal the TUI left behind, where it stays after quitting
+    if config.session_summary {
+        match app.history.recap(app.session_solves) {
+            Some(recap) => println!("{}", recap),
+            None => println!("No puzzles solved this session."),
+        }
+    }
     Ok(())
 }
 
 fn draw(f: &mut Frame, app: &mut App) {
+    draw_screen(f, app);
+    if app.ascii {
+        ascii::to_ascii(f.buffer_mut());
+    }
+}
+
+fn draw_screen(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        draw_too_small(f, area, &app.theme.palette());
+        return;
+    }
+
+    // Footer (Instructions), wrapped over as many lines as the width needs
+    let footer_text = match app.mode {
+        _ if app.crash.is_some() => "c: Copy Report | Esc: Close",
+        _ if app.attract.is_some() => "Demo | Press any key to stop",
+        AppMode::Input => {
+            "Arrows: Move | 0-9: Fill | Tab: Start/Goal | p: Paste | s: Seed | g/G: Goal Tiles/Preset | o/f: Rotate/Mirror | x: Hardest | P: Presets | t: Theme | m: Play | r: Race | a: Agent | c: Compare | H/A: History/Analytics | L: Leaderboard | j: Jobs | T: Tour | h/w/k: Heuristic/Weight/Cost | v: Visualize | Enter: Solve | q: Quit"
+        }
+        AppMode::Searching if app.is_paused() => "Paused | p: Resume | q: Abort",
+        AppMode::Searching if app.visualize => "Calculating... | p: Pause | q: Abort",
+        AppMode::Searching => "Calculating... | p: Pause | b: Run in Background | q: Abort",
+        AppMode::Summary => {
+            "Enter: View Steps | e/E: Export Text/Cast | c: Copy Moves | i: Diagnostics | r: Next Algorithm | d: Discard"
+        }
+        AppMode::Result => {
+            "Left/Right: Prev/Next Step | PgUp/PgDn/Home/End: Jump | o: Options | i: Diagnostics | a: Count Optimal | y/Y: Copy Moves/Boards | s: Summary | q: New Puzzle"
+        }
+        AppMode::Seed => "0-9: Type Seed | Enter: Scramble | Esc: Cancel",
+        AppMode::GoalTiles => {
+            "0-9: Type Tiles | Enter: Set Goal (empty for every tile) | Esc: Cancel"
+        }
+        AppMode::Play => {
+            "Arrows: Move Blank | r: Record Macro | 1-9: Save/Play Macro | h: Hint | f: Finish | e: Export | q: Back"
+        }
+        AppMode::History => "Up/Down: Select | Enter: Open Solution | q: Back",
+        AppMode::Analytics => "q: Back",
+        AppMode::Leaderboard => "Up/Down: Select | Enter: Play Again | q: Back",
+        AppMode::Jobs => "Up/Down: Select | Enter: Open Solution | d: Remove | q: Back",
+        AppMode::Presets => "Up/Down: Select | Enter: Load Puzzle | q: Back",
+        AppMode::Race => "Arrows: Move Blank | r: Restart | q: Back",
+        AppMode::Agent if app.agent_paused => "Paused | p: Resume | r: New Trial | q: Back",
+        AppMode::Agent => "p: Pause | r: New Trial (keeps what it learned) | q: Back",
+        AppMode::Versus => {
+            "Tab: Switch Side | a/h/w: Algorithm/Heuristic/Weight | Enter: Solve Both | q: Back"
+        }
+    };
+    let footer_inner_width = (area.width - 4) as usize; // margin + borders
+    let footer_lines = footer_text.len().div_ceil(footer_inner_width) as u16;
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints(
             [
-                Constraint::Length(3), // Title
-                Constraint::Min(0),    // Content
-                Constraint::Length(3), // Footer (Instructions)
+                Constraint::Length(3),                // Title
+                Constraint::Min(0),                   // Content
+                Constraint::Length(footer_lines + 2), // Footer (Instructions)
             ]
             .as_ref(),
         )
@@ -243,149 +386,716 @@ fn draw(f: &mut Frame, app: &mut App) {
     // Title
     let title_block = Block::default()
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Cyan));
+        .style(app.theme.palette().title);
     let title = Paragraph::new("Rust A* 8-Puzzle Solver")
         .block(title_block)
         .alignment(Alignment::Center);
     f.render_widget(title, chunks[0]);
 
     // Footer
-    let footer_text = match app.mode {
-        AppMode::Input => "Arrows: Move | 0-8: Fill | Space: Empty | Enter: Solve | q: Quit",
-        AppMode::Searching => "Calculating... Please wait...",
-        AppMode::Result => "Left/Right: Prev/Next Step | q: New Puzzle",
-    };
     let footer = Paragraph::new(footer_text)
         .block(Block::default().borders(Borders::ALL))
-        .alignment(Alignment::Center);
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
     f.render_widget(footer, chunks[2]);
 
-    // Main Content
-    let content_area = chunks[1];
+    // Main Content, above the lesson of the tour while it is taken
+    let mut content_area = chunks[1];
+    if let Some(tutorial) = &app.tutorial {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(TUTORIAL_HEIGHT)].as_ref())
+            .split(content_area);
+        content_area = rows[0];
+        draw_tutorial(f, tutorial, &app.theme.palette(), rows[1]);
+    }
 
     match app.mode {
         AppMode::Input => draw_input(f, app, content_area),
         AppMode::Searching => draw_searching(f, app, content_area),
-        AppMode::Result => draw_result(f, app, content_area),
+        AppMode::Summary => {
+            draw_result(f, app, content_area);
+            match app.show_diagnostics {
+                true => draw_diagnostics(f, app, content_area),
+                false => draw_summary(f, app, content_area),
+            }
+        }
+        AppMode::Result => {
+            draw_result(f, app, content_area);
+            if app.show_diagnostics {
+                draw_diagnostics(f, app, content_area);
+            }
+        }
+        AppMode::Play => draw_play(f, app, content_area),
+        AppMode::Seed => {
+            draw_input(f, app, content_area);
+            draw_seed_prompt(f, app, content_area);
+        }
+        AppMode::GoalTiles => {
+            draw_input(f, app, content_area);
+            draw_goal_tiles_prompt(f, app, content_area);
+        }
+        AppMode::Versus => {
+            if let Some(versus) = &app.versus {
+                draw_versus(f, versus, &app.theme.palette(), content_area);
+            }
+        }
+        AppMode::History => draw_history(f, app, content_area),
+        AppMode::Analytics => draw_analytics(f, &app.history, &app.theme.palette(), content_area),
+        AppMode::Leaderboard => draw_leaderboard(f, app, content_area),
+        AppMode::Jobs => draw_jobs(f, app, content_area),
+        AppMode::Presets => draw_presets(f, app, content_area),
+        AppMode::Agent => {
+            if let Some(agent) = &app.agent {
+                draw_agent(f, app, agent, content_area);
+            }
+        }
+        AppMode::Race => {
+            if let Some(race) = &app.race {
+                draw_race(f, race, &app.theme.palette(), app.ascii, content_area);
+            }
+        }
+    }
+    if let Some(report) = &app.crash {
+        let palette = app.theme.palette();
+        draw_crash(f, report, app.crash_msg.as_deref(), &palette, content_area);
     }
 }
 
-fn draw_board(
+fn draw_tutorial(f: &mut Frame, tutorial: &Tutorial, palette: &Palette, area: Rect) {
+    let p = Paragraph::new(tutorial.text())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(palette.selected)
+                .title(format!(
+                    "Tour {}/{} (T in the editor ends it)",
+                    tutorial.lesson.number(),
+                    Lesson::ALL.len()
+                )),
+        )
+        .wrap(Wrap { trim: true });
+    f.render_widget(p, area);
+}
+
+fn draw_crash(
     f: &mut Frame,
-    board: &Board,
+    report: &crash::Report,
+    msg: Option<&str>,
+    palette: &Palette,
     area: ratatui::layout::Rect,
-    highlight_pos: Option<(usize, usize)>,
 ) {
-    // Create a 3x3 layout centered in the area
-    let layout_v = Layout::default()
+    let popup = area.centered(Constraint::Percentage(90), Constraint::Percentage(90));
+    let mut text = format!(
+        "Thread '{}' panicked at {}:\n{}\n\nThe puzzle was left as it was. Copy this report to share it.\n",
+        report.thread, report.location, report.message
+    );
+    if let Some(msg) = msg {
+        text.push_str(&format!("{}\n", msg));
+    }
+    text.push_str(&format!("\nStack backtrace:\n{}", report.backtrace));
+    let p = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Search crashed")
+                .style(palette.error),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, popup);
+    f.render_widget(p, popup);
+}
+
+fn draw_history(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let items: Vec<ListItem> = app
+        .history
+        .entries
+        .iter()
+        .rev()
+        .map(|entry| {
+            let moves = notation::parse_lurd(&entry.solution.moves).map_or(0, |m| m.len());
+            ListItem::new(format!(
+                "{} ({}) | {} moves | {:.1} ms | {}",
+                entry.solution.start,
+                entry.board_size,
+                moves,
+                entry.solution.elapsed_ms,
+                entry.settings()
+            ))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("History (newest first)"),
+        )
+        .highlight_style(app.theme.palette().selected)
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, &mut app.history_list);
+}
+
+// Search time and nodes of the latest solves, then the averages of each of
+// the settings used.
+fn draw_analytics(f: &mut Frame, history: &History, palette: &Palette, area: Rect) {
+    let stats = history.stats_by_settings();
+    let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Length(ANALYTICS_SPARKLINE_HEIGHT),
+                Constraint::Length(ANALYTICS_SPARKLINE_HEIGHT),
+                Constraint::Min(0),
             ]
             .as_ref(),
         )
         .split(area);
 
-    for r in 0..3 {
-        let layout_h = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(
-                [
-                    Constraint::Length(7),
-                    Constraint::Length(7),
-                    Constraint::Length(7),
-                ]
-                .as_ref(),
-            )
-            .split(layout_v[r]);
+    let entries = &history.entries;
+    let solves = entries.len() as f64;
+    let total = |value: fn(&HistoryEntry) -> f64| entries.iter().map(value).sum::<f64>();
+    let overview = format!(
+        "{} solves | {:.1} moves, {:.0} nodes, {:.1} ms on average",
+        entries.len(),
+        total(|e| e.solution.moves.len() as f64) / solves,
+        total(|e| e.solution.expanded as f64) / solves,
+        total(|e| e.solution.elapsed_ms) / solves,
+    );
+    let overview = Paragraph::new(overview)
+        .style(palette.label)
+        .alignment(Alignment::Center);
+    f.render_widget(overview, chunks[0]);
 
-        for c in 0..3 {
-            let cell_value = match board.b[r][c] {
-                Some(v) => v.to_string(),
-                None => " ".to_string(),
+    // as many of the latest solves as fit, oldest on the left
+    let fits = chunks[1].width.saturating_sub(2) as usize;
+    let latest = &entries[entries.len().saturating_sub(fits)..];
+    let times: Vec<u64> = latest
+        .iter()
+        .map(|e| e.solution.elapsed_ms.round() as u64)
+        .collect();
+    let nodes: Vec<u64> = latest.iter().map(|e| e.solution.expanded as u64).collect();
+    for (title, data, area) in [
+        ("Search time (ms)", &times, chunks[1]),
+        ("Nodes expanded", &nodes, chunks[2]),
+    ] {
+        let max = data.iter().max().copied().unwrap_or(0);
+        let sparkline = Sparkline::default()
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "{}, last {} solves, up to {}",
+                title,
+                data.len(),
+                max
+            )))
+            .data(data)
+            .style(palette.progress);
+        f.render_widget(sparkline, area);
+    }
+
+    let bars: Vec<Bar> = stats
+        .iter()
+        .map(|s| {
+            return Bar::default()
+                .value(s.elapsed_ms.round() as u64)
+                .text_value(format!(
+                    "{:.1} ms, {} nodes, {} solve{}",
+                    s.elapsed_ms,
+                    compact_count(s.expanded.round() as usize),
+                    s.solves,
+                    if s.solves == 1 { "" } else { "s" }
+                ))
+                .label(Line::from(s.settings.clone()));
+        })
+        .collect();
+    let chart = BarChart::horizontal(bars)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Average search time by settings"),
+        )
+        .bar_width(1)
+        .bar_gap(0)
+        .bar_style(palette.progress)
+        .value_style(palette.label);
+    f.render_widget(chart, chunks[3]);
+}
+
+fn draw_leaderboard(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let items: Vec<ListItem> = app
+        .leaderboard
+        .ranked()
+        .into_iter()
+        .map(|record| {
+            let puzzle = match (record.seed, record.scramble_moves) {
+                (Some(seed), Some(moves)) => format!("seed {} ({} scramble moves)", seed, moves),
+                _ => record.start.clone(),
+            };
+            let difficulty = match record.optimal {
+                Some(optimal) => format!("optimal {}", optimal),
+                None => "optimal ?".to_string(),
             };
+            ListItem::new(format!(
+                "{} ({}) | {} | best time {} ({} moves) | fewest moves {} ({}) | solved {}x",
+                puzzle,
+                record.board_size,
+                difficulty,
+                leaderboard::format_time(record.best_time()),
+                record.best_time_moves,
+                record.fewest_moves,
+                leaderboard::format_time(record.fewest_moves_time()),
+                record.solves
+            ))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Leaderboard (hardest first)"),
+        )
+        .highlight_style(app.theme.palette().selected)
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, &mut app.leaderboard_list);
+}
 
-            let mut style = Style::default().fg(Color::White);
-            let mut border_style = Style::default();
+fn draw_presets(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let items: Vec<ListItem> = app
+        .presets
+        .iter()
+        .map(|(puzzle, board)| {
+            let mut line = format!("{} | {}", puzzle.title(), board.code());
+            if let Some(optimal) = puzzle.optimal {
+                line.push_str(&format!(" | {} moves", optimal));
+            }
+            if let Some(author) = &puzzle.author {
+                line.push_str(&format!(" | by {}", author));
+            }
+            if !puzzle.tags.is_empty() {
+                line.push_str(&format!(" | {}", puzzle.tags.join(", ")));
+            }
+            return ListItem::new(line);
+        })
+        .collect();
+    let title = match app.presets_name.is_empty() {
+        true => "Presets".to_string(),
+        false => format!("Presets: {}", app.presets_name),
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(title),
+        )
+        .highlight_style(app.theme.palette().selected)
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, &mut app.presets_list);
+}
 
-            // Highlight cursor if in Input mode
-            if let Some((hr, hc)) = highlight_pos
-                && r == hr
-                && c == hc
-            {
-                style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
-                border_style = border_style.fg(Color::Yellow);
+fn draw_jobs(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let items: Vec<ListItem> = app
+        .jobs
+        .iter()
+        .enumerate()
+        .map(|(i, job)| {
+            let detail = match &job.outcome {
+                Outcome::Failed(msg) => msg.clone(),
+                _ => job.status(),
+            };
+            let mut settings = job.algorithm.to_string();
+            if job.algorithm.is_informed() {
+                settings.push_str(&format!(", {}", job.config.heuristic));
             }
+            ListItem::new(format!(
+                "{}: {} | {} | {}",
+                i + 1,
+                job.board.code(),
+                settings,
+                detail
+            ))
+        })
+        .collect();
+    let running = app.jobs.iter().filter(|job| job.is_running()).count();
+    let mut title = format!("Background searches ({} running", running);
+    let queued = app.jobs.iter().filter(|job| job.is_waiting()).count();
+    if queued > 0 {
+        title.push_str(&format!(", {} queued", queued));
+    }
+    title.push(')');
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(title),
+        )
+        .highlight_style(app.theme.palette().selected)
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, &mut app.jobs_list);
+}
+
+// One tab per background search, above the board being edited.
+fn draw_job_tabs(f: &mut Frame, jobs: &[Job], palette: &Palette, area: ratatui::layout::Rect) {
+    let titles: Vec<String> = jobs
+        .iter()
+        .enumerate()
+        .map(|(i, job)| format!("{}: {}", i + 1, job.status()))
+        .collect();
+    let tabs = Tabs::new(titles).select(None).style(palette.notice);
+    f.render_widget(tabs, area);
+}
+
+fn draw_versus(f: &mut Frame, versus: &Versus, palette: &Palette, area: ratatui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)].as_ref())
+        .split(area);
+    let heading = Paragraph::new(format!("Board: {}", versus.board.code()))
+        .alignment(Alignment::Center)
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    f.render_widget(heading, chunks[0]);
 
-            // Highlight 'None' (empty tile) distinctively in result view
-            if board.b[r][c].is_none() {
-                style = style.bg(Color::DarkGray);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1); 2].as_ref())
+        .spacing(1)
+        .split(chunks[1]);
+    for (i, (side, column)) in versus.sides.iter().zip(columns.iter()).enumerate() {
+        let mut lines = vec![side.label(), String::new()];
+        match &side.outcome {
+            Outcome::Waiting => lines.push("Press Enter to solve".to_string()),
+            Outcome::Running => lines.push("Searching...".to_string()),
+            Outcome::Failed(msg) => lines.push(msg.clone()),
+            Outcome::Solved(solution) => {
+                lines.push(format!("Solution length: {} moves", solution.moves()));
+                lines.push(format!("Nodes expanded: {}", solution.stats.expanded));
+                lines.push(format!(
+                    "Search time: {:.1} ms",
+                    solution.stats.elapsed.as_secs_f64() * 1000.0
+                ));
+                lines.push(match solution.guaranteed_optimal {
+                    true => "Optimal".to_string(),
+                    false => "Not guaranteed optimal".to_string(),
+                });
+                lines.push(String::new());
+                lines.push(notation::to_letters(&solution.directions));
             }
+        }
+
+        let border_style = match i == versus.focus {
+            true => palette.selected,
+            false => Style::default(),
+        };
+        let title = if i == 0 { "Left" } else { "Right" };
+        let p = Paragraph::new(lines.join("\n"))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(border_style)
+                    .title(title),
+            )
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false });
+        f.render_widget(p, *column);
+    }
+}
+
+fn draw_too_small(f: &mut Frame, area: ratatui::layout::Rect, palette: &Palette) {
+    let text = format!(
+        "Terminal too small ({}x{}), need at least {}x{}",
+        area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+    );
+    let p = Paragraph::new(text)
+        .style(palette.error)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(p, area.centered_vertically(Constraint::Length(2)));
+}
+
+fn draw_board(
+    f: &mut Frame,
+    board: &Board,
+    area: ratatui::layout::Rect,
+    highlight: Option<((usize, usize), Style)>, // a cell and how to show it
+    goal: &GoalContext,
+    palette: &Palette,
+    ascii: bool,
+) {
+    let cells = cell_areas(board, area);
+    for r in 0..board.rows() {
+        for c in 0..board.cols() {
+            // Highlight the cursor in Input mode, the moved tile in the step viewer
+            let cell_highlight = highlight
+                .filter(|((hr, hc), _)| (*hr, *hc) == (r, c))
+                .map(|(_, style)| style);
+            let cell_area = cells[r * board.cols() + c];
+            let tile = board.get(r, c);
+            let in_place = tile.is_some_and(|n| goal.is_in_place(n, (r, c)));
+            draw_tile(f, tile, cell_area, cell_highlight, in_place, palette, ascii);
+        }
+    }
+}
+
+// Where each cell of the board goes, row by row, scaled to the available
+// space and centered in it.
+fn cell_areas(board: &Board, area: ratatui::layout::Rect) -> Vec<ratatui::layout::Rect> {
+    let (rows, cols) = (board.rows() as u16, board.cols() as u16);
+    let cell_w = (area.width / cols).clamp(MIN_CELL_WIDTH, MAX_CELL_WIDTH);
+    let cell_h = (area.height / rows).clamp(MIN_CELL_HEIGHT, MAX_CELL_HEIGHT);
+    let board_area = area.centered(
+        Constraint::Length(cell_w * cols),
+        Constraint::Length(cell_h * rows),
+    );
+
+    let layout_v = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(cell_h); board.rows()])
+        .split(board_area);
+    return layout_v
+        .iter()
+        .flat_map(|row| {
+            let layout_h = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Length(cell_w); board.cols()])
+                .split(*row);
+            return layout_h.to_vec();
+        })
+        .collect();
+}
 
-            let p = Paragraph::new(cell_value)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .border_type(BorderType::Rounded)
-                        .border_style(border_style),
-                )
-                .alignment(Alignment::Center)
-                .style(style);
-
-            f.render_widget(p, layout_h[c]);
+fn draw_tile(
+    f: &mut Frame,
+    tile: Option<i64>,
+    area: ratatui::layout::Rect,
+    highlight: Option<Style>,
+    in_place: bool,
+    palette: &Palette,
+    ascii: bool,
+) {
+    // Pad with empty lines so the number sits in the middle of the cell
+    let padding = "\n".repeat((area.height.saturating_sub(2) / 2) as usize);
+    let cell_value = match tile {
+        Some(v) => format!("{}{}", padding, v),
+        None => " ".to_string(),
+    };
+
+    let mut style = palette.tile;
+    if in_place {
+        style = style.patch(palette.in_place);
+    }
+    let mut border_style = Style::default();
+    let mut border_set = match ascii {
+        true => ascii::BORDER,
+        false => BorderType::Rounded.to_border_set(),
+    };
+    if let Some(highlight) = highlight {
+        style = style.patch(highlight);
+        border_style.fg = highlight.fg;
+        if ascii {
+            border_set = ascii::CURSOR_BORDER;
         }
     }
+
+    // Highlight 'None' (empty tile) distinctively
+    if tile.is_none() {
+        style = match ascii {
+            true => style.add_modifier(Modifier::REVERSED),
+            false => style.patch(palette.blank),
+        };
+    }
+
+    let p = Paragraph::new(cell_value)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_set(border_set)
+                .border_style(border_style),
+        )
+        .alignment(Alignment::Center)
+        .style(style);
+    f.render_widget(p, area);
+}
+
+// `to` with the tile that moved from `from` part of the way, `progress` from
+// 0 to 1, across the gap between the two cells.
+fn draw_sliding_board(
+    f: &mut Frame,
+    (from, progress): (&Board, f64), // the board it slides from, and how far along
+    to: &Board,
+    area: ratatui::layout::Rect,
+    goal: &GoalContext,
+    palette: &Palette,
+    ascii: bool,
+) {
+    let (Some(source), Some(target)) = (to.find_empty_cell(), from.find_empty_cell()) else {
+        draw_board(f, to, area, None, goal, palette, ascii);
+        return;
+    };
+    let tile = to.get(target.0, target.1);
+    let mut under = *to;
+    under.set(target.0, target.1, None);
+    draw_board(f, &under, area, None, goal, palette, ascii);
+
+    let cells = cell_areas(to, area);
+    let (start, end) = (
+        cells[source.0 * to.cols() + source.1],
+        cells[target.0 * to.cols() + target.1],
+    );
+    let lerp = |a: u16, b: u16| (a as f64 + (b as f64 - a as f64) * progress).round() as u16;
+    let mut tile_area = start;
+    tile_area.x = lerp(start.x, end.x);
+    tile_area.y = lerp(start.y, end.y);
+    f.render_widget(Clear, tile_area);
+    // in place once it lands where the blank was
+    let in_place = tile.is_some_and(|n| goal.is_in_place(n, target));
+    draw_tile(
+        f,
+        tile,
+        tile_area,
+        Some(palette.moved),
+        in_place,
+        palette,
+        ascii,
+    );
 }
 
 fn draw_input(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(10), Constraint::Min(0)].as_ref())
-        .split(area);
-
-    // Center the board area
-    let board_area_centered = Layout::default()
-        .direction(Direction::Horizontal)
         .constraints(
             [
-                Constraint::Percentage(40),
-                Constraint::Length(21),
-                Constraint::Percentage(40),
+                Constraint::Length(app.jobs.len().min(1) as u16),
+                Constraint::Min(9),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(2),
             ]
             .as_ref(),
         )
-        .split(chunks[0])[1];
+        .split(area);
 
-    draw_board(
-        f,
-        &app.input_board,
-        board_area_centered,
-        Some(app.cursor_pos),
+    draw_job_tabs(f, &app.jobs, &app.theme.palette(), chunks[0]);
+    let palette = app.theme.palette();
+    let goal = app.search_goal();
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1); 2].as_ref())
+        .spacing(1)
+        .split(chunks[1]);
+    let grids = [
+        ("Start", &app.input_board, app.cursor_pos, !app.editing_goal),
+        ("Goal", &app.goal_input, app.goal_cursor, app.editing_goal),
+    ];
+    // the cursor only shows in the grid Tab has focused
+    for ((label, board, cursor, focused), column) in grids.into_iter().zip(columns.iter()) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(9)].as_ref())
+            .split(*column);
+        let label_p = Paragraph::new(label)
+            .alignment(Alignment::Center)
+            .style(match focused {
+                true => palette.selected,
+                false => palette.label,
+            });
+        f.render_widget(label_p, rows[0]);
+        let highlight = Some((cursor, palette.cursor)).filter(|_| focused);
+        draw_board(f, board, rows[1], highlight, &goal, &palette, app.ascii);
+    }
+
+    let mut settings = format!(
+        "Heuristic: {} | Weight: {}",
+        app.search_config.heuristic.name(),
+        app.search_config.weight
     );
+    if app.algorithm == Algorithm::Beam {
+        settings.push_str(&format!(" | Beam width: {}", app.search_config.beam_width));
+    }
+    if app.search_config.cost != CostModel::Unit {
+        settings.push_str(&format!(" | Cost: {}", app.search_config.cost));
+    }
+    let mut goal = Vec::new();
+    let preset = app
+        .goal_preset
+        .board(app.input_board.rows(), app.input_board.cols());
+    // a goal typed in, rotated or mirrored is no preset any more
+    match *app.goal.goal() == preset {
+        true if app.goal_preset != GoalPreset::Standard => goal.push(app.goal_preset.to_string()),
+        true => {}
+        false => goal.push("custom".to_string()),
+    }
+    if !app.goal_tiles.is_empty() {
+        goal.push(format!("tiles {}", join_tiles(&app.goal_tiles)));
+    }
+    if !goal.is_empty() {
+        settings.push_str(&format!(" | Goal: {}", goal.join(", ")));
+    }
+    if app.algorithm == Algorithm::AStar && app.search_config.actions == ActionSet::Macro {
+        settings.push_str(" | Macro moves");
+    }
+    if app.algorithm == Algorithm::AStar && app.search_config.symmetry {
+        settings.push_str(" | Symmetry");
+    }
+    if !app.algorithm.is_optimal(&app.search_config) {
+        settings.push_str(" (not guaranteed optimal)");
+    }
+    if app.visualize {
+        settings.push_str(" | Visualize");
+    }
+    settings.push_str(&format!(" | Theme: {}", app.theme));
+    // only while the board is still the scrambled one
+    if let Some(seed) = app.scramble_seed(&app.input_board) {
+        settings.push_str(&format!(" | Seed: {}", seed));
+    }
+    let settings_widget = Paragraph::new(settings).alignment(Alignment::Center);
+    f.render_widget(settings_widget, chunks[2]);
+
+    if let Some(difficulty) = app.difficulty() {
+        let difficulty_widget = Paragraph::new(difficulty)
+            .style(app.theme.palette().label)
+            .alignment(Alignment::Center);
+        f.render_widget(difficulty_widget, chunks[3]);
+    }
 
     if let Some(err) = &app.error_msg {
         let err_widget = Paragraph::new(format!("Error: {}", err))
-            .style(Style::default().fg(Color::Red))
-            .alignment(Alignment::Center);
-        f.render_widget(err_widget, chunks[1]);
+            .style(app.theme.palette().error)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(err_widget, chunks[4]);
+    } else if let Some(note) = app.known_board() {
+        let note_widget = Paragraph::new(note)
+            .style(app.theme.palette().notice)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(note_widget, chunks[4]);
     }
 }
 
 fn draw_searching(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    if app.visualize
+        && let Some(progress) = &app.progress
+    {
+        let goal = app.search_goal();
+        draw_visualization(f, progress, area, &goal, &app.theme.palette(), app.ascii);
+        return;
+    }
+
     let spinners = ["|", "/", "-", "\\"];
     let spinner = spinners[app.spinner_idx];
 
-    let text = format!("Solving... {}", spinner);
+    let text = match app.is_paused() {
+        true => "Paused".to_string(),
+        false => format!("Solving... {}", spinner),
+    };
 
     let p = Paragraph::new(text)
-        .style(
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )
+        .style(app.theme.palette().notice.add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::NONE));
 
@@ -404,52 +1114,825 @@ fn draw_searching(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
 
     f.render_widget(p, v_layout[1]);
 
-    // Render a "Progress Bar" (Indeterminate)
+    // how much closer to the goal the search has got, by the heuristic
+    let percent = app.estimate.percent().min(100);
+    let label = match (app.is_paused(), app.estimate.remaining()) {
+        (true, _) => "Paused, press p to resume".to_string(),
+        (false, None) => format!("{}%, estimating...", percent),
+        (false, Some(remaining)) => {
+            format!("{}%, {} left", percent, eta::format_remaining(remaining))
+        }
+    };
     let gauge = Gauge::default()
         .block(Block::default().borders(Borders::ALL).title("Searching"))
-        .gauge_style(Style::default().fg(Color::Green))
-        .percent(100) // Since we can't track A* progress inside the function, we just show a full bar or pulsing
-        .label("Computing Path...");
+        .gauge_style(app.theme.palette().progress)
+        .percent(percent)
+        .label(label);
 
     f.render_widget(gauge, v_layout[1]);
+
+    if let Some(progress) = &app.progress {
+        let below = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(2),
+                    Constraint::Max(FRONTIER_CHART_HEIGHT),
+                ]
+                .as_ref(),
+            )
+            .split(v_layout[2]);
+        let memory = Paragraph::new(memory_usage(progress.memory, app.max_memory_mb))
+            .style(app.theme.palette().label)
+            .alignment(Alignment::Center);
+        f.render_widget(memory, below[0]);
+        draw_frontier(f, &progress.frontier_f, below[1], &app.theme.palette());
+    }
 }
 
-fn draw_result(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
-    let board = &app.solution_path[app.current_step];
+// Bars of the f values waiting in the queue, lowest first: the search
+// expands the leftmost bar, and a good heuristic keeps the bars few and short.
+fn draw_frontier(f: &mut Frame, histogram: &FrontierHistogram, area: Rect, palette: &Palette) {
+    if histogram.is_empty() {
+        return;
+    }
+    let bars: Vec<Bar> = histogram
+        .counts
+        .iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let f = histogram.lowest + i as i64;
+            let label = match i + 1 == HISTOGRAM_BARS {
+                true => format!("{}+", f),
+                false => f.to_string(),
+            };
+            return Bar::default()
+                .value(*count as u64)
+                .text_value(compact_count(*count))
+                .label(Line::from(label));
+        })
+        .collect();
+    let width = (HISTOGRAM_BARS as u16 * (FRONTIER_BAR_WIDTH + 1) + 1).min(area.width);
+    let area = Rect {
+        x: area.x + (area.width - width) / 2,
+        width,
+        ..area
+    };
+    let chart = BarChart::vertical(bars)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Frontier by f"),
+        )
+        .bar_width(FRONTIER_BAR_WIDTH)
+        .bar_gap(1)
+        .bar_style(palette.progress)
+        .value_style(palette.progress.add_modifier(Modifier::REVERSED));
+    f.render_widget(chart, area);
+}
 
+// e.g. 950, 12k, 3.4M: short enough for a bar
+fn compact_count(count: usize) -> String {
+    return match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{}k", count / 1_000),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    };
+}
+
+// e.g. "Memory: ~12.3 MB of 1024 MB"
+fn memory_usage(bytes: usize, max_mb: u64) -> String {
+    let used = format!("Memory: ~{:.1} MB", bytes as f64 / MEGABYTE as f64);
+    return match max_mb {
+        0 => used,
+        max => format!("{} of {} MB", used, max),
+    };
+}
+
+fn draw_visualization(
+    f: &mut Frame,
+    progress: &Progress,
+    area: ratatui::layout::Rect,
+    goal: &GoalContext,
+    palette: &Palette,
+    ascii: bool,
+) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
             [
                 Constraint::Length(2),
-                Constraint::Length(10),
-                Constraint::Min(0),
+                Constraint::Min(9),
+                Constraint::Length(3),
+                Constraint::Length(match progress.frontier_f.is_empty() {
+                    true => 0,
+                    false => FRONTIER_CHART_HEIGHT,
+                }),
             ]
             .as_ref(),
         )
         .split(area);
 
-    let step_info = format!(
-        "Step {} / {}",
-        app.current_step + 1,
-        app.solution_path.len()
+    let stats = format!(
+        "Expanding: f = {} | g = {} | h = {}\nFrontier: {} | Expanded: {}",
+        progress.f, progress.g, progress.h, progress.frontier, progress.expanded
     );
+    let stats_p = Paragraph::new(stats)
+        .alignment(Alignment::Center)
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    f.render_widget(stats_p, chunks[0]);
+
+    draw_board(f, &progress.board, chunks[1], None, goal, palette, ascii);
+
+    // How much of the initial estimate the best board so far has covered
+    let heat = if progress.initial_h > 0 {
+        100 * (progress.initial_h - progress.best_h) / progress.initial_h
+    } else {
+        100
+    };
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Closest to goal"),
+        )
+        .gauge_style(palette.heat)
+        .percent(heat as u16)
+        .label(format!("best h = {}", progress.best_h));
+    f.render_widget(gauge, chunks[2]);
+    draw_frontier(f, &progress.frontier_f, chunks[3], palette);
+}
+
+fn draw_result(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let detailed = area.width >= COMMENTARY_MIN_WIDTH;
+    let list_width = if detailed {
+        COMMENTARY_WIDTH
+    } else {
+        STEP_LIST_WIDTH
+    };
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(list_width)].as_ref())
+        .spacing(1)
+        .split(area);
+    draw_step_list(f, app, columns[1], detailed);
+    let area = columns[0];
+    let path = &app.solution.path;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(2),
+                Constraint::Min(10),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(2),
+                Constraint::Length(2),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let phases = explain::phases(&app.solution);
+    let mut step_info = format!("Step {} / {}", app.current_step + 1, path.len());
+    if let Some(phase) = explain::phase_at(&phases, app.current_step) {
+        step_info.push_str(&format!(" - {}", phase.label));
+    }
+    if let Some(step) = app.solution.steps.get(app.current_step) {
+        step_info.push_str(&format!(
+            "\ng = {} | h = {} | f = {}",
+            step.g,
+            step.h,
+            step.g + step.h
+        ));
+        // the move actually taken is marked with a star
+        let taken = app.solution.directions.get(app.current_step).copied();
+        if app.show_options && taken.is_some() {
+            let options: Vec<String> = step
+                .options
+                .iter()
+                .map(|(m, h)| {
+                    let mark = if Some(*m) == taken { "*" } else { "" };
+                    format!("{}{}:{}", m.letter(), mark, h)
+                })
+                .collect();
+            step_info.push_str(&format!(" | Next: {}", options.join(" ")));
+        }
+    }
     let info_p = Paragraph::new(step_info)
         .alignment(Alignment::Center)
         .style(Style::default().add_modifier(Modifier::BOLD));
     f.render_widget(info_p, chunks[0]);
 
-    let board_area_centered = Layout::default()
+    // Start, current step, and goal side by side
+    let columns = Layout::default()
         .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1); 3].as_ref())
+        .spacing(1)
+        .split(chunks[1]);
+    let palette = app.theme.palette();
+    let goal = app.search_goal();
+    // the tile that just moved sits where the blank was a step before
+    let moved = app
+        .current_step
+        .checked_sub(1)
+        .and_then(|previous| path[previous].find_empty_cell())
+        .map(|pos| (pos, palette.moved));
+    let sliding = app
+        .slide
+        .as_ref()
+        .filter(|slide| slide.from.abs_diff(app.current_step) == 1 && slide.from < path.len())
+        .map(|slide| {
+            let progress = slide.frame as f64 / SLIDE_FRAMES as f64;
+            return (&path[slide.from], progress);
+        });
+    let boards = [
+        ("Start", &path[0], None),
+        ("Current", &path[app.current_step], moved),
+        ("Goal", &path[path.len() - 1], None),
+    ];
+    for ((label, board, highlight), column) in boards.into_iter().zip(columns.iter()) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(9)].as_ref())
+            .split(*column);
+        let label_p = Paragraph::new(label)
+            .alignment(Alignment::Center)
+            .style(palette.label);
+        f.render_widget(label_p, rows[0]);
+        match sliding.filter(|_| label == "Current") {
+            Some(slide) => {
+                draw_sliding_board(f, slide, board, rows[1], &goal, &palette, app.ascii);
+            }
+            None => draw_board(f, board, rows[1], highlight, &goal, &palette, app.ascii),
+        }
+    }
+
+    // How far along the solution the current step is
+    let moves = app.solution.moves();
+    let ratio = match moves {
+        0 => 1.0,
+        _ => app.current_step as f64 / moves as f64,
+    };
+    let gauge = Gauge::default()
+        .gauge_style(palette.progress)
+        .ratio(ratio)
+        .label(format!("Move {} / {}", app.current_step, moves));
+    f.render_widget(gauge, chunks[2]);
+
+    if app.attract.is_some() {
+        let demo = Paragraph::new("Demo: a random scramble, solved")
+            .style(palette.label)
+            .alignment(Alignment::Center);
+        f.render_widget(demo, chunks[3]);
+    } else if let Some(playback) = &app.playback {
+        let replay = Paragraph::new(format!(
+            "Replay {} / {} | Space: {}",
+            leaderboard::format_time(playback.time_at(app.current_step)),
+            leaderboard::format_time(playback.length()),
+            match playback.is_paused() || app.current_step >= app.solution.moves() {
+                true => "Play",
+                false => "Pause",
+            }
+        ))
+        .style(palette.label)
+        .alignment(Alignment::Center);
+        f.render_widget(replay, chunks[3]);
+    } else if !app.solution.guaranteed_optimal {
+        let warning = Paragraph::new("Not guaranteed optimal: a shorter solution may exist")
+            .style(palette.notice)
+            .alignment(Alignment::Center);
+        f.render_widget(warning, chunks[3]);
+    }
+
+    let lurd = format!(
+        "Moves (LURD): {}",
+        notation::to_letters(&app.solution.directions)
+    );
+    let lurd_p = Paragraph::new(lurd)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false });
+    f.render_widget(lurd_p, chunks[4]);
+
+    if let Some(msg) = &app.status_msg {
+        let status_p = Paragraph::new(msg.as_str())
+            .style(palette.notice)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(status_p, chunks[5]);
+    }
+}
+
+fn draw_play(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let play = &app.play;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Percentage(40),
-                Constraint::Length(21),
-                Constraint::Percentage(40),
+                Constraint::Length(2),
+                Constraint::Min(9),
+                Constraint::Length(2),
+                Constraint::Length(3),
             ]
             .as_ref(),
         )
-        .split(chunks[1])[1];
+        .split(area);
 
-    draw_board(f, board, board_area_centered, None);
+    let time = leaderboard::format_time(play.elapsed());
+    let mut info = format!(
+        "Moves: {} | Time: {} | Macros used: {}",
+        play.moves.len(),
+        time,
+        play.macros_used
+    );
+    if play.is_solved() && play.solver_moves > 0 {
+        info = format!(
+            "Solved in {} moves ({} by the solver)",
+            play.moves.len(),
+            play.solver_moves
+        );
+    } else if play.is_solved() {
+        info = format!("Solved in {} moves and {}!", play.moves.len(), time);
+    } else if let Some(recording) = &play.recording {
+        info.push_str(&format!("\nRecording: {}", notation::to_letters(recording)));
+    }
+    let mut info_text = Text::from(info);
+    if let Some(review) = &play.review {
+        // the moves played, with the first detour marked
+        let detour = review.first_detour.as_ref().map(|d| d.index);
+        let spans: Vec<Span> = play
+            .moves
+            .iter()
+            .enumerate()
+            .map(|(i, m)| match detour == Some(i) {
+                true => Span::styled(m.to_string(), app.theme.palette().error),
+                false => Span::raw(m.to_string()),
+            })
+            .collect();
+        info_text.push_line(Line::from(spans));
+    }
+    let info_p = Paragraph::new(info_text)
+        .alignment(Alignment::Center)
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    f.render_widget(info_p, chunks[0]);
+
+    draw_board(
+        f,
+        &play.board,
+        chunks[1],
+        None,
+        &play.goal,
+        &app.theme.palette(),
+        app.ascii,
+    );
+
+    let macros: Vec<String> = play
+        .macros
+        .iter()
+        .enumerate()
+        .filter_map(|(i, m)| Some(format!("{}: {}", i + 1, notation::to_letters(m.as_ref()?))))
+        .collect();
+    let macros_p = Paragraph::new(format!("Macros  {}", macros.join("  ")))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(macros_p, chunks[2]);
+
+    if let Some(msg) = &play.message {
+        let msg_p = Paragraph::new(msg.as_str())
+            .style(app.theme.palette().notice)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(msg_p, chunks[3]);
+    }
+}
+
+fn draw_agent(f: &mut Frame, app: &App, agent: &Agent, area: ratatui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(9),
+                Constraint::Length(2),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let status = match agent.is_solved() {
+        true => format!(
+            "Trial {}: reached the goal in {} moves",
+            agent.trial,
+            agent.moves()
+        ),
+        false => format!("Trial {} | Moves: {}", agent.trial, agent.moves()),
+    };
+    let info = format!(
+        "{}\nh = {} (learned for {} boards, raised {} times)",
+        status,
+        agent.h(&agent.board),
+        agent.learned(),
+        agent.updates
+    );
+    let info_p = Paragraph::new(info)
+        .alignment(Alignment::Center)
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    f.render_widget(info_p, chunks[0]);
+
+    draw_board(
+        f,
+        &agent.board,
+        chunks[1],
+        None,
+        agent.goal(),
+        &app.theme.palette(),
+        app.ascii,
+    );
+
+    // earlier trials get shorter as the agent learns
+    if !agent.finished.is_empty() {
+        let trials: Vec<String> = agent.finished.iter().map(|m| m.to_string()).collect();
+        let trials_p = Paragraph::new(format!("Earlier trials: {} moves", trials.join(", ")))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(trials_p, chunks[2]);
+    }
+}
+
+fn draw_race(
+    f: &mut Frame,
+    race: &Race,
+    palette: &Palette,
+    ascii: bool,
+    area: ratatui::layout::Rect,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(2),
+                Constraint::Min(10),
+                Constraint::Length(3),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let info = format!(
+        "You: {} moves | Solver: {} / {} moves",
+        race.player_moves,
+        race.solver_step,
+        race.optimal()
+    );
+    let info_p = Paragraph::new(info)
+        .alignment(Alignment::Center)
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    f.render_widget(info_p, chunks[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1); 2].as_ref())
+        .spacing(1)
+        .split(chunks[1]);
+    let sides = [("You", race.player), ("Solver", race.solver_board())];
+    for ((label, board), column) in sides.into_iter().zip(columns.iter()) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(9)].as_ref())
+            .split(*column);
+        let label_p = Paragraph::new(label)
+            .alignment(Alignment::Center)
+            .style(palette.label);
+        f.render_widget(label_p, rows[0]);
+        draw_board(f, &board, rows[1], None, &race.goal, palette, ascii);
+    }
+
+    let (banner, style) = match race.winner {
+        Some(Winner::Player) => (
+            format!(
+                "You win! Solved in {} moves, the shortest solution has {}.",
+                race.player_moves,
+                race.optimal()
+            ),
+            palette.selected,
+        ),
+        Some(Winner::Solver) => (
+            format!(
+                "The solver wins. Its {} moves: {}",
+                race.optimal(),
+                race.solver_moves()
+            ),
+            palette.error,
+        ),
+        None if !race.is_started() => (
+            "The solver starts with your first move.".to_string(),
+            palette.notice,
+        ),
+        None => (race.message.clone().unwrap_or_default(), palette.notice),
+    };
+    let banner_p = Paragraph::new(banner)
+        .style(style)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(banner_p, chunks[2]);
+}
+
+// Every board of the solution by the move that led to it, with the current
+// one selected; `detailed` adds what each move achieves.
+fn draw_step_list(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect, detailed: bool) {
+    let path = &app.solution.path;
+    let labels = match detailed {
+        true => explain::commentary(path, &GoalContext::new(path[path.len() - 1])),
+        false => explain::slides(path),
+    };
+
+    let mut items = vec![ListItem::new(" 0. Start")];
+    items.extend(
+        labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| ListItem::new(format!("{:>2}. {}", i + 1, label))),
+    );
+    let title = if detailed { "Commentary" } else { "Steps" };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(title),
+        )
+        .highlight_style(app.theme.palette().selected);
+    // the list keeps the selected step in view
+    app.step_list.select(Some(app.current_step));
+    f.render_stateful_widget(list, area, &mut app.step_list);
+}
+
+fn draw_seed_prompt(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let popup = area.centered(Constraint::Length(30), Constraint::Length(3));
+    let p = Paragraph::new(format!("{}_", app.seed_input))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Scramble from seed"),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(Clear, popup);
+    f.render_widget(p, popup);
+}
+
+fn draw_goal_tiles_prompt(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let popup = area.centered(Constraint::Length(40), Constraint::Length(3));
+    let p = Paragraph::new(format!("{}_", app.goal_tiles_input))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Tiles to put in place"),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(Clear, popup);
+    f.render_widget(p, popup);
+}
+
+fn draw_summary(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let stats = &app.solution.stats;
+    let optimality = if app.solution.guaranteed_optimal {
+        "Optimal"
+    } else {
+        "Not guaranteed optimal"
+    };
+    let mut lines = vec![format!("Solution length: {} moves", app.solution.moves())];
+    if app.solution.shortened > 0 {
+        lines.push(format!(
+            "Shortened by {} moves after the search",
+            app.solution.shortened
+        ));
+    }
+    if app.search_config.cost != CostModel::Unit {
+        lines.push(format!(
+            "Total cost: {} ({})",
+            app.search_config.cost.of_path(&app.solution.path),
+            app.search_config.cost
+        ));
+    }
+    lines.extend([
+        format!(
+            "Search time: {:.1} ms",
+            stats.elapsed.as_secs_f64() * 1000.0
+        ),
+        format!("Nodes expanded: {}", stats.expanded),
+    ]);
+    if stats.symmetric > 0 {
+        lines.push(format!("Symmetric twins skipped: {}", stats.symmetric));
+    }
+    if app.algorithm == Algorithm::AStar && app.search_config.actions == ActionSet::Macro {
+        lines.push(format!(
+            "Nodes generated: {} (with macro moves)",
+            stats.generated
+        ));
+    }
+    lines.extend([
+        format!("Heuristic: {}", app.search_config.heuristic.name()),
+        optimality.to_string(),
+        String::new(),
+        app.status_msg.clone().unwrap_or_default(),
+    ]);
+
+    // Center a fixed-size popup over the result view
+    let popup_v = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Fill(1),
+                Constraint::Length(lines.len() as u16 + 2),
+                Constraint::Fill(1),
+            ]
+            .as_ref(),
+        )
+        .split(area)[1];
+    let popup = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Fill(1),
+                Constraint::Length(44),
+                Constraint::Fill(1),
+            ]
+            .as_ref(),
+        )
+        .split(popup_v)[1];
+
+    let p = Paragraph::new(lines.join("\n"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Solved"),
+        )
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, popup);
+    f.render_widget(p, popup);
+}
+
+// Where the search spent its effort: the boards it took off the queue only to
+// skip them, and the ones it pushed more than once.
+fn draw_diagnostics(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let stats = &app.solution.stats;
+    let share = |count: usize, total: usize| match total {
+        0 => 0.0,
+        total => count as f64 * 100.0 / total as f64,
+    };
+    let popped = stats.expanded + stats.duplicates + stats.symmetric;
+    let lines = [
+        format!("Nodes expanded: {}", stats.expanded),
+        format!("Nodes generated: {}", stats.generated),
+        format!(
+            "Duplicates skipped: {} ({:.1}% of pops)",
+            stats.duplicates,
+            share(stats.duplicates, popped)
+        ),
+        format!(
+            "Twins skipped: {} ({:.1}% of pops)",
+            stats.symmetric,
+            share(stats.symmetric, popped)
+        ),
+        format!(
+            "Re-pushed: {} ({:.1}% of pushes)",
+            stats.repushed,
+            share(stats.repushed, stats.generated)
+        ),
+        format!("Peak queue size: {}", stats.peak_frontier),
+    ];
+
+    let popup = area.centered(
+        Constraint::Length(44),
+        Constraint::Length(lines.len() as u16 + 2),
+    );
+    let p = Paragraph::new(lines.join("\n"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Diagnostics"),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(Clear, popup);
+    f.render_widget(p, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use eight_puzzle_solver::a_star::SearchConfig;
+    use ratatui::backend::TestBackend;
+    use std::time::Duration;
+
+    // The snapshots are plain text, so colors are left out of them.
+    fn app() -> App {
+        return App::new(&Config {
+            ascii: Some(false),
+            ..Config::default()
+        });
+    }
+
+    fn render(app: &mut App, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| draw(f, app)).unwrap();
+        return terminal.backend().to_string();
+    }
+
+    fn board(code: &str) -> Board {
+        return code.parse().unwrap();
+    }
+
+    #[test]
+    fn input_with_cursor() {
+        let mut app = app();
+        app.input_board = board("123456780");
+        app.handle_key(KeyCode::Right);
+        app.handle_key(KeyCode::Down);
+        app.ascii = true; // the cursor only stands out by its color otherwise
+        insta::assert_snapshot!(render(&mut app, 100, 30));
+    }
+
+    #[test]
+    fn input_with_error() {
+        let mut app = app();
+        app.input_board = board("123456780");
+        app.input_board.set(0, 1, Some(1)); // two 1s
+        app.handle_key(KeyCode::Enter);
+        insta::assert_snapshot!(render(&mut app, 100, 30));
+    }
+
+    #[test]
+    fn searching() {
+        let mut app = app();
+        app.mode = AppMode::Searching;
+        app.progress = Some(Progress {
+            board: board("867254301"),
+            parent: None,
+            g: 0,
+            h: 21,
+            f: 21,
+            frontier: 43,
+            frontier_f: FrontierHistogram {
+                lowest: 21,
+                counts: [3, 0, 12, 0, 20, 0, 6, 0, 2, 0, 0, 0],
+            },
+            expanded: 1,
+            initial_h: 21,
+            best_h: 21,
+            memory: 0,
+        });
+        insta::assert_snapshot!(render(&mut app, 100, 30));
+    }
+
+    #[test]
+    fn result_mid_path() {
+        let mut app = app();
+        let start = board("123405786");
+        let mut solution = Algorithm::AStar
+            .solve(start, &app.goal, SearchConfig::default())
+            .solution()
+            .unwrap();
+        solution.stats.elapsed = Duration::ZERO; // the only thing that changes between runs
+        app.solution = solution;
+        app.mode = AppMode::Result;
+        app.current_step = 1;
+        insta::assert_snapshot!(render(&mut app, 100, 30));
+    }
+
+    #[test]
+    fn analytics() {
+        let mut app = app();
+        for (i, heuristic) in ["manhattan", "misplaced", "manhattan"].iter().enumerate() {
+            app.history.push(HistoryEntry {
+                board_size: "3x3".to_string(),
+                algorithm: "a-star".to_string(),
+                heuristic: heuristic.to_string(),
+                weight: 1,
+                solution: session::SavedSolution {
+                    start: "123405786".to_string(),
+                    moves: "RD".to_string(),
+                    guaranteed_optimal: true,
+                    expanded: 100 * (i + 1),
+                    generated: 300 * (i + 1),
+                    elapsed_ms: 1.5 * (i + 1) as f64,
+                },
+            });
+        }
+        app.handle_key(KeyCode::Char('A'));
+        insta::assert_snapshot!(render(&mut app, 100, 30));
+    }
+
+    #[test]
+    fn too_small() {
+        let mut app = app();
+        insta::assert_snapshot!(render(&mut app, 40, 20));
+    }
 }
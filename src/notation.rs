@@ -0,0 +1,156 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::board::Board;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Direction the blank moves in, as used by the standard LURD notation.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Move {
+    Left,
+    Up,
+    Right,
+    Down,
+}
+
+impl Move {
+    pub const ALL: [Move; 4] = [Move::Left, Move::Up, Move::Right, Move::Down];
+
+    pub fn letter(&self) -> char {
+        return match self {
+            Move::Left => 'L',
+            Move::Up => 'U',
+            Move::Right => 'R',
+            Move::Down => 'D',
+        };
+    }
+
+    pub fn from_letter(letter: char) -> Option<Move> {
+        return match letter.to_ascii_uppercase() {
+            'L' => Some(Move::Left),
+            'U' => Some(Move::Up),
+            'R' => Some(Move::Right),
+            'D' => Some(Move::Down),
+            _ => None,
+        };
+    }
+
+    /// The same move on the board turned a quarter clockwise.
+    pub fn rotate_cw(&self) -> Move {
+        return match self {
+            Move::Left => Move::Up,
+            Move::Up => Move::Right,
+            Move::Right => Move::Down,
+            Move::Down => Move::Left,
+        };
+    }
+
+    /// The same move on the board mirrored left to right.
+    pub fn mirror(&self) -> Move {
+        return match self {
+            Move::Left => Move::Right,
+            Move::Right => Move::Left,
+            vertical => *vertical,
+        };
+    }
+
+    /// Row and column offset of the blank.
+    pub fn offset(&self) -> (i64, i64) {
+        return match self {
+            Move::Left => (0, -1),
+            Move::Up => (-1, 0),
+            Move::Right => (0, 1),
+            Move::Down => (1, 0),
+        };
+    }
+
+    /// Where the blank ends up when it moves from `blank`, or `None` if that
+    /// is off a `rows` x `cols` board.
+    pub fn target(
+        &self,
+        blank: (usize, usize),
+        rows: usize,
+        cols: usize,
+    ) -> Option<(usize, usize)> {
+        let (dr, dc) = self.offset();
+        let new_r = blank.0 as i64 + dr;
+        let new_c = blank.1 as i64 + dc;
+        if new_r < 0 || new_r >= rows as i64 || new_c < 0 || new_c >= cols as i64 {
+            return None;
+        }
+        return Some((new_r as usize, new_c as usize));
+    }
+
+    /// The board after moving the blank, or `None` if the move would take the
+    /// blank off the board.
+    pub fn apply(&self, board: &Board) -> Option<Board> {
+        let blank = board.find_empty_cell()?;
+        let target = self.target(blank, board.rows(), board.cols())?;
+        return Some(board.copy_and_swap(blank, target));
+    }
+
+    /// The move that takes the blank from `from` to `to`, if they are
+    /// neighboring cells.
+    pub fn between(from: (usize, usize), to: (usize, usize)) -> Option<Move> {
+        let dr = to.0 as i64 - from.0 as i64;
+        let dc = to.1 as i64 - from.1 as i64;
+        return match (dr, dc) {
+            (0, -1) => Some(Move::Left),
+            (-1, 0) => Some(Move::Up),
+            (0, 1) => Some(Move::Right),
+            (1, 0) => Some(Move::Down),
+            _ => None,
+        };
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.letter());
+    }
+}
+
+/// Moves of the blank between consecutive boards of a solution path. Pairs
+/// that are not a single legal move are skipped.
+pub fn moves(path: &[Board]) -> Vec<Move> {
+    let mut list: Vec<Move> = Vec::new();
+    for pair in path.windows(2) {
+        if let (Some(from), Some(to)) = (pair[0].find_empty_cell(), pair[1].find_empty_cell())
+            && let Some(m) = Move::between(from, to)
+        {
+            list.push(m);
+        }
+    }
+    return list;
+}
+
+/// The solution path as a LURD string, e.g. `"RDLU"`.
+pub fn to_lurd(path: &[Board]) -> String {
+    return to_letters(&moves(path));
+}
+
+/// A list of moves as a LURD string.
+pub fn to_letters(moves: &[Move]) -> String {
+    return moves.iter().map(|m| m.letter()).collect();
+}
+
+/// Every board visited while playing `moves` from `board`, starting with
+/// `board` itself, or `None` if any of the moves is illegal.
+pub fn apply_moves(board: &Board, moves: &[Move]) -> Option<Vec<Board>> {
+    let mut boards = vec![*board];
+    for m in moves {
+        boards.push(m.apply(&boards[boards.len() - 1])?);
+    }
+    return Some(boards);
+}
+
+/// Reads a LURD string such as `"RDLU"`, ignoring whitespace.
+pub fn parse_lurd(text: &str) -> Result<Vec<Move>, String> {
+    return text
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .map(|ch| Move::from_letter(ch).ok_or_else(|| format!("unexpected move '{}'", ch)))
+        .collect();
+}
@@ -0,0 +1,188 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{board::Board, goal::GoalContext};
+
+use std::collections::HashMap;
+
+/// Every solution of a board with a given number of moves, counted without
+/// listing them, so any one of them can be picked by its index.
+pub struct OptimalPaths {
+    start: Board,
+    goal: GoalContext, // may ask for only some of the tiles
+    length: usize,
+    // solutions from a board in exactly that many moves, for every board the
+    // count went through
+    counts: HashMap<(Board, usize), u128>,
+}
+
+impl OptimalPaths {
+    /// Counts the solutions of `start` in `length` moves, with `length` the
+    /// optimal solution length. `lower_bound` must never overestimate the
+    /// moves left; the closer it is, the fewer boards are visited, and an
+    /// exact distance visits only the boards of the optimal solutions.
+    pub fn count(
+        start: Board,
+        goal: &GoalContext,
+        length: usize,
+        lower_bound: &dyn Fn(&Board) -> i64,
+    ) -> OptimalPaths {
+        let mut paths = OptimalPaths {
+            start,
+            goal: goal.clone(),
+            length,
+            counts: HashMap::new(),
+        };
+        paths.count_from(&start, length, lower_bound);
+        return paths;
+    }
+
+    fn count_from(
+        &mut self,
+        board: &Board,
+        remaining: usize,
+        lower_bound: &dyn Fn(&Board) -> i64,
+    ) -> u128 {
+        if remaining == 0 {
+            return self.goal.is_goal(board) as u128;
+        }
+        if lower_bound(board) > remaining as i64 {
+            return 0;
+        }
+        if let Some(count) = self.counts.get(&(*board, remaining)) {
+            return *count;
+        }
+        let mut count: u128 = 0;
+        for next in board.get_possible_next_states() {
+            let from_next = self.count_from(&next.board, remaining - 1, lower_bound);
+            count = count.saturating_add(from_next);
+        }
+        self.counts.insert((*board, remaining), count);
+        return count;
+    }
+
+    /// How many solutions there are (saturating at `u128::MAX`).
+    pub fn len(&self) -> u128 {
+        return self.solutions_from(&self.start, self.length);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Number of moves of every solution.
+    pub fn length(&self) -> usize {
+        return self.length;
+    }
+
+    /// The board the solutions start from.
+    pub fn start(&self) -> Board {
+        return self.start;
+    }
+
+    /// The `index`-th solution as the boards along it, in the order the moves
+    /// are generated; `None` past the last one.
+    pub fn nth(&self, index: u128) -> Option<Vec<Board>> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut index = index;
+        let mut board = self.start;
+        let mut path = vec![board];
+        for remaining in (0..self.length).rev() {
+            let mut chosen = None;
+            for next in board.get_possible_next_states() {
+                let count = self.solutions_from(&next.board, remaining);
+                if index < count {
+                    chosen = Some(next.board);
+                    break;
+                }
+                index -= count;
+            }
+            board = chosen?;
+            path.push(board);
+        }
+        return Some(path);
+    }
+
+    /// Where `path` comes in the order of [`OptimalPaths::nth`], or `None` if
+    /// it is not one of the counted solutions.
+    pub fn index_of(&self, path: &[Board]) -> Option<u128> {
+        if path.len() != self.length + 1 || path.first() != Some(&self.start) {
+            return None;
+        }
+        let mut index: u128 = 0;
+        for (remaining, pair) in (0..self.length).rev().zip(path.windows(2)) {
+            let mut found = false;
+            for next in pair[0].get_possible_next_states() {
+                if next.board == pair[1] {
+                    found = true;
+                    break;
+                }
+                index = index.saturating_add(self.solutions_from(&next.board, remaining));
+            }
+            if !found || self.solutions_from(&pair[1], remaining) == 0 {
+                return None;
+            }
+        }
+        return Some(index);
+    }
+
+    fn solutions_from(&self, board: &Board, remaining: usize) -> u128 {
+        if remaining == 0 {
+            return self.goal.is_goal(board) as u128;
+        }
+        return self.counts.get(&(*board, remaining)).copied().unwrap_or(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        a_star::SearchConfig, algorithms::Algorithm, board, heuristic::Heuristic, oracle::Oracle,
+    };
+
+    // Checks that every index names a different solution, found again by
+    // `index_of`.
+    fn assert_round_trip(paths: &OptimalPaths, goal: &GoalContext) {
+        assert!(!paths.is_empty());
+        for index in 0..paths.len() {
+            let path = paths.nth(index).unwrap();
+            assert_eq!(path.len(), paths.length() + 1);
+            assert!(goal.is_goal(path.last().unwrap()));
+            assert_eq!(paths.index_of(&path), Some(index));
+        }
+        assert_eq!(paths.nth(paths.len()), None);
+    }
+
+    #[test]
+    fn counts_agree_with_the_exact_distances() {
+        let goal = GoalContext::standard();
+        let oracle = Oracle::build(&goal);
+        for seed in 0..5 {
+            let start = board::scramble_from(goal.goal(), seed, 12);
+            let length = oracle.distance(&start).unwrap() as usize;
+            let exact = |b: &Board| oracle.distance(b).map_or(i64::MAX, i64::from);
+            let estimate = |b: &Board| Heuristic::Manhattan.estimate(b, &goal);
+            let pruned_exactly = OptimalPaths::count(start, &goal, length, &exact);
+            let pruned_loosely = OptimalPaths::count(start, &goal, length, &estimate);
+            assert_eq!(pruned_exactly.len(), pruned_loosely.len());
+            assert_round_trip(&pruned_exactly, &goal);
+        }
+    }
+
+    #[test]
+    fn partial_goals_are_counted_and_listed_alike() {
+        let goal = GoalContext::partial(*GoalContext::standard().goal(), &[1, 2, 3]);
+        let start = board::scramble_from(goal.goal(), 3, 14);
+        let length = Algorithm::AStar
+            .solve(start, &goal, SearchConfig::default())
+            .solution()
+            .unwrap()
+            .moves();
+        assert!(length > 0);
+        let paths = OptimalPaths::count(start, &goal, length, &|_| 0);
+        assert_round_trip(&paths, &goal);
+    }
+}
@@ -0,0 +1,130 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{board::Board, goal::GoalContext};
+
+use std::collections::{HashMap, VecDeque, hash_map::Entry};
+
+/// Largest board (in cells) an oracle is practical for: 3x4 already has
+/// 12!/2 reachable states.
+pub const MAX_CELLS: usize = 9;
+
+/// Exact distance to the goal for every reachable board, computed with a
+/// breadth-first search backwards from the goal. The 8-puzzle only has
+/// 9!/2 = 181440 reachable states, so this is cheap enough to build on demand.
+pub struct Oracle {
+    distances: HashMap<Board, u8>,
+}
+
+impl Oracle {
+    pub fn build(goal: &GoalContext) -> Oracle {
+        return Oracle::build_within(goal, u8::MAX);
+    }
+
+    /// Retrograde analysis limited to the boards at most `max_distance` moves
+    /// away from the goal; every other board is reported as unreachable.
+    pub fn build_within(goal: &GoalContext, max_distance: u8) -> Oracle {
+        let mut distances: HashMap<Board, u8> = HashMap::new();
+        let mut queue: VecDeque<Board> = VecDeque::new();
+
+        distances.insert(*goal.goal(), 0);
+        queue.push_back(*goal.goal());
+
+        // moves are reversible, so the forward successors work backwards too
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[&current];
+            if distance >= max_distance {
+                continue;
+            }
+            let next_distance = distance + 1;
+            for next in current.get_possible_next_states() {
+                let next_board = next.board;
+                if let Entry::Vacant(entry) = distances.entry(next_board) {
+                    entry.insert(next_distance);
+                    queue.push_back(next_board);
+                }
+            }
+        }
+
+        return Oracle { distances };
+    }
+
+    /// Exact number of moves needed to reach the goal, or `None` if the board
+    /// cannot reach it at all.
+    pub fn distance(&self, board: &Board) -> Option<u8> {
+        return self.distances.get(board).copied();
+    }
+
+    /// The board after an optimal first move, or `None` if the board is
+    /// already solved or cannot reach the goal.
+    pub fn first_move(&self, board: &Board) -> Option<Board> {
+        let distance = self.distance(board)?;
+        if distance == 0 {
+            return None;
+        }
+        return board
+            .get_possible_next_states()
+            .into_iter()
+            .map(|n| n.board)
+            .find(|next| self.distance(next) == Some(distance - 1));
+    }
+
+    /// An optimal path from the board to the goal, both included.
+    pub fn path(&self, board: &Board) -> Option<Vec<Board>> {
+        self.distance(board)?;
+        let mut path = vec![*board];
+        while let Some(next) = self.first_move(&path[path.len() - 1]) {
+            path.push(next);
+        }
+        return Some(path);
+    }
+
+    /// Every board the oracle knows, with its distance, in no particular order.
+    pub fn distances(&self) -> impl Iterator<Item = (&Board, u8)> + '_ {
+        return self
+            .distances
+            .iter()
+            .map(|(board, distance)| (board, *distance));
+    }
+
+    pub fn len(&self) -> usize {
+        return self.distances.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.distances.is_empty();
+    }
+
+    /// How many boards are at each distance from the goal, indexed by the
+    /// distance.
+    pub fn depth_distribution(&self) -> Vec<usize> {
+        let mut counts: Vec<usize> = Vec::new();
+        for distance in self.distances.values() {
+            let distance = *distance as usize;
+            if counts.len() <= distance {
+                counts.resize(distance + 1, 0);
+            }
+            counts[distance] += 1;
+        }
+        return counts;
+    }
+
+    /// The boards farthest from the goal, in the order of their codes.
+    pub fn hardest(&self) -> Vec<Board> {
+        let Some(max) = self.distances.values().max() else {
+            return Vec::new();
+        };
+        let mut boards: Vec<Board> = self
+            .distances
+            .iter()
+            .filter(|(_, distance)| *distance == max)
+            .map(|(board, _)| *board)
+            .collect();
+        boards.sort_by_key(|board| board.code());
+        return boards;
+    }
+
+    pub fn boards(&self) -> impl Iterator<Item = &Board> {
+        return self.distances.keys();
+    }
+}
@@ -0,0 +1,76 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::board::Board;
+
+/// The standard goal for a `rows` x `cols` puzzle in row-major order: tiles
+/// 1..rows()*cols-1 followed by the blank in the bottom-right corner.
+pub fn standard_goal(rows: usize, cols: usize) -> Vec<Option<i64>> {
+    let mut cells: Vec<Option<i64>> = (1..(rows * cols) as i64).map(Some).collect();
+    cells.push(None);
+    return cells;
+}
+
+/// Number of pairs of tiles that appear in the opposite order in `cells`
+/// (row-major, blank ignored).
+pub fn inversions(cells: &[Option<i64>]) -> usize {
+    let tiles: Vec<i64> = cells.iter().flatten().copied().collect();
+    let mut count = 0;
+    for i in 0..tiles.len() {
+        for j in (i + 1)..tiles.len() {
+            if tiles[i] > tiles[j] {
+                count += 1;
+            }
+        }
+    }
+    return count;
+}
+
+/// Whether `start` can be turned into `goal` by sliding tiles, for any grid
+/// with `cols` columns (both given row-major).
+///
+/// Each move changes the inversion count by an even amount when the width is
+/// odd, so only the inversion parity matters there. With an even width a
+/// vertical move flips the parity and also moves the blank one row, so the
+/// blank's row distance has to be taken into account.
+pub fn is_solvable_grid(start: &[Option<i64>], goal: &[Option<i64>], cols: usize) -> bool {
+    if start.len() != goal.len() || cols == 0 || !start.len().is_multiple_of(cols) {
+        return false;
+    }
+
+    let mut start_tiles: Vec<Option<i64>> = start.to_vec();
+    let mut goal_tiles: Vec<Option<i64>> = goal.to_vec();
+    start_tiles.sort();
+    goal_tiles.sort();
+    if start_tiles != goal_tiles || start_tiles.iter().filter(|c| c.is_none()).count() != 1 {
+        return false;
+    }
+
+    // count inversions relative to the goal order, so custom goals work too;
+    // both boards hold the same tiles, so every tile is found in the goal
+    let relative: Vec<Option<i64>> = start
+        .iter()
+        .map(|cell| cell.and_then(|n| Some(goal.iter().position(|g| *g == Some(n))? as i64)))
+        .collect();
+    let mut parity = inversions(&relative);
+
+    if cols.is_multiple_of(2) {
+        let blank_row =
+            |cells: &[Option<i64>]| cells.iter().position(|c| c.is_none()).map(|i| i / cols);
+        let (Some(start_blank_row), Some(goal_blank_row)) = (blank_row(start), blank_row(goal))
+        else {
+            return false;
+        };
+        parity += start_blank_row.abs_diff(goal_blank_row);
+    }
+
+    return parity.is_multiple_of(2);
+}
+
+/// Whether `start` can reach `goal`; boards of different shapes never can.
+pub fn is_solvable(start: &Board, goal: &Board) -> bool {
+    if (start.rows(), start.cols()) != (goal.rows(), goal.cols()) {
+        return false;
+    }
+    return is_solvable_grid(&start.cells(), &goal.cells(), start.cols());
+}
@@ -0,0 +1,203 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::config::{self, BoardSize, Config};
+use anyhow::Result;
+use eight_puzzle_solver::{
+    a_star::Progress,
+    algorithms::{Algorithm, SearchOutcome},
+    goal::GoalContext,
+    heuristic::Heuristic,
+    notation, validate,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    io::{self, BufRead, Write},
+    time::{Duration, Instant},
+};
+
+/// One line of input. Everything but the board falls back to the
+/// configuration.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Request {
+    id: Option<Value>, // echoed back, to match responses to requests
+    board: String,
+    board_size: Option<String>,
+    algorithm: Option<String>,
+    heuristic: Option<String>,
+    weight: Option<i64>,
+    max_expanded: Option<usize>, // give up after expanding this many boards
+    timeout_ms: Option<u64>,     // or after this long
+}
+
+#[derive(Serialize, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+enum Status {
+    Solved,
+    Unsolvable, // the goal cannot be reached from the board
+    NoSolution, // the search gave up without reaching the goal, see `error`
+    Limit,      // stopped by `max-expanded` or `timeout-ms`
+    Invalid,    // the request could not be read, see `error`
+}
+
+/// One line of output per request.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Response {
+    id: Option<Value>,
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    moves: Option<String>, // LURD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    optimal: Option<bool>, // whether the solution is guaranteed to be the shortest
+    expanded: usize,
+    time_ms: f64,
+}
+
+impl Response {
+    fn invalid(id: Option<Value>, error: String) -> Response {
+        return Response {
+            id,
+            status: Status::Invalid,
+            error: Some(error),
+            moves: None,
+            length: None,
+            optimal: None,
+            expanded: 0,
+            time_ms: 0.0,
+        };
+    }
+}
+
+/// Reads one JSON request per line from stdin and writes one JSON response
+/// per line to stdout, until stdin ends. Nothing else is printed, so the
+/// solver can run as a subprocess of another program.
+pub fn run(config: &Config) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => answer(request, config),
+            Err(e) => Response::invalid(None, format!("invalid request: {}", e)),
+        };
+        serde_json::to_writer(&mut stdout, &response)?;
+        writeln!(stdout)?;
+        stdout.flush()?;
+    }
+    return Ok(());
+}
+
+fn answer(request: Request, config: &Config) -> Response {
+    let id = request.id.clone();
+    return match search(request, config) {
+        Ok(response) => response,
+        Err(error) => Response::invalid(id, error),
+    };
+}
+
+fn search(request: Request, config: &Config) -> Result<Response, String> {
+    let size = match &request.board_size {
+        Some(size) => size.parse::<BoardSize>()?,
+        None => config.board_size,
+    };
+    let goal = GoalContext::for_preset(config.goal, size.rows, size.cols);
+    // unsolvable boards are parsed, the search tells them apart
+    let board = validate::parse_sized(&request.board, size.rows, size.cols, config.cell_order)
+        .map_err(|rejection| format!("rejected board: {}", rejection))?;
+    let algorithm = match &request.algorithm {
+        Some(name) => name.parse::<Algorithm>()?,
+        None => config.algorithm,
+    };
+    let mut search = config.search;
+    if let Some(name) = &request.heuristic {
+        search.heuristic = name.parse::<Heuristic>()?;
+    }
+    if let Some(weight) = request.weight {
+        config::check_weight(weight)?;
+        search.weight = weight;
+    }
+
+    let started = Instant::now();
+    let timeout = request.timeout_ms.map(Duration::from_millis);
+    let mut expanded = 0;
+    let mut observer = |progress: &Progress| {
+        expanded = progress.expanded;
+        let limited = request
+            .max_expanded
+            .is_some_and(|max| progress.expanded >= max)
+            || timeout.is_some_and(|timeout| started.elapsed() >= timeout);
+        return !limited;
+    };
+    let outcome = algorithm.solve_observed(board, &goal, search, &mut observer);
+    let time_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let mut response = Response {
+        id: request.id,
+        status: Status::NoSolution,
+        error: None,
+        moves: None,
+        length: None,
+        optimal: None,
+        expanded: 0,
+        time_ms,
+    };
+    match outcome {
+        SearchOutcome::Solved(solution) => {
+            response.status = Status::Solved;
+            response.moves = Some(notation::to_lurd(&solution.path));
+            response.length = Some(solution.moves());
+            response.optimal = Some(solution.guaranteed_optimal);
+            response.expanded = solution.stats.expanded;
+        }
+        SearchOutcome::Unsolvable => response.status = Status::Unsolvable,
+        SearchOutcome::Cancelled => {
+            response.status = Status::Limit;
+            response.expanded = expanded;
+        }
+        SearchOutcome::LimitExceeded { expanded } => {
+            response.error = Some(algorithm.failure_message(&search));
+            response.expanded = expanded;
+        }
+        SearchOutcome::Error(e) => {
+            response.status = Status::Invalid;
+            response.error = Some(e.to_string());
+        }
+    }
+    return Ok(response);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ask(line: &str) -> Response {
+        let request: Request = serde_json::from_str(line).unwrap();
+        return answer(request, &Config::default());
+    }
+
+    #[test]
+    fn weights_out_of_range_are_invalid() {
+        for weight in [-3, 0, i64::MAX] {
+            let line = format!(r#"{{"board":"724506831","weight":{}}}"#, weight);
+            let response = ask(&line);
+            assert!(
+                matches!(response.status, Status::Invalid),
+                "weight {}",
+                weight
+            );
+            assert_eq!(response.expanded, 0);
+        }
+        assert!(matches!(
+            ask(r#"{"board":"724506831","weight":1000}"#).status,
+            Status::Solved
+        ));
+    }
+}
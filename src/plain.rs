@@ -0,0 +1,135 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::config::Config;
+use anyhow::Result;
+use eight_puzzle_solver::{
+    a_star::Solution,
+    algorithms::SearchOutcome,
+    board::{self, Board},
+    book, explain,
+    goal::GoalContext,
+    validate,
+};
+use std::io::{self, BufRead, Write};
+
+/// Line-based stand-in for the TUI: no alternate screen, no raw mode, and
+/// nothing drawn, only sentences a screen reader can read out.
+pub fn run(config: &Config) -> Result<()> {
+    let goal = config.goal();
+    let mut lines = io::stdin().lock().lines();
+    println!("Eight puzzle solver, plain mode.");
+    println!(
+        "Type a board row by row with 0 for the empty cell, for example {}. Type q to quit.",
+        board::scramble_from(goal.goal(), 1, board::DEFAULT_SCRAMBLE_MOVES).code()
+    );
+    loop {
+        let Some(line) = prompt(&mut lines, "Board: ")? else {
+            return Ok(());
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.eq_ignore_ascii_case("q") {
+            return Ok(());
+        }
+        let board = match validate::check_import(line, &goal, config.cell_order) {
+            Ok(board) => board,
+            Err(rejection) => {
+                println!("Rejected: {}.", rejection);
+                continue;
+            }
+        };
+        println!("Solving.");
+        let outcome = match book::lookup_solution(&board, &goal, &config.search) {
+            Some(solution) => SearchOutcome::Solved(solution),
+            None => config.algorithm.solve(board, &goal, config.search),
+        };
+        let solution = match outcome.into_result(config.algorithm, &config.search) {
+            Ok(solution) => solution,
+            Err(message) => {
+                println!("{}", message);
+                continue;
+            }
+        };
+        if !read_solution(&solution, &goal, &mut lines)? {
+            return Ok(());
+        }
+    }
+}
+
+// Reads the solution out move by move. False once the input has ended.
+fn read_solution(
+    solution: &Solution,
+    goal: &GoalContext,
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+) -> Result<bool> {
+    let moves = solution.moves();
+    if moves == 0 {
+        println!("This board is already solved.");
+        return Ok(true);
+    }
+    println!(
+        "Solved in {} move{}, after searching {} boards in {:.1} milliseconds.",
+        moves,
+        if moves == 1 { "" } else { "s" },
+        solution.stats.expanded,
+        solution.stats.elapsed.as_secs_f64() * 1000.0
+    );
+    if !solution.guaranteed_optimal {
+        println!("A shorter solution may exist.");
+    }
+    println!(
+        "Press Enter for the next move, a for all remaining moves, b to read the board, or q for a new board."
+    );
+    let commentary = explain::commentary(&solution.path, goal);
+    let mut step = 0;
+    while step < moves {
+        let Some(answer) = prompt(lines, "")? else {
+            return Ok(false);
+        };
+        match answer.trim() {
+            "" => {
+                println!("Move {} of {}: {}.", step + 1, moves, commentary[step]);
+                step += 1;
+            }
+            "a" | "A" => {
+                for (i, sentence) in commentary.iter().enumerate().skip(step) {
+                    println!("Move {} of {}: {}.", i + 1, moves, sentence);
+                }
+                step = moves;
+            }
+            "b" | "B" => println!("{}", describe_board(&solution.path[step])),
+            "q" | "Q" => return Ok(true),
+            _ => println!("Press Enter, a, b, or q."),
+        }
+    }
+    println!("Solved.");
+    return Ok(true);
+}
+
+// "Row 1: 7, 2, 4. Row 2: 5, empty, 6. ..."
+fn describe_board(board: &Board) -> String {
+    let rows: Vec<String> = (0..board.rows())
+        .map(|r| {
+            let cells: Vec<String> = (0..board.cols())
+                .map(|c| match board.get(r, c) {
+                    Some(tile) => tile.to_string(),
+                    None => "empty".to_string(),
+                })
+                .collect();
+            return format!("Row {}: {}.", r + 1, cells.join(", "));
+        })
+        .collect();
+    return rows.join(" ");
+}
+
+fn prompt(
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+    text: &str,
+) -> Result<Option<String>> {
+    print!("{}", text);
+    io::stdout().flush()?;
+    return Ok(lines.next().transpose()?);
+}
@@ -0,0 +1,240 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use eight_puzzle_solver::{
+    board::Board,
+    goal::GoalContext,
+    hint,
+    notation::{self, Move},
+    oracle::Oracle,
+    review::{self, Review},
+};
+use std::time::{Duration, Instant};
+
+/// Number of macro slots, bound to the keys 1-9.
+pub const MACRO_SLOTS: usize = 9;
+
+/// State of a manual play session.
+pub struct PlayState {
+    pub goal: GoalContext,
+    pub start: Board,
+    pub board: Board,
+    pub moves: Vec<Move>, // every move made, including the ones from macros
+    pub move_times: Vec<Duration>, // when each move was made, from the first one
+    pub macros_used: usize,
+    pub solver_moves: usize, // moves played by "finish it for me"
+    pub macros: [Option<Vec<Move>>; MACRO_SLOTS],
+    pub recording: Option<Vec<Move>>,
+    pub message: Option<String>,
+    pub review: Option<Review>, // once solved, how the moves compare with the shortest solution
+    pub started: Option<Instant>, // at the first move
+    pub time: Option<Duration>, // taken to solve the board, `None` until then
+}
+
+impl PlayState {
+    pub fn new() -> PlayState {
+        let mut macros: [Option<Vec<Move>>; MACRO_SLOTS] = Default::default();
+        // rotates the three tiles of the 2x2 block below and right of the blank
+        macros[0] = Some(vec![Move::Right, Move::Down, Move::Left, Move::Up]);
+
+        return PlayState {
+            goal: GoalContext::standard(),
+            start: Board::default(),
+            board: Board::default(),
+            moves: Vec::new(),
+            move_times: Vec::new(),
+            macros_used: 0,
+            solver_moves: 0,
+            macros,
+            recording: None,
+            message: None,
+            review: None,
+            started: None,
+            time: None,
+        };
+    }
+
+    /// Starts a new session from `board` towards `goal`, keeping the recorded
+    /// macros.
+    pub fn restart(&mut self, board: Board, goal: &GoalContext) {
+        self.goal = goal.clone();
+        self.start = board;
+        self.board = board;
+        self.moves.clear();
+        self.move_times.clear();
+        self.macros_used = 0;
+        self.solver_moves = 0;
+        self.recording = None;
+        self.message = None;
+        self.review = None;
+        self.started = None;
+        self.time = None;
+    }
+
+    /// Time since the first move, stopped once the board is solved.
+    pub fn elapsed(&self) -> Duration {
+        return match (self.time, self.started) {
+            (Some(time), _) => time,
+            (None, Some(started)) => started.elapsed(),
+            (None, None) => Duration::ZERO,
+        };
+    }
+
+    pub fn is_clock_running(&self) -> bool {
+        return self.started.is_some() && self.time.is_none();
+    }
+
+    /// Continues the clock of a saved game that had been played for
+    /// `elapsed`.
+    pub fn resume_clock(&mut self, elapsed: Duration) {
+        if self.moves.is_empty() {
+            return;
+        }
+        self.started = Some(
+            Instant::now()
+                .checked_sub(elapsed)
+                .unwrap_or_else(Instant::now),
+        );
+        self.time = self.is_solved().then_some(elapsed);
+    }
+
+    // Starts the clock at the first move and stops it at the goal.
+    fn clock(&mut self) {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        self.time = self.is_solved().then(|| started.elapsed());
+    }
+
+    pub fn is_solved(&self) -> bool {
+        return self.goal.is_goal(&self.board);
+    }
+
+    pub fn make_move(&mut self, m: Move) {
+        match m.apply(&self.board) {
+            Some(next) => {
+                self.board = next;
+                self.moves.push(m);
+                self.review = None;
+                self.clock();
+                self.move_times.push(self.elapsed());
+                if let Some(recording) = &mut self.recording {
+                    recording.push(m);
+                }
+                self.message = None;
+            }
+            None => self.message = Some("The blank cannot move that way.".to_string()),
+        }
+    }
+
+    pub fn toggle_recording(&mut self) {
+        if self.recording.take().is_some() {
+            self.message = Some("Recording cancelled.".to_string());
+        } else {
+            self.recording = Some(Vec::new());
+            self.message = Some("Recording: press 1-9 to save the macro.".to_string());
+        }
+    }
+
+    /// Saves the macro being recorded into `slot`, or plays the macro stored
+    /// there when not recording.
+    pub fn use_slot(&mut self, slot: usize) {
+        if let Some(recording) = self.recording.take() {
+            self.message = Some(format!(
+                "Saved macro {}: {}",
+                slot + 1,
+                notation::to_letters(&recording)
+            ));
+            self.macros[slot] = Some(recording);
+            return;
+        }
+
+        let Some(moves) = self.macros[slot].clone() else {
+            self.message = Some(format!("Macro {} is empty.", slot + 1));
+            return;
+        };
+        // check the whole macro first, so it is either played fully or not at all
+        match notation::apply_moves(&self.board, &moves) {
+            Some(boards) => {
+                self.board = boards[boards.len() - 1];
+                self.moves.extend(moves);
+                self.review = None;
+                self.clock();
+                let at = self.elapsed();
+                self.move_times.resize(self.moves.len(), at);
+                self.macros_used += 1;
+                self.message = None;
+            }
+            None => {
+                self.message = Some(format!(
+                    "Macro {} is not legal from this position.",
+                    slot + 1
+                ))
+            }
+        }
+    }
+
+    /// Shows the suggested moves, see [`hint::hint`].
+    pub fn hint(&mut self, oracle: Option<&Oracle>, full_within: u8) {
+        self.message = Some(
+            match hint::hint(&self.board, &self.goal, oracle, full_within) {
+                Some(moves) if moves.is_empty() => "Already solved.".to_string(),
+                Some(moves) if moves.len() == 1 => format!("Hint: move the blank {}", moves[0]),
+                Some(moves) => format!("Hint: {} to finish", notation::to_letters(&moves)),
+                None => "The goal cannot be reached from here.".to_string(),
+            },
+        );
+    }
+
+    /// Plays the rest of an optimal solution.
+    pub fn finish(&mut self, oracle: Option<&Oracle>) {
+        let Some(moves) = hint::hint(&self.board, &self.goal, oracle, u8::MAX) else {
+            self.message = Some("The goal cannot be reached from here.".to_string());
+            return;
+        };
+        for m in &moves {
+            self.make_move(*m);
+        }
+        self.solver_moves += moves.len();
+        self.message = Some(format!("The solver played the last {} moves.", moves.len()));
+    }
+
+    /// Once the board is solved, compares the moves with a shortest solution
+    /// and points out the first one that strayed from it.
+    pub fn review(&mut self, oracle: Option<&Oracle>) {
+        if !self.is_solved() || self.moves.is_empty() || self.review.is_some() {
+            return;
+        }
+        let goal = self.goal.clone();
+        let mut distance = |board: &Board| {
+            return Some(hint::hint(board, &goal, oracle, u8::MAX)?.len());
+        };
+        self.review = review::review(&self.start, &self.moves, &mut distance);
+        let Some(review) = &self.review else {
+            return;
+        };
+        self.message = Some(match &review.first_detour {
+            None => format!(
+                "Optimal: no solution is shorter than {} moves.",
+                review.optimal
+            ),
+            Some(detour) => {
+                let before = notation::apply_moves(&self.start, &self.moves[..detour.index]);
+                let better = before
+                    .and_then(|path| hint::hint(&path[path.len() - 1], &goal, oracle, 0))
+                    .and_then(|moves| moves.first().copied());
+                let mut text = format!(
+                    "The shortest solution has {} moves. Move {} ({}) was the first detour: {} moves to go before it, {} after",
+                    review.optimal,
+                    detour.index + 1,
+                    detour.mv,
+                    detour.before,
+                    detour.after
+                );
+                if let Some(better) = better {
+                    text.push_str(&format!("; moving the blank {} was shorter", better));
+                }
+                text.push('.');
+                text
+            }
+        });
+    }
+}
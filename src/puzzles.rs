@@ -0,0 +1,104 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    board::Board,
+    goal::GoalContext,
+    validate::{self, CellOrder, Rejection},
+};
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, path::Path};
+
+/// A set of puzzles to share, written in TOML (one `[[puzzle]]` table per
+/// puzzle) or in JSON with the same fields.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Collection {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, rename = "puzzle")]
+    pub puzzles: Vec<Puzzle>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Puzzle {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>, // when not the author of the collection
+    pub board: String, // in any form the board importer accepts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimal: Option<usize>, // length of the shortest solution, if known
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Format {
+    Toml,
+    Json,
+}
+
+impl Format {
+    /// JSON for `.json` files, TOML for everything else.
+    pub fn from_path(path: &Path) -> Format {
+        return match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Format::Json,
+            _ => Format::Toml,
+        };
+    }
+}
+
+#[derive(Debug)]
+pub enum CollectionError {
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for CollectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            CollectionError::Toml(e) => write!(f, "{}", e),
+            CollectionError::Json(e) => write!(f, "{}", e),
+        };
+    }
+}
+
+impl std::error::Error for CollectionError {}
+
+// The presets of the TUI, also an example of the format.
+const CLASSIC: &str = include_str!("../puzzles/classic.toml");
+
+impl Collection {
+    pub fn parse(text: &str, format: Format) -> Result<Collection, CollectionError> {
+        return match format {
+            Format::Toml => toml::from_str(text).map_err(CollectionError::Toml),
+            Format::Json => serde_json::from_str(text).map_err(CollectionError::Json),
+        };
+    }
+
+    /// The built-in collection of 8-puzzles, from easy to the hardest ones.
+    pub fn classic() -> Collection {
+        return Collection::parse(CLASSIC, Format::Toml).expect("invalid built-in puzzles");
+    }
+}
+
+impl Puzzle {
+    /// The board, checked like any imported board written row by row; boards
+    /// of another shape than `goal` are rejected.
+    pub fn board(&self, goal: &GoalContext) -> Result<Board, Rejection> {
+        return validate::check_import(&self.board, goal, CellOrder::Rows);
+    }
+
+    /// The name, or the board when the puzzle has none.
+    pub fn title(&self) -> &str {
+        return match self.name.is_empty() {
+            true => &self.board,
+            false => &self.name,
+        };
+    }
+}
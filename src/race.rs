@@ -0,0 +1,100 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use eight_puzzle_solver::{
+    board::Board,
+    goal::GoalContext,
+    notation::{self, Move},
+};
+use std::time::{Duration, Instant};
+
+/// Time between two moves of the solver unless configured otherwise.
+pub const DEFAULT_PACE_MS: u64 = 1000;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Winner {
+    Player,
+    Solver,
+}
+
+/// The player against the solver's optimal solution, played back one move
+/// every `pace` from the player's first move on.
+pub struct Race {
+    pub goal: GoalContext,
+    pub player: Board,
+    pub player_moves: usize,
+    pub solver_path: Vec<Board>, // from the start to the goal
+    pub solver_step: usize,
+    pub winner: Option<Winner>,
+    pub message: Option<String>,
+    pace: Duration,
+    last_step: Option<Instant>, // `None` until the player moves
+}
+
+impl Race {
+    pub fn new(solver_path: Vec<Board>, goal: &GoalContext, pace: Duration) -> Race {
+        return Race {
+            goal: goal.clone(),
+            player: solver_path[0],
+            player_moves: 0,
+            solver_path,
+            solver_step: 0,
+            winner: None,
+            message: None,
+            pace,
+            last_step: None,
+        };
+    }
+
+    pub fn solver_board(&self) -> Board {
+        return self.solver_path[self.solver_step];
+    }
+
+    /// Number of moves of the optimal solution.
+    pub fn optimal(&self) -> usize {
+        return self.solver_path.len() - 1;
+    }
+
+    pub fn is_started(&self) -> bool {
+        return self.last_step.is_some();
+    }
+
+    pub fn make_move(&mut self, m: Move) {
+        if self.winner.is_some() {
+            return;
+        }
+        let Some(next) = m.apply(&self.player) else {
+            self.message = Some("The blank cannot move that way.".to_string());
+            return;
+        };
+        self.player = next;
+        self.player_moves += 1;
+        self.message = None;
+        if self.last_step.is_none() {
+            self.last_step = Some(Instant::now());
+        }
+        if self.goal.is_goal(&self.player) {
+            self.winner = Some(Winner::Player);
+        }
+    }
+
+    /// Plays the solver's moves that are due.
+    pub fn tick(&mut self) {
+        let Some(mut last_step) = self.last_step else {
+            return;
+        };
+        while self.winner.is_none() && last_step.elapsed() >= self.pace {
+            last_step += self.pace;
+            self.solver_step += 1;
+            if self.solver_step >= self.optimal() {
+                self.winner = Some(Winner::Solver);
+            }
+        }
+        self.last_step = Some(last_step);
+    }
+
+    /// The solver's moves as LURD, to learn from after the race.
+    pub fn solver_moves(&self) -> String {
+        return notation::to_lurd(&self.solver_path);
+    }
+}
@@ -0,0 +1,172 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{play::PlayState, session};
+
+use anyhow::{Context, Result, bail};
+use eight_puzzle_solver::{
+    a_star::{self, SearchStats, Solution},
+    board::Board,
+    goal::GoalContext,
+    heuristic::Heuristic,
+    notation::{self, Move},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// A game played by hand, to share or look back at: the boards and every
+/// move with the time it was made, counted from the first one.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Replay {
+    pub board_size: String,
+    pub start: String,
+    pub goal: String,
+    pub moves: Vec<TimedMove>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct TimedMove {
+    #[serde(rename = "move")]
+    pub letter: char, // in LURD notation
+    pub at_ms: u64,
+}
+
+impl Replay {
+    pub fn from_play(play: &PlayState) -> Replay {
+        let goal = play.goal.goal();
+        return Replay {
+            board_size: format!("{}x{}", goal.rows(), goal.cols()),
+            start: play.start.code(),
+            goal: goal.code(),
+            moves: play
+                .moves
+                .iter()
+                .zip(&play.move_times)
+                .map(|(m, at)| TimedMove {
+                    letter: m.letter(),
+                    at_ms: at.as_millis() as u64,
+                })
+                .collect(),
+        };
+    }
+
+    pub fn load(path: &Path) -> Result<Replay> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+        return serde_json::from_str(&text)
+            .with_context(|| format!("invalid replay {}", path.display()));
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let text = serde_json::to_string_pretty(self).context("cannot serialize the replay")?;
+        fs::write(path, text).with_context(|| format!("cannot write {}", path.display()))?;
+        return Ok(());
+    }
+
+    /// The game as a solution for the step viewer, with the time of each
+    /// move. Fails if the replay is for boards of another shape than `goal`
+    /// or its moves are not legal.
+    pub fn solution(
+        &self,
+        goal: &GoalContext,
+        heuristic: Heuristic,
+    ) -> Result<(Solution, Vec<Duration>)> {
+        let shape = goal.goal();
+        let (rows, cols) = (shape.rows(), shape.cols());
+        let board = |code: &str| -> Result<Board> {
+            return session::board_from_code(code, rows, cols).with_context(|| {
+                format!(
+                    "{} is not a {}x{} board, the replay is for {} boards",
+                    code, rows, cols, self.board_size
+                )
+            });
+        };
+        let start = board(&self.start)?;
+        let goal = GoalContext::new(board(&self.goal)?);
+        let mut directions: Vec<Move> = Vec::new();
+        for timed in &self.moves {
+            match Move::from_letter(timed.letter) {
+                Some(m) => directions.push(m),
+                None => bail!("{} is not a move", timed.letter),
+            }
+        }
+        let Some(path) = notation::apply_moves(&start, &directions) else {
+            bail!("the moves of the replay are not legal from its start");
+        };
+        let times: Vec<Duration> = self
+            .moves
+            .iter()
+            .map(|timed| Duration::from_millis(timed.at_ms))
+            .collect();
+        let solution = Solution {
+            steps: a_star::annotate(&path, &goal, heuristic),
+            path,
+            directions,
+            phases: Vec::new(),
+            shortened: 0,
+            guaranteed_optimal: false, // played by hand
+            stats: SearchStats {
+                elapsed: times.last().copied().unwrap_or_default(),
+                ..SearchStats::default()
+            },
+        };
+        return Ok((solution, times));
+    }
+}
+
+// The start board is shown this long before the first move is played back.
+const LEAD: Duration = Duration::from_secs(1);
+
+/// Plays a replay back in the step viewer at the pace it was played.
+pub struct Playback {
+    pub times: Vec<Duration>, // of each move
+    origin: Option<Instant>,  // when the first move was played, `None` while paused
+}
+
+impl Playback {
+    pub fn new(times: Vec<Duration>) -> Playback {
+        return Playback {
+            times,
+            origin: Some(Instant::now() + LEAD),
+        };
+    }
+
+    pub fn is_paused(&self) -> bool {
+        return self.origin.is_none();
+    }
+
+    /// Time into the game at `step`, the board after that many moves.
+    pub fn time_at(&self, step: usize) -> Duration {
+        return match step {
+            0 => Duration::ZERO,
+            _ => self.times[(step - 1).min(self.times.len() - 1)],
+        };
+    }
+
+    pub fn pause(&mut self) {
+        self.origin = None;
+    }
+
+    /// Goes on from `step`, as if it had just been played.
+    pub fn resume(&mut self, step: usize) {
+        let now = Instant::now();
+        self.origin = Some(now.checked_sub(self.time_at(step)).unwrap_or(now));
+    }
+
+    /// The step due now, `None` while paused.
+    pub fn step(&self) -> Option<usize> {
+        let elapsed = self.origin?.elapsed(); // zero until the lead is over
+        return Some(self.times.iter().filter(|at| **at < elapsed).count());
+    }
+
+    /// Total time of the game.
+    pub fn length(&self) -> Duration {
+        return self.times.last().copied().unwrap_or_default();
+    }
+}
@@ -0,0 +1,68 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{SearchStats, Solution},
+    algorithms::Algorithm,
+    board::Board,
+    heuristic::Heuristic,
+};
+
+/// How one algorithm and heuristic did on one puzzle, for the per-puzzle
+/// reports of `compare-matrix` and `solve-batch`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Row {
+    pub puzzle: Board,
+    pub algorithm: Algorithm,
+    pub heuristic: Option<Heuristic>, // `None` for the uninformed algorithms
+    pub length: Option<usize>,        // `None` when no solution was found
+    pub stats: SearchStats,           // all zero without a solution
+}
+
+impl Row {
+    pub fn new(
+        puzzle: Board,
+        algorithm: Algorithm,
+        heuristic: Option<Heuristic>,
+        solution: Option<&Solution>,
+    ) -> Row {
+        return Row {
+            puzzle,
+            algorithm,
+            heuristic,
+            length: solution.map(|s| s.moves()),
+            stats: solution.map_or_else(SearchStats::default, |s| s.stats),
+        };
+    }
+
+    pub fn millis(&self) -> f64 {
+        return self.stats.elapsed.as_secs_f64() * 1000.0;
+    }
+}
+
+/// One CSV line per row; the numbers are left empty for unsolved puzzles.
+pub fn to_csv(rows: &[Row]) -> String {
+    let mut out =
+        String::from("puzzle,algorithm,heuristic,length,nodes_expanded,time_ms,peak_frontier\n");
+    for row in rows {
+        let numbers = match row.length {
+            Some(length) => format!(
+                "{},{},{:.3},{}",
+                length,
+                row.stats.expanded,
+                row.millis(),
+                row.stats.peak_frontier
+            ),
+            None => ",,,".to_string(),
+        };
+        // boards with two-digit tiles have commas in their code
+        out.push_str(&format!(
+            "\"{}\",{},{},{}\n",
+            row.puzzle.code(),
+            row.algorithm.name(),
+            row.heuristic.map_or("-", |h| h.name()),
+            numbers
+        ));
+    }
+    return out;
+}
@@ -0,0 +1,56 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{board::Board, notation::Move};
+
+/// How a solution played by hand compares with the shortest one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Review {
+    pub moves: usize,
+    pub optimal: usize,
+    pub first_detour: Option<Detour>, // `None` when every move was optimal
+}
+
+/// The first move that did not bring the board closer to the goal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Detour {
+    pub index: usize, // of the move, from 0
+    pub mv: Move,
+    pub before: usize, // moves to the goal before it
+    pub after: usize,  // and after it
+}
+
+/// Reviews `moves` played from `start`, with `distance` giving the moves
+/// left to the goal from a board. Distances are only asked for up to the
+/// first detour. `None` if a move is illegal or a board cannot be solved.
+pub fn review(
+    start: &Board,
+    moves: &[Move],
+    distance: &mut dyn FnMut(&Board) -> Option<usize>,
+) -> Option<Review> {
+    let optimal = distance(start)?;
+    let mut board = *start;
+    let mut before = optimal;
+    for (index, mv) in moves.iter().enumerate() {
+        board = mv.apply(&board)?;
+        let after = distance(&board)?;
+        if after + 1 != before {
+            return Some(Review {
+                moves: moves.len(),
+                optimal,
+                first_detour: Some(Detour {
+                    index,
+                    mv: *mv,
+                    before,
+                    after,
+                }),
+            });
+        }
+        before = after;
+    }
+    return Some(Review {
+        moves: moves.len(),
+        optimal,
+        first_detour: None,
+    });
+}
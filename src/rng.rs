@@ -0,0 +1,26 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use rand::{SeedableRng, rngs::Xoshiro256PlusPlus};
+
+/// The generator behind every random choice: scrambles, sampled boards and
+/// the random tie-break of A*. A named generator, unlike StdRng its output is
+/// fixed across versions and platforms, so a seed always replays the same way.
+pub type SeededRng = Xoshiro256PlusPlus;
+
+/// What every seed the user did not give is in deterministic mode.
+pub const DETERMINISTIC_SEED: u64 = 0;
+
+pub fn seeded(seed: u64) -> SeededRng {
+    return SeededRng::seed_from_u64(seed);
+}
+
+/// A seed for something that was not given one: a fresh one every time, or
+/// always [`DETERMINISTIC_SEED`] when `deterministic`, for tests and
+/// benchmarks that have to give the same results on every run.
+pub fn fresh_seed(deterministic: bool) -> u64 {
+    return match deterministic {
+        true => DETERMINISTIC_SEED,
+        false => rand::random(),
+    };
+}
@@ -0,0 +1,173 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use anyhow::{Context, Result};
+use eight_puzzle_solver::{
+    a_star::{self, SearchStats, Solution},
+    board::Board,
+    goal::GoalContext,
+    heuristic::Heuristic,
+    notation,
+};
+use serde::{Deserialize, Serialize};
+use std::{env, fs, io, path::PathBuf, time::Duration};
+
+/// What the TUI restores on the next launch. Boards are stored in their
+/// compact form and moves in LURD notation, so the file stays readable.
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Session {
+    pub board_size: String, // the rest only applies to boards of this size
+    #[serde(default)]
+    pub goal: String, // the goal preset, empty in sessions from before there was a choice
+    #[serde(default)]
+    pub goal_board: String, // the committed goal, which may have been edited from the preset
+    #[serde(default)]
+    pub goal_tiles: Vec<i64>, // the only tiles searches put in place, empty for all
+    pub board: String,
+    pub seed: Option<u64>,
+    pub scramble_moves: usize,
+    pub heuristic: String,
+    pub weight: i64,
+    pub visualize: bool,
+    pub screen: Screen,
+    #[serde(default)]
+    pub current_step: usize, // in the solution
+    pub macros: Vec<String>, // one per slot, empty for an empty slot
+    pub solution: Option<SavedSolution>,
+    pub play: Option<SavedPlay>,
+}
+
+/// The screen that was open, among the ones worth coming back to.
+#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Screen {
+    #[default]
+    Input,
+    Result,
+    Play,
+}
+
+/// A solution as its start board and moves; the rest is recomputed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct SavedSolution {
+    pub start: String,
+    pub moves: String,
+    pub guaranteed_optimal: bool,
+    pub expanded: usize,
+    pub generated: usize,
+    pub elapsed_ms: f64,
+}
+
+impl SavedSolution {
+    pub fn new(solution: &Solution) -> Option<SavedSolution> {
+        return Some(SavedSolution {
+            start: solution.path.first()?.code(),
+            moves: notation::to_letters(&solution.directions),
+            guaranteed_optimal: solution.guaranteed_optimal,
+            expanded: solution.stats.expanded,
+            generated: solution.stats.generated,
+            elapsed_ms: solution.stats.elapsed.as_secs_f64() * 1000.0,
+        });
+    }
+
+    /// The solution again, with its steps annotated by `heuristic`; `None` if
+    /// the board or the moves do not fit `goal`.
+    pub fn restore(&self, goal: &GoalContext, heuristic: Heuristic) -> Option<Solution> {
+        let shape = goal.goal();
+        let start = board_from_code(&self.start, shape.rows(), shape.cols())?;
+        let moves = notation::parse_lurd(&self.moves).ok()?;
+        let path = notation::apply_moves(&start, &moves)?;
+        return Some(Solution {
+            steps: a_star::annotate(&path, goal, heuristic),
+            path,
+            directions: moves,
+            phases: Vec::new(),
+            shortened: 0,
+            guaranteed_optimal: self.guaranteed_optimal,
+            stats: SearchStats {
+                expanded: self.expanded,
+                generated: self.generated,
+                elapsed: Duration::from_secs_f64(self.elapsed_ms.max(0.0) / 1000.0),
+                ..SearchStats::default() // the rest is not saved
+            },
+        });
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct SavedPlay {
+    pub start: String,
+    pub moves: String,
+    pub macros_used: usize,
+    pub solver_moves: usize,
+    #[serde(default)]
+    pub elapsed_ms: u64, // on the clock when the session was saved
+    #[serde(default)]
+    pub move_times_ms: Vec<u64>,
+}
+
+/// `$XDG_STATE_HOME/eight-puzzle-solver`, with `~/.local/state` when
+/// `XDG_STATE_HOME` is not set.
+pub fn state_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_STATE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?)
+            .join(".local")
+            .join("state"),
+    };
+    return Some(base.join("eight-puzzle-solver"));
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    return Some(state_dir()?.join("session.toml"));
+}
+
+impl Session {
+    /// The saved session, or `None` if there is none yet.
+    pub fn load(path: &PathBuf) -> Result<Option<Session>> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("cannot read {}", path.display())),
+        };
+        let session =
+            toml::from_str(&text).with_context(|| format!("invalid session {}", path.display()))?;
+        return Ok(Some(session));
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        }
+        let text = toml::to_string(self).context("cannot serialize the session")?;
+        fs::write(path, text).with_context(|| format!("cannot write {}", path.display()))?;
+        return Ok(());
+    }
+}
+
+/// Reads a board written by [`Board::code`], even one still being edited
+/// (duplicate or missing tiles); `None` if it does not fit a `rows` x `cols`
+/// board.
+pub fn board_from_code(code: &str, rows: usize, cols: usize) -> Option<Board> {
+    // one character per cell unless the cells are separated by commas
+    let tokens: Vec<String> = match code.contains(',') {
+        true => code.split(',').map(String::from).collect(),
+        false => code.chars().map(String::from).collect(),
+    };
+    if tokens.len() != rows * cols {
+        return None;
+    }
+    let max_tile = (rows * cols - 1) as i64;
+    let mut cells: Vec<Option<i64>> = Vec::new();
+    for token in tokens {
+        match token.trim().parse::<i64>().ok()? {
+            0 => cells.push(None),
+            n if n <= max_tile => cells.push(Some(n)),
+            _ => return None,
+        }
+    }
+    return Some(Board::from_cells(rows, cols, &cells));
+}
@@ -0,0 +1,135 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{self, SearchConfig, Solution},
+    actions::ActionSet,
+    board::{Board, BoardMap},
+    explain::Phase,
+    goal::GoalContext,
+    notation,
+};
+
+/// Moves of the stretches of a solution that are solved again optimally.
+const WINDOW: usize = 12;
+
+/// Makes the solution of a non-optimal search shorter: drops every detour
+/// that comes back to a board it already passed, like a move straight back,
+/// then solves short stretches of what is left again with A* and splices in
+/// any cheaper way between their ends that takes no more moves. Returns how
+/// many moves were saved, also kept in `solution.shortened`.
+pub fn shorten(solution: &mut Solution, goal: &GoalContext, config: SearchConfig) -> usize {
+    let before = solution.moves();
+    // for each board, the index of the board it came from in the old path
+    let mut origin: Vec<usize> = (0..solution.path.len()).collect();
+    let mut path = solution.path.clone();
+    drop_loops(&mut path, &mut origin);
+
+    let window_config = SearchConfig {
+        weight: 1,
+        actions: ActionSet::Single,
+        ..config
+    };
+    let mut start = 0;
+    while start + 1 < path.len() {
+        let end = (start + WINDOW).min(path.len() - 1);
+        let window_goal = GoalContext::new(path[end]);
+        if let Some(shortcut) = a_star::search_with(path[start], &window_goal, window_config) {
+            let cost = |boards: &[Board]| config.cost.of_path(boards);
+            // a cheaper way may take more moves when moves differ in cost
+            if cost(&shortcut.path) < cost(&path[start..=end])
+                && shortcut.path.len() <= end - start + 1
+            {
+                // the new boards belong where the stretch started
+                let inner = shortcut.path.len() - 2;
+                path.splice(start + 1..end, shortcut.path[1..=inner].iter().copied());
+                origin.splice(start + 1..end, vec![origin[start]; inner]);
+                drop_loops(&mut path, &mut origin);
+            }
+        }
+        // the windows overlap, so a detour across two of them is found too
+        start += WINDOW / 2;
+    }
+
+    let saved = before - (path.len() - 1);
+    if path != solution.path {
+        solution.phases = remap(&solution.phases, &origin);
+        solution.directions = notation::moves(&path);
+        solution.steps = a_star::annotate(&path, goal, config.heuristic);
+        solution.path = path;
+        solution.shortened += saved;
+    }
+    return saved;
+}
+
+// Cuts out every stretch of `path` between two visits of the same board.
+fn drop_loops(path: &mut Vec<Board>, origin: &mut Vec<usize>) {
+    let mut seen: BoardMap<usize> = BoardMap::default();
+    let mut kept = Vec::with_capacity(path.len());
+    let mut kept_origin = Vec::with_capacity(path.len());
+    for (board, from) in path.iter().zip(origin.iter()) {
+        if let Some(&first) = seen.get(board) {
+            for dropped in kept.drain(first + 1..) {
+                seen.remove(&dropped);
+            }
+            kept_origin.truncate(first + 1);
+            continue;
+        }
+        seen.insert(*board, kept.len());
+        kept.push(*board);
+        kept_origin.push(*from);
+    }
+    *path = kept;
+    *origin = kept_origin;
+}
+
+// The phases of the old path over the new one: each starts and ends at the
+// first board that came from at or after where it did, empty ones dropped.
+fn remap(phases: &[Phase], origin: &[usize]) -> Vec<Phase> {
+    let moves = origin.len() - 1;
+    let position = |old: usize| origin.iter().position(|o| *o >= old).unwrap_or(moves);
+    return phases
+        .iter()
+        .map(|phase| Phase {
+            start: position(phase.start),
+            end: position(phase.end).min(moves),
+            ..phase.clone()
+        })
+        .filter(|phase| phase.end > phase.start)
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::Algorithm, cost::CostModel, validate, verify};
+
+    #[test]
+    fn cheaper_shortcuts_never_add_moves() {
+        let board = validate::parse("726513408").unwrap();
+        let goal = GoalContext::standard();
+        let config = SearchConfig {
+            cost: CostModel::TileValue,
+            ..SearchConfig::default()
+        };
+        // the human-style solver shortens its own solution, which used to
+        // take a longer but cheaper shortcut and count the moves below zero
+        let mut solution = Algorithm::Human
+            .solve(board, &goal, config)
+            .solution()
+            .unwrap();
+        assert_eq!(
+            verify::verify_with_goal(&board, &solution.path, &goal),
+            Ok(())
+        );
+        let (moves, cost) = (solution.moves(), config.cost.of_path(&solution.path));
+
+        let saved = shorten(&mut solution, &goal, config);
+        assert_eq!(solution.moves() + saved, moves);
+        assert!(config.cost.of_path(&solution.path) <= cost);
+        assert_eq!(
+            verify::verify_with_goal(&board, &solution.path, &goal),
+            Ok(())
+        );
+    }
+}
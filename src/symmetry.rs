@@ -0,0 +1,151 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{self, Solution},
+    board::Board,
+    goal::GoalContext,
+    heuristic::Heuristic,
+    notation::Move,
+};
+
+use std::fmt;
+
+/// A way of turning the whole puzzle over. Applied to a board and its goal
+/// alike, the puzzle stays the same one: every solution of the old puzzle,
+/// with its moves turned the same way, solves the new one in as many moves.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Symmetry {
+    RotateCw, // a quarter turn clockwise
+    Mirror,   // left to right
+}
+
+impl Symmetry {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Symmetry::RotateCw => "rotated",
+            Symmetry::Mirror => "mirrored",
+        };
+    }
+
+    pub fn board(&self, board: &Board) -> Board {
+        return match self {
+            Symmetry::RotateCw => board.rotate_cw(),
+            Symmetry::Mirror => board.mirror(),
+        };
+    }
+
+    pub fn cell(&self, (row, col): (usize, usize), rows: usize, cols: usize) -> (usize, usize) {
+        return match self {
+            Symmetry::RotateCw => (col, rows - 1 - row),
+            Symmetry::Mirror => (row, cols - 1 - col),
+        };
+    }
+
+    pub fn mv(&self, mv: Move) -> Move {
+        return match self {
+            Symmetry::RotateCw => mv.rotate_cw(),
+            Symmetry::Mirror => mv.mirror(),
+        };
+    }
+
+    /// The goal turned over, asking for the same tiles as before.
+    pub fn goal(&self, goal: &GoalContext) -> GoalContext {
+        return GoalContext::partial(self.board(goal.goal()), &goal.targets());
+    }
+
+    /// A solution of a puzzle as a solution of the puzzle turned over, to
+    /// `goal`, with the steps estimated again by `heuristic`.
+    pub fn solution(
+        &self,
+        solution: &Solution,
+        goal: &GoalContext,
+        heuristic: Heuristic,
+    ) -> Solution {
+        let path: Vec<Board> = solution.path.iter().map(|b| self.board(b)).collect();
+        return Solution {
+            steps: a_star::annotate(&path, goal, heuristic),
+            path,
+            directions: solution.directions.iter().map(|m| self.mv(*m)).collect(),
+            ..solution.clone()
+        };
+    }
+}
+
+/// A turn of the board, with the tiles renumbered after it, that maps the
+/// goal onto itself. A board and its image are then the same number of moves
+/// from the goal, so a search only needs to expand one of them.
+#[derive(Clone, Debug)]
+pub struct GoalSymmetry {
+    turns: Vec<Symmetry>, // applied in order
+    tiles: Vec<i64>,      // the number each tile gets, by its old number
+}
+
+impl GoalSymmetry {
+    pub fn apply(&self, board: &Board) -> Board {
+        let turned = self.turns.iter().fold(*board, |b, turn| turn.board(&b));
+        let cells: Vec<Option<i64>> = turned
+            .cells()
+            .into_iter()
+            .map(|cell| cell.map(|tile| self.tiles[tile as usize]))
+            .collect();
+        return Board::from_cells(turned.rows(), turned.cols(), &cells);
+    }
+}
+
+/// The turns of the board, other than no turn at all, that `goal` cannot
+/// tell from no turn: those that keep its shape and its blank where they
+/// are, and ask for the same tiles. For the standard goal that is only the
+/// flip over the diagonal through the blank; goals with the blank in the
+/// middle have seven.
+pub fn goal_symmetries(goal: &GoalContext) -> Vec<GoalSymmetry> {
+    let board = goal.goal();
+    let mut found = Vec::new();
+    for quarter_turns in 0..4 {
+        for mirrored in [false, true] {
+            if (quarter_turns == 0 && !mirrored)
+                || (quarter_turns % 2 == 1 && board.rows() != board.cols())
+            {
+                continue;
+            }
+            let mut turns = vec![Symmetry::RotateCw; quarter_turns];
+            if mirrored {
+                turns.push(Symmetry::Mirror);
+            }
+            let turned = turns.iter().fold(*board, |b, turn| turn.board(&b));
+            if turned.find_empty_cell() != board.find_empty_cell() {
+                continue;
+            }
+            // each tile takes the number of the goal's tile where it lands
+            let mut tiles = vec![0; board.max_tile() as usize + 1];
+            for (from, to) in turned.cells().into_iter().zip(board.cells()) {
+                if let (Some(from), Some(to)) = (from, to) {
+                    tiles[from as usize] = to;
+                }
+            }
+            let same_targets = (1..=board.max_tile())
+                .all(|t| goal.is_target(t) == goal.is_target(tiles[t as usize]));
+            if same_targets {
+                found.push(GoalSymmetry { turns, tiles });
+            }
+        }
+    }
+    return found;
+}
+
+/// The one of `board` and its images under `symmetries` that stands for
+/// them all.
+pub fn canonical(board: &Board, symmetries: &[GoalSymmetry]) -> Board {
+    return symmetries
+        .iter()
+        .map(|symmetry| symmetry.apply(board))
+        .chain([*board])
+        .min_by_key(|image| image.packed())
+        .unwrap_or(*board);
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
@@ -0,0 +1,136 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use ratatui::style::{Color, Modifier, Style};
+use std::{fmt, str::FromStr};
+
+/// The built-in color themes of the TUI.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Theme {
+    #[default]
+    Classic,
+    Light,        // for terminals with a light background
+    HighContrast, // bold text on solid backgrounds
+    Colorblind,   // never tells two things apart by red and green alone
+}
+
+/// Every style the TUI draws with, by what it is used for.
+pub struct Palette {
+    pub title: Style,
+    pub label: Style, // headings above boards, the difficulty
+    pub tile: Style,
+    pub blank: Style,
+    pub cursor: Style,   // the tile being edited
+    pub moved: Style,    // the tile that just moved in the step viewer
+    pub in_place: Style, // tiles already where the goal wants them
+    pub selected: Style, // list selection, focused side of a comparison
+    pub notice: Style,   // status messages and warnings
+    pub error: Style,
+    pub progress: Style, // progress bars
+    pub heat: Style,     // how close a search got to the goal
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [
+        Theme::Classic,
+        Theme::Light,
+        Theme::HighContrast,
+        Theme::Colorblind,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Theme::Classic => "classic",
+            Theme::Light => "light",
+            Theme::HighContrast => "high-contrast",
+            Theme::Colorblind => "colorblind",
+        };
+    }
+
+    pub fn next(&self) -> Theme {
+        let idx = Theme::ALL.iter().position(|t| t == self).unwrap_or(0);
+        return Theme::ALL[(idx + 1) % Theme::ALL.len()];
+    }
+
+    pub fn palette(&self) -> Palette {
+        let plain = Style::default();
+        let bold = plain.add_modifier(Modifier::BOLD);
+        return match self {
+            Theme::Classic => Palette {
+                title: plain.fg(Color::Cyan),
+                label: plain.fg(Color::Cyan),
+                tile: plain.fg(Color::White),
+                blank: plain.bg(Color::DarkGray),
+                cursor: bold.fg(Color::Yellow),
+                moved: bold.fg(Color::Green),
+                in_place: plain.fg(Color::LightGreen),
+                selected: bold.fg(Color::Yellow),
+                notice: plain.fg(Color::Yellow),
+                error: plain.fg(Color::Red),
+                progress: plain.fg(Color::Green),
+                heat: plain.fg(Color::Red),
+            },
+            Theme::Light => Palette {
+                title: plain.fg(Color::Blue),
+                label: plain.fg(Color::Blue),
+                tile: plain.fg(Color::Black),
+                blank: plain.bg(Color::Gray),
+                cursor: bold.fg(Color::Magenta),
+                moved: bold.fg(Color::Blue),
+                in_place: plain.fg(Color::Green),
+                selected: bold.fg(Color::Magenta),
+                notice: plain.fg(Color::Magenta),
+                error: bold.fg(Color::Red),
+                progress: plain.fg(Color::Blue),
+                heat: plain.fg(Color::Magenta),
+            },
+            Theme::HighContrast => Palette {
+                title: bold.fg(Color::White),
+                label: bold.fg(Color::White),
+                tile: bold.fg(Color::White),
+                blank: plain.bg(Color::White),
+                cursor: bold.fg(Color::Black).bg(Color::Yellow),
+                moved: bold.fg(Color::Black).bg(Color::Cyan),
+                in_place: bold.fg(Color::LightGreen),
+                selected: bold.add_modifier(Modifier::REVERSED),
+                notice: bold.fg(Color::Black).bg(Color::Yellow),
+                error: bold.fg(Color::White).bg(Color::Red),
+                progress: plain.fg(Color::White),
+                heat: plain.fg(Color::Yellow),
+            },
+            // blue, yellow and magenta stay apart with every common form of
+            // color blindness
+            Theme::Colorblind => Palette {
+                title: plain.fg(Color::Cyan),
+                label: plain.fg(Color::Cyan),
+                tile: plain.fg(Color::White),
+                blank: plain.bg(Color::DarkGray),
+                cursor: bold.fg(Color::Yellow),
+                moved: bold.fg(Color::LightBlue).add_modifier(Modifier::UNDERLINED),
+                in_place: plain.fg(Color::LightCyan),
+                selected: bold.fg(Color::Yellow),
+                notice: plain.fg(Color::Yellow),
+                error: bold.fg(Color::Magenta),
+                progress: plain.fg(Color::Blue),
+                heat: plain.fg(Color::Yellow),
+            },
+        };
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Theme::ALL
+            .into_iter()
+            .find(|t| t.name() == s)
+            .ok_or_else(|| format!("unknown theme: {}", s));
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
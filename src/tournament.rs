@@ -0,0 +1,221 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{
+    a_star::{Progress, SearchConfig},
+    algorithms::Algorithm,
+    board::Board,
+    goal::GoalContext,
+    heuristic::Heuristic,
+    report::Row,
+};
+
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// An algorithm and heuristic taking part in a tournament, written
+/// `a-star:manhattan`, or just `bfs` for the uninformed algorithms.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Entrant {
+    pub algorithm: Algorithm,
+    pub heuristic: Option<Heuristic>, // `None` for the uninformed algorithms
+}
+
+impl Entrant {
+    /// Every algorithm with every heuristic it uses, like `compare-matrix`.
+    pub fn all() -> Vec<Entrant> {
+        let mut entrants = Vec::new();
+        for algorithm in Algorithm::ALL {
+            match algorithm.is_informed() {
+                true => entrants.extend(Heuristic::ALL.into_iter().map(|h| Entrant {
+                    algorithm,
+                    heuristic: Some(h),
+                })),
+                false => entrants.push(Entrant {
+                    algorithm,
+                    heuristic: None,
+                }),
+            }
+        }
+        return entrants;
+    }
+}
+
+/// The same limits for every entrant on every puzzle; a search that runs
+/// past one has not solved the puzzle.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Budget {
+    pub max_expanded: Option<usize>,
+    pub time_limit: Option<Duration>,
+}
+
+/// How an entrant did over the whole corpus.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Standing {
+    pub entrant: Entrant,
+    pub solved: usize,
+    pub unsolved: usize,
+    pub optimal: usize,  // solutions as short as the puzzle allows
+    pub fastest: usize,  // puzzles no other entrant solved sooner
+    pub expanded: usize, // summed over the solved puzzles, like the two below
+    pub moves: usize,
+    pub elapsed: Duration,
+}
+
+impl Standing {
+    /// A point for each solve, each optimal solve, and each fastest solve.
+    pub fn points(&self) -> usize {
+        return self.solved + self.optimal + self.fastest;
+    }
+
+    pub fn avg_expanded(&self) -> f64 {
+        return self.per_solved(self.expanded as f64);
+    }
+
+    pub fn avg_moves(&self) -> f64 {
+        return self.per_solved(self.moves as f64);
+    }
+
+    pub fn avg_millis(&self) -> f64 {
+        return self.per_solved(self.elapsed.as_secs_f64() * 1000.0);
+    }
+
+    fn per_solved(&self, total: f64) -> f64 {
+        if self.solved == 0 {
+            return 0.0;
+        }
+        return total / self.solved as f64;
+    }
+}
+
+/// Solves every board with every entrant within `budget`, using the rest of
+/// `base` (weight, beam width) for all of them. One row per entrant and
+/// board, without a length for the puzzles the entrant did not solve.
+pub fn play(
+    entrants: &[Entrant],
+    boards: &[Board],
+    goal: &GoalContext,
+    base: SearchConfig,
+    budget: Budget,
+) -> Vec<Row> {
+    let mut rows: Vec<Row> = Vec::new();
+    for entrant in entrants {
+        let config = SearchConfig {
+            heuristic: entrant.heuristic.unwrap_or_default(),
+            ..base
+        };
+        for board in boards {
+            let started = Instant::now();
+            let mut within_budget = |progress: &Progress| {
+                return budget
+                    .max_expanded
+                    .is_none_or(|max| progress.expanded < max)
+                    && budget
+                        .time_limit
+                        .is_none_or(|limit| started.elapsed() < limit);
+            };
+            let solution = entrant
+                .algorithm
+                .solve_observed(*board, goal, config, &mut within_budget)
+                .solution();
+            rows.push(Row::new(
+                *board,
+                entrant.algorithm,
+                entrant.heuristic,
+                solution.as_ref(),
+            ));
+        }
+    }
+    return rows;
+}
+
+/// The entrants ranked by points, then by total search time. A solve is
+/// optimal when it is as short as `exact` says the puzzle allows, or, where
+/// `exact` does not know, as short as the best solve of any entrant.
+pub fn standings(rows: &[Row], exact: impl Fn(&Board) -> Option<usize>) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = Vec::new();
+    for row in rows {
+        let entrant = Entrant {
+            algorithm: row.algorithm,
+            heuristic: row.heuristic,
+        };
+        let standing = match standings.iter().position(|s| s.entrant == entrant) {
+            Some(i) => &mut standings[i],
+            None => {
+                standings.push(Standing {
+                    entrant,
+                    solved: 0,
+                    unsolved: 0,
+                    optimal: 0,
+                    fastest: 0,
+                    expanded: 0,
+                    moves: 0,
+                    elapsed: Duration::ZERO,
+                });
+                standings.last_mut().unwrap()
+            }
+        };
+        let Some(length) = row.length else {
+            standing.unsolved += 1;
+            continue;
+        };
+        standing.solved += 1;
+        standing.expanded += row.stats.expanded;
+        standing.moves += length;
+        standing.elapsed += row.stats.elapsed;
+
+        let rivals: Vec<&Row> = rows
+            .iter()
+            .filter(|r| r.puzzle == row.puzzle && r.length.is_some())
+            .collect();
+        let shortest = exact(&row.puzzle)
+            .or_else(|| rivals.iter().filter_map(|r| r.length).min())
+            .unwrap_or(length);
+        if length == shortest {
+            standing.optimal += 1;
+        }
+        if rivals.iter().all(|r| r.stats.elapsed >= row.stats.elapsed) {
+            standing.fastest += 1;
+        }
+    }
+    standings.sort_by(|a, b| {
+        return b.points().cmp(&a.points()).then(a.elapsed.cmp(&b.elapsed));
+    });
+    return standings;
+}
+
+impl FromStr for Entrant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (algorithm, heuristic) = match s.split_once(':') {
+            Some((algorithm, heuristic)) => (algorithm, Some(heuristic)),
+            None => (s, None),
+        };
+        let algorithm: Algorithm = algorithm.parse()?;
+        let heuristic: Option<Heuristic> = heuristic.map(str::parse).transpose()?;
+        return match (algorithm.is_informed(), heuristic) {
+            (true, None) => Err(format!(
+                "{} needs a heuristic, e.g. {}:manhattan",
+                algorithm, algorithm
+            )),
+            (false, Some(_)) => Err(format!("{} does not use a heuristic", algorithm)),
+            _ => Ok(Entrant {
+                algorithm,
+                heuristic,
+            }),
+        };
+    }
+}
+
+impl fmt::Display for Entrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self.heuristic {
+            Some(heuristic) => write!(f, "{}:{}", self.algorithm.name(), heuristic.name()),
+            None => write!(f, "{}", self.algorithm.name()),
+        };
+    }
+}
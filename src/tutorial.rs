@@ -0,0 +1,87 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use eight_puzzle_solver::board::{self, Board};
+
+// The tour's board is this many moves from the goal, always the same ones.
+const TOUR_MOVES: usize = 3;
+const TOUR_SEED: u64 = 1;
+
+/// A step of the guided tour, over with the first key that gets the TUI to
+/// where it asks.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Lesson {
+    TypeBoard,
+    Solve,
+    Summary,
+    StepThrough,
+    Return,
+}
+
+impl Lesson {
+    pub const ALL: [Lesson; 5] = [
+        Lesson::TypeBoard,
+        Lesson::Solve,
+        Lesson::Summary,
+        Lesson::StepThrough,
+        Lesson::Return,
+    ];
+
+    pub fn next(&self) -> Option<Lesson> {
+        let i = Lesson::ALL.iter().position(|l| l == self)?;
+        return Lesson::ALL.get(i + 1).copied();
+    }
+
+    /// Counted from 1, for "2/5".
+    pub fn number(&self) -> usize {
+        return Lesson::ALL.iter().position(|l| l == self).unwrap_or(0) + 1;
+    }
+}
+
+/// The guided tour of a first solve: typing a board, solving it, and going
+/// through the solution.
+pub struct Tutorial {
+    pub lesson: Lesson,
+    pub board: Board, // the one the tour has typed in and solved
+}
+
+impl Tutorial {
+    pub fn new(goal: &Board) -> Tutorial {
+        return Tutorial {
+            lesson: Lesson::TypeBoard,
+            board: board::scramble_from(goal, TOUR_SEED, TOUR_MOVES),
+        };
+    }
+
+    /// What to do for the current lesson.
+    pub fn text(&self) -> String {
+        return match self.lesson {
+            Lesson::TypeBoard => {
+                let rows: Vec<String> = self
+                    .board
+                    .cells()
+                    .chunks(self.board.cols())
+                    .map(|row| {
+                        let tiles: Vec<String> =
+                            row.iter().map(|t| t.unwrap_or(0).to_string()).collect();
+                        return tiles.join(" ");
+                    })
+                    .collect();
+                format!(
+                    "Type {} into the Start grid, one row after the other, with 0 for the blank. The arrows move the cursor, a digit fills the cell under it.",
+                    rows.join(" / ")
+                )
+            }
+            Lesson::Solve => "Press Enter to solve the board.".to_string(),
+            Lesson::Summary => {
+                "The summary tells how long the solution is and how hard the search worked for it. Press Enter to see it move by move.".to_string()
+            }
+            Lesson::StepThrough => {
+                "Press Right to play the next move, Left to take it back. Go on until the goal.".to_string()
+            }
+            Lesson::Return => {
+                "Solved! Press q to go back to the editor and try a board of your own.".to_string()
+            }
+        };
+    }
+}
@@ -0,0 +1,275 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{board::Board, goal::GoalContext};
+
+use std::{fmt, str::FromStr};
+
+/// A single reason why an imported board was rejected.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Problem {
+    InvalidCharacter(char),
+    WrongCellCount { expected: usize, found: usize },
+    TileOutOfRange { tile: i64, max: i64 },
+    DuplicateTile(i64),
+    MissingTile(i64),
+    WrongBlankCount(usize),
+    Unsolvable,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Problem::InvalidCharacter(ch) => write!(f, "unexpected character '{}'", ch),
+            Problem::WrongCellCount { expected, found } => {
+                write!(f, "expected {} cells, got {}", expected, found)
+            }
+            Problem::TileOutOfRange { tile, max } => {
+                write!(f, "tile {} is outside 1-{}", tile, max)
+            }
+            Problem::DuplicateTile(n) => write!(f, "tile {} appears more than once", n),
+            Problem::MissingTile(n) => write!(f, "tile {} is missing", n),
+            Problem::WrongBlankCount(count) => {
+                write!(f, "expected exactly 1 empty cell, got {}", count)
+            }
+            Problem::Unsolvable => write!(f, "the goal cannot be reached from this board"),
+        };
+    }
+}
+
+/// Every problem found in a rejected board, so the user can fix them all at
+/// once instead of one per attempt.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Rejection {
+    pub problems: Vec<Problem>,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problems: Vec<String> = self.problems.iter().map(|p| p.to_string()).collect();
+        return write!(f, "{}", problems.join("; "));
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+/// In which order the cells of an imported board are written.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum CellOrder {
+    #[default]
+    Auto, // row by row, unless the lines of the text can only be the columns
+    Rows,
+    Columns,
+}
+
+impl CellOrder {
+    pub const ALL: [CellOrder; 3] = [CellOrder::Auto, CellOrder::Rows, CellOrder::Columns];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            CellOrder::Auto => "auto",
+            CellOrder::Rows => "rows",
+            CellOrder::Columns => "columns",
+        };
+    }
+}
+
+impl FromStr for CellOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return CellOrder::ALL
+            .into_iter()
+            .find(|o| o.name() == s)
+            .ok_or_else(|| format!("unknown cell order: {}", s));
+    }
+}
+
+impl fmt::Display for CellOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
+
+/// Reads a 3x3 board written row by row as digits, with 0 for the empty cell
+/// (e.g. "724506831"). Whitespace and commas between digits are ignored.
+/// Only the shape and the tiles are checked, not solvability.
+pub fn parse(text: &str) -> Result<Board, Rejection> {
+    return parse_sized(text, 3, 3, CellOrder::Auto);
+}
+
+/// Same as [`parse`] for a `rows` x `cols` board written in `order`. Boards
+/// with two-digit tiles need the cells separated by whitespace or commas
+/// (e.g. "1,2,3,4,5,6,7,8,9,10,11,0"). The empty cell may be written as `0`,
+/// `_` or `.`, or left out, which puts it in the last cell.
+pub fn parse_sized(
+    text: &str,
+    rows: usize,
+    cols: usize,
+    order: CellOrder,
+) -> Result<Board, Rejection> {
+    let mut problems: Vec<Problem> = Vec::new();
+    let lines: Vec<Vec<Option<i64>>> = text
+        .lines()
+        .map(|line| parse_line(line, rows * cols <= 10, &mut problems))
+        .filter(|cells| !cells.is_empty())
+        .collect();
+    let by_columns = match order {
+        CellOrder::Auto => written_by_columns(&lines, rows, cols),
+        CellOrder::Rows => false,
+        CellOrder::Columns => true,
+    };
+    let mut cells = lines.concat();
+    if cells.len() + 1 == rows * cols && !cells.contains(&None) {
+        cells.push(None);
+    }
+
+    problems.extend(tile_problems(&cells, rows, cols));
+    if !problems.is_empty() {
+        return Err(Rejection { problems });
+    }
+
+    if by_columns {
+        cells = (0..rows * cols)
+            .map(|i| cells[(i % cols) * rows + i / cols])
+            .collect();
+    }
+    return Ok(Board::from_cells(rows, cols, &cells));
+}
+
+// The cells of one line of an imported board.
+fn parse_line(line: &str, single_digits: bool, problems: &mut Vec<Problem>) -> Vec<Option<i64>> {
+    let mut cells: Vec<Option<i64>> = Vec::new();
+    if single_digits {
+        for ch in line.chars() {
+            if ch.is_whitespace() || ch == ',' {
+                continue;
+            }
+            match ch.to_digit(10) {
+                Some(0) => cells.push(None),
+                Some(d) => cells.push(Some(d as i64)),
+                None if ch == '_' || ch == '.' => cells.push(None),
+                None => push_invalid(problems, ch),
+            }
+        }
+        return cells;
+    }
+
+    for token in line.split(|ch: char| ch.is_whitespace() || ch == ',') {
+        if token.is_empty() {
+            continue;
+        }
+        if token == "_" || token == "." {
+            cells.push(None);
+            continue;
+        }
+        if let Some(ch) = token.chars().find(|ch| !ch.is_ascii_digit()) {
+            push_invalid(problems, ch);
+            continue;
+        }
+        // too many digits to parse is still just a tile out of range
+        match token.parse::<i64>().unwrap_or(i64::MAX) {
+            0 => cells.push(None),
+            n => cells.push(Some(n)),
+        }
+    }
+    return cells;
+}
+
+// Whether `lines` can only be the columns of a `rows` x `cols` board: one
+// line per column, each as long as a column (the last one may be missing
+// its blank). Square boards are always read row by row.
+fn written_by_columns(lines: &[Vec<Option<i64>>], rows: usize, cols: usize) -> bool {
+    if rows == cols || lines.len() != cols {
+        return false;
+    }
+    let (last, columns) = lines.split_last().expect("a board has columns");
+    return columns.iter().all(|column| column.len() == rows)
+        && (last.len() == rows || last.len() + 1 == rows);
+}
+
+/// Full validation for boards coming from outside the app (clipboard, files,
+/// command line): shape, tiles, and whether `goal` can be reached at all. The
+/// board must have the same shape as the goal.
+pub fn check_import(text: &str, goal: &GoalContext, order: CellOrder) -> Result<Board, Rejection> {
+    let board = parse_sized(text, goal.goal().rows(), goal.goal().cols(), order)?;
+    if !goal.is_reachable(&board) {
+        return Err(Rejection {
+            problems: vec![Problem::Unsolvable],
+        });
+    }
+    return Ok(board);
+}
+
+/// Same as [`check_import`], for boards that are already split into cells
+/// (row-major, `None` for the empty cell).
+pub fn check_cells(cells: &[Option<i64>], goal: &GoalContext) -> Result<Board, Rejection> {
+    let (rows, cols) = (goal.goal().rows(), goal.goal().cols());
+    let problems = tile_problems(cells, rows, cols);
+    if !problems.is_empty() {
+        return Err(Rejection { problems });
+    }
+
+    let board = Board::from_cells(rows, cols, cells);
+    if !goal.is_reachable(&board) {
+        return Err(Rejection {
+            problems: vec![Problem::Unsolvable],
+        });
+    }
+    return Ok(board);
+}
+
+/// Checks that `board` has every tile of its shape once and a single blank.
+/// Solvability is not checked.
+pub fn check_tiles(board: &Board) -> Result<(), Rejection> {
+    let problems = tile_problems(&board.cells(), board.rows(), board.cols());
+    if !problems.is_empty() {
+        return Err(Rejection { problems });
+    }
+    return Ok(());
+}
+
+fn tile_problems(cells: &[Option<i64>], rows: usize, cols: usize) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = Vec::new();
+    let expected = rows * cols;
+    if cells.len() != expected {
+        problems.push(Problem::WrongCellCount {
+            expected,
+            found: cells.len(),
+        });
+    }
+
+    let max_tile = (expected - 1) as i64;
+    let mut counts = vec![0; expected];
+    let mut blanks = 0;
+    for cell in cells {
+        match cell {
+            Some(n) if (1..=max_tile).contains(n) => counts[*n as usize] += 1,
+            Some(n) => problems.push(Problem::TileOutOfRange {
+                tile: *n,
+                max: max_tile,
+            }),
+            None => blanks += 1,
+        }
+    }
+
+    for n in 1..=max_tile {
+        match counts[n as usize] {
+            0 => problems.push(Problem::MissingTile(n)),
+            1 => {}
+            _ => problems.push(Problem::DuplicateTile(n)),
+        }
+    }
+    if blanks != 1 {
+        problems.push(Problem::WrongBlankCount(blanks));
+    }
+
+    return problems;
+}
+
+fn push_invalid(problems: &mut Vec<Problem>, ch: char) {
+    let problem = Problem::InvalidCharacter(ch);
+    if !problems.contains(&problem) {
+        problems.push(problem);
+    }
+}
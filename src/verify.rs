@@ -0,0 +1,111 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use crate::{board::Board, goal::GoalContext};
+
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VerifyError {
+    InvalidStart,
+    EmptySolution,
+    StartMismatch,
+    IllegalMove { step: usize },
+    NotAtGoal,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            VerifyError::InvalidStart => write!(f, "starting board is not a valid board"),
+            VerifyError::EmptySolution => write!(f, "solution contains no boards"),
+            VerifyError::StartMismatch => {
+                write!(f, "solution does not begin with the starting board")
+            }
+            VerifyError::IllegalMove { step } => {
+                write!(f, "step {} is not a single legal move of the blank", step)
+            }
+            VerifyError::NotAtGoal => write!(f, "solution does not end at the goal board"),
+        };
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Checks that `solution` starts at `start`, that every consecutive pair of
+/// boards differs by exactly one legal move of the blank, and that the last
+/// board is the standard goal.
+pub fn verify(start: &Board, solution: &[Board]) -> Result<(), VerifyError> {
+    return verify_with_goal(start, solution, &GoalContext::standard());
+}
+
+/// Same as [`verify`], but against a custom goal.
+pub fn verify_with_goal(
+    start: &Board,
+    solution: &[Board],
+    goal: &GoalContext,
+) -> Result<(), VerifyError> {
+    if !start.is_valid() {
+        return Err(VerifyError::InvalidStart);
+    }
+
+    let first = solution.first().ok_or(VerifyError::EmptySolution)?;
+    if first != start {
+        return Err(VerifyError::StartMismatch);
+    }
+
+    for (i, pair) in solution.windows(2).enumerate() {
+        // a legal move is exactly one of the successors of the previous board
+        if !pair[0]
+            .get_possible_next_states()
+            .iter()
+            .any(|n| n.board == pair[1])
+        {
+            return Err(VerifyError::IllegalMove { step: i + 1 });
+        }
+    }
+
+    if !solution.last().is_some_and(|b| goal.is_goal(b)) {
+        return Err(VerifyError::NotAtGoal);
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate;
+
+    fn boards(codes: &[&str]) -> Vec<Board> {
+        return codes.iter().map(|c| validate::parse(c).unwrap()).collect();
+    }
+
+    #[test]
+    fn moves_to_the_goal_are_accepted() {
+        let path = boards(&["123456078", "123456708", "123456780"]);
+        assert_eq!(verify(&path[0], &path), Ok(()));
+    }
+
+    #[test]
+    fn jumps_of_the_blank_are_rejected() {
+        let path = boards(&["123456078", "123456780"]);
+        assert_eq!(
+            verify(&path[0], &path),
+            Err(VerifyError::IllegalMove { step: 1 })
+        );
+    }
+
+    #[test]
+    fn solutions_of_another_board_are_rejected() {
+        let path = boards(&["123456708", "123456780"]);
+        let start = validate::parse("123456078").unwrap();
+        assert_eq!(verify(&start, &path), Err(VerifyError::StartMismatch));
+    }
+
+    #[test]
+    fn solutions_stopping_short_are_rejected() {
+        let path = boards(&["123456078", "123456708"]);
+        assert_eq!(verify(&path[0], &path), Err(VerifyError::NotAtGoal));
+    }
+}
@@ -0,0 +1,192 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use eight_puzzle_solver::{
+    a_star::{SearchConfig, Solution},
+    algorithms::{Algorithm, SearchOutcome},
+    board::Board,
+    goal::GoalContext,
+    heuristic::Heuristic,
+};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+};
+
+/// One of the two configurations being compared, and how its run went.
+pub struct Contender {
+    pub algorithm: Algorithm,
+    pub config: SearchConfig,
+    pub outcome: Outcome,
+    rx: Option<Receiver<SearchOutcome>>,
+}
+
+pub enum Outcome {
+    Waiting, // not started yet, or its settings changed since
+    Running,
+    Solved(Solution),
+    Failed(String),
+}
+
+impl Outcome {
+    /// The outcome of a finished search of `algorithm` with `config`.
+    pub fn from_search(
+        result: SearchOutcome,
+        algorithm: Algorithm,
+        config: &SearchConfig,
+    ) -> Outcome {
+        return match result.into_result(algorithm, config) {
+            Ok(solution) => Outcome::Solved(solution),
+            Err(message) => Outcome::Failed(message),
+        };
+    }
+}
+
+/// Two configurations solving the same board at the same time.
+pub struct Versus {
+    pub board: Board,
+    pub goal: GoalContext,
+    pub sides: [Contender; 2],
+    pub focus: usize, // the side whose settings the keys change
+    cancel: Arc<AtomicBool>,
+}
+
+impl Versus {
+    /// Pits `algorithm` with `config` (left) against the same algorithm with
+    /// the next heuristic (right).
+    pub fn new(
+        board: Board,
+        goal: &GoalContext,
+        algorithm: Algorithm,
+        config: SearchConfig,
+    ) -> Versus {
+        let right = SearchConfig {
+            heuristic: next_of(&Heuristic::ALL, config.heuristic),
+            ..config
+        };
+        return Versus {
+            board,
+            goal: goal.clone(),
+            sides: [
+                Contender::new(algorithm, config),
+                Contender::new(algorithm, right),
+            ],
+            focus: 0,
+            cancel: Arc::new(AtomicBool::new(false)),
+        };
+    }
+
+    /// Starts both searches, each on its own thread.
+    pub fn start(&mut self) {
+        self.stop();
+        self.cancel = Arc::new(AtomicBool::new(false));
+        for side in &mut self.sides {
+            let (tx, rx) = mpsc::channel();
+            let (board, goal, algorithm, config) =
+                (self.board, self.goal.clone(), side.algorithm, side.config);
+            let cancel = Arc::clone(&self.cancel);
+            thread::spawn(move || {
+                let mut observer = |_: &_| !cancel.load(Ordering::Relaxed);
+                let result = algorithm.solve_observed(board, &goal, config, &mut observer);
+                // nobody is listening any more once the comparison is closed
+                let _ = tx.send(result);
+            });
+            side.rx = Some(rx);
+            side.outcome = Outcome::Running;
+        }
+    }
+
+    /// Abandons the searches that are still running.
+    pub fn stop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        for side in &mut self.sides {
+            side.rx = None;
+        }
+    }
+
+    /// Collects the results of the searches that have finished.
+    pub fn poll(&mut self) {
+        for side in &mut self.sides {
+            let Some(rx) = &side.rx else {
+                continue;
+            };
+            side.outcome = match rx.try_recv() {
+                Ok(result) => Outcome::from_search(result, side.algorithm, &side.config),
+                Err(TryRecvError::Empty) => continue,
+                Err(TryRecvError::Disconnected) => {
+                    Outcome::Failed("Search thread panicked.".to_string())
+                }
+            };
+            side.rx = None;
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        return self.sides.iter().any(|side| side.rx.is_some());
+    }
+
+    pub fn switch_focus(&mut self) {
+        self.focus = 1 - self.focus;
+    }
+
+    pub fn cycle_algorithm(&mut self) {
+        let side = self.focused();
+        side.algorithm = next_of(&Algorithm::ALL, side.algorithm);
+    }
+
+    pub fn cycle_heuristic(&mut self) {
+        let side = self.focused();
+        side.config.heuristic = next_of(&Heuristic::ALL, side.config.heuristic);
+    }
+
+    pub fn cycle_weight(&mut self, weights: &[i64]) {
+        let side = self.focused();
+        side.config.weight = next_of(weights, side.config.weight);
+    }
+
+    // changing the settings invalidates the side's result
+    fn focused(&mut self) -> &mut Contender {
+        let side = &mut self.sides[self.focus];
+        side.outcome = Outcome::Waiting;
+        return side;
+    }
+}
+
+impl Drop for Versus {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl Contender {
+    fn new(algorithm: Algorithm, config: SearchConfig) -> Contender {
+        return Contender {
+            algorithm,
+            config,
+            outcome: Outcome::Waiting,
+            rx: None,
+        };
+    }
+
+    /// The settings as one line, e.g. "a-star | manhattan | weight 1".
+    pub fn label(&self) -> String {
+        let mut label = self.algorithm.name().to_string();
+        if self.algorithm.is_informed() {
+            label.push_str(&format!(" | {}", self.config.heuristic));
+        }
+        if self.algorithm == Algorithm::AStar {
+            label.push_str(&format!(" | weight {}", self.config.weight));
+        }
+        return label;
+    }
+}
+
+// The item after `current` in `all`, wrapping around.
+fn next_of<T: Copy + PartialEq>(all: &[T], current: T) -> T {
+    let idx = all.iter().position(|x| *x == current).unwrap_or(0);
+    return all[(idx + 1) % all.len()];
+}
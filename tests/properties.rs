@@ -0,0 +1,190 @@
+#![deny(unused_variables)]
+#![deny(unused_imports)]
+
+use eight_puzzle_solver::{
+    a_star::{SearchConfig, TieBreak},
+    algorithms::Algorithm,
+    board::Board,
+    goal::{GoalContext, GoalPreset},
+    notation, parity,
+    symmetry::Symmetry,
+    tournament::{self, Budget, Entrant},
+    verify,
+};
+use proptest::prelude::*;
+
+// Any arrangement of the tiles of a `rows` x `cols` board, solvable or not.
+fn any_board(rows: usize, cols: usize) -> impl Strategy<Value = Board> {
+    return Just(parity::standard_goal(rows, cols))
+        .prop_shuffle()
+        .prop_map(move |cells| Board::from_cells(rows, cols, &cells));
+}
+
+// Any board of any shape from 2x2 to 4x4.
+fn any_shape() -> impl Strategy<Value = Board> {
+    return (2..=4usize, 2..=4usize).prop_flat_map(|(rows, cols)| any_board(rows, cols));
+}
+
+// 8-puzzles the standard goal can be reached from.
+fn solvable_board() -> impl Strategy<Value = Board> {
+    return any_board(3, 3).prop_filter("unsolvable", |board| {
+        parity::is_solvable(board, &Board::goal(3, 3))
+    });
+}
+
+// Positions of the cells that differ between two boards of the same shape.
+fn differing_cells(a: &Board, b: &Board) -> Vec<(usize, usize)> {
+    return (0..a.rows())
+        .flat_map(|r| (0..a.cols()).map(move |c| (r, c)))
+        .filter(|&(r, c)| a.get(r, c) != b.get(r, c))
+        .collect();
+}
+
+proptest! {
+    #[test]
+    fn successors_differ_by_one_legal_swap(board in any_shape()) {
+        let blank = board.find_empty_cell().unwrap();
+        for neighbor in board.get_possible_next_states() {
+            let next = neighbor.board;
+            let target = next.find_empty_cell().unwrap();
+            // the blank moved one cell the way the move says
+            prop_assert_eq!(neighbor.mv.target(blank, board.rows(), board.cols()), Some(target));
+            // and swapped with the tile there, nothing else changed
+            let mut changed = differing_cells(&board, &next);
+            changed.sort();
+            let mut expected = vec![blank, target];
+            expected.sort();
+            prop_assert_eq!(changed, expected);
+            prop_assert_eq!(next.get(blank.0, blank.1), board.get(target.0, target.1));
+            prop_assert!(next.is_valid());
+        }
+    }
+
+    #[test]
+    fn distance_is_symmetric(
+        (a, b) in (2..=4usize, 2..=4usize)
+            .prop_flat_map(|(rows, cols)| (any_board(rows, cols), any_board(rows, cols)))
+    ) {
+        prop_assert_eq!(a.distance(&b), b.distance(&a));
+    }
+
+    #[test]
+    fn distance_is_zero_only_for_equal_boards(
+        (a, b) in (2..=4usize, 2..=4usize)
+            .prop_flat_map(|(rows, cols)| (any_board(rows, cols), any_board(rows, cols)))
+    ) {
+        prop_assert_eq!(a.distance(&a), 0);
+        prop_assert_eq!(a.distance(&b) == 0, a == b);
+    }
+
+    #[test]
+    fn moves_preserve_solvability(board in any_shape()) {
+        let goal = Board::goal(board.rows(), board.cols());
+        let solvable = parity::is_solvable(&board, &goal);
+        for neighbor in board.get_possible_next_states() {
+            prop_assert_eq!(parity::is_solvable(&neighbor.board, &goal), solvable);
+        }
+    }
+
+    #[test]
+    fn solvable_boards_get_verified_solutions(board in solvable_board()) {
+        let goal = GoalContext::standard();
+        let solution = Algorithm::AStar.solve(board, &goal, SearchConfig::default()).solution();
+        prop_assert!(solution.is_some());
+        let solution = solution.unwrap();
+        prop_assert_eq!(verify::verify(&board, &solution.path), Ok(()));
+        // every move changes the parity of the blank's position
+        let start = board.find_empty_cell().unwrap();
+        let end = goal.goal().find_empty_cell().unwrap();
+        let blank_parity = (start.0 + start.1 + end.0 + end.1) % 2;
+        prop_assert_eq!(solution.moves() % 2, blank_parity);
+    }
+
+    #[test]
+    fn four_quarter_turns_are_no_turn(board in any_shape()) {
+        let turned = board.rotate_cw();
+        prop_assert_eq!((turned.rows(), turned.cols()), (board.cols(), board.rows()));
+        prop_assert_eq!(turned.rotate_cw().rotate_cw().rotate_cw(), board);
+        // the top-left corner ends up top-right
+        prop_assert_eq!(turned.get(0, turned.cols() - 1), board.get(0, 0));
+    }
+
+    #[test]
+    fn mirroring_twice_is_no_mirroring(board in any_shape()) {
+        let mirrored = board.mirror();
+        prop_assert_eq!(mirrored.mirror(), board);
+        prop_assert_eq!(mirrored.get(0, mirrored.cols() - 1), board.get(0, 0));
+    }
+
+    #[test]
+    fn turned_moves_replay_on_turned_boards(board in any_shape()) {
+        for symmetry in [Symmetry::RotateCw, Symmetry::Mirror] {
+            for neighbor in board.get_possible_next_states() {
+                prop_assert_eq!(
+                    symmetry.mv(neighbor.mv).apply(&symmetry.board(&board)),
+                    Some(symmetry.board(&neighbor.board))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn turned_solutions_solve_turned_puzzles(board in solvable_board()) {
+        let goal = GoalContext::standard();
+        let config = SearchConfig::default();
+        let solution = Algorithm::AStar.solve(board, &goal, config).solution().unwrap();
+        for symmetry in [Symmetry::RotateCw, Symmetry::Mirror] {
+            let turned_goal = symmetry.goal(&goal);
+            let turned = symmetry.solution(&solution, &turned_goal, config.heuristic);
+            let start = symmetry.board(&board);
+            prop_assert_eq!(verify::verify_with_goal(&start, &turned.path, &turned_goal), Ok(()));
+            prop_assert_eq!(notation::apply_moves(&start, &turned.directions), Some(turned.path.clone()));
+            // the same puzzle turned over is no shorter to solve
+            let searched = Algorithm::AStar.solve(start, &turned_goal, config).solution().unwrap();
+            prop_assert_eq!(searched.moves(), solution.moves());
+        }
+    }
+
+    #[test]
+    fn skipping_symmetric_twins_keeps_solutions_optimal(board in any_board(3, 3)) {
+        // the blank in the middle leaves the goal the same under every turn
+        let goal = GoalContext::for_preset(GoalPreset::Spiral, 3, 3);
+        prop_assume!(goal.is_reachable(&board));
+        let config = SearchConfig::default();
+        let plain = Algorithm::AStar.solve(board, &goal, config).solution().unwrap();
+        let reduced = Algorithm::AStar
+            .solve(board, &goal, SearchConfig { symmetry: true, ..config })
+            .solution()
+            .unwrap();
+        prop_assert_eq!(verify::verify_with_goal(&board, &reduced.path, &goal), Ok(()));
+        prop_assert_eq!(reduced.moves(), plain.moves());
+    }
+
+    #[test]
+    fn a_star_solves_are_optimal_in_tournaments(board in solvable_board()) {
+        let entrants: Vec<Entrant> = ["human:manhattan", "a-star:manhattan"]
+            .iter()
+            .map(|e| e.parse().unwrap())
+            .collect();
+        let goal = GoalContext::standard();
+        let rows = tournament::play(&entrants, &[board], &goal, SearchConfig::default(), Budget::default());
+        // without exact distances, the shortest solve sets the bar
+        let standings = tournament::standings(&rows, |_| None);
+        let a_star = standings.iter().find(|s| s.entrant == entrants[1]).unwrap();
+        prop_assert_eq!((a_star.solved, a_star.optimal), (1, 1));
+        prop_assert!(standings.windows(2).all(|w| w[0].points() >= w[1].points()));
+    }
+
+    #[test]
+    fn tie_breaking_keeps_solutions_optimal(board in solvable_board()) {
+        let goal = GoalContext::standard();
+        let moves = |tie_break: TieBreak| {
+            let config = SearchConfig { tie_break, ..SearchConfig::default() };
+            return Algorithm::AStar.solve(board, &goal, config).solution().unwrap().moves();
+        };
+        let shortest = moves(TieBreak::SmallerH);
+        for tie_break in TieBreak::ALL {
+            prop_assert_eq!(moves(tie_break), shortest);
+        }
+    }
+}